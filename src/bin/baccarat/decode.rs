@@ -0,0 +1,75 @@
+extern crate poker;
+
+use poker::cards::traits::shoe::Shoe as ShoeTrait;
+use poker::games::baccarat::decode::{decode, point_values};
+use poker::games::baccarat::game::Game;
+use poker::games::baccarat::shoe::Shoe;
+use std::time::{Duration, Instant};
+
+const DECK_NUM: usize = 8;
+const ORDER_NUM: usize = 64;
+const RESERVE: usize = 14;
+const SECS: u64 = 3;
+
+// Decodes the same candidate orders over and over, the way a search does.
+// Their point values are worked out once, as a search would keep them.
+fn decode_shoes(orders: &[Vec<u8>]) -> (u64, u64) {
+    let (mut shoe_num, mut round_num) = (0, 0);
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_secs(SECS) {
+        for values in orders.iter() {
+            round_num += decode(values, RESERVE).count() as u64;
+        }
+        shoe_num += orders.len() as u64;
+    }
+    (shoe_num, round_num)
+}
+
+// The same orders dealt by a reused `Game`, for comparison. A cloned shoe
+// shares its order, so loading one copies no cards.
+fn play_shoes(shoes: &[Shoe]) -> (u64, u64) {
+    let (mut shoe_num, mut round_num) = (0, 0);
+    let mut game = Game::new(DECK_NUM);
+    game.skip_results = true;
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_secs(SECS) {
+        for shoe in shoes.iter() {
+            game.shoe.clone_from(shoe);
+            while game.shoe.remaining() > RESERVE && game.play_one_round().is_ok() {
+                round_num += 1;
+            }
+        }
+        shoe_num += shoes.len() as u64;
+    }
+    (shoe_num, round_num)
+}
+
+fn main() {
+    let shoes: Vec<Shoe> = (0..ORDER_NUM)
+        .map(|seed| {
+            let mut shoe = Shoe::new(DECK_NUM);
+            shoe.shuffle_with_seed(seed as u64);
+            shoe
+        })
+        .collect();
+    let orders: Vec<Vec<u8>> = shoes
+        .iter()
+        .map(|shoe| point_values(shoe.cards()))
+        .collect();
+
+    let (shoe_num, round_num) = decode_shoes(&orders);
+    let decoded = shoe_num as f64 / SECS as f64;
+    println!(
+        "decode: {:.0} shoes, {:.0} rounds per secs.",
+        decoded,
+        round_num as f64 / SECS as f64
+    );
+    let (shoe_num, round_num) = play_shoes(&shoes);
+    let played = shoe_num as f64 / SECS as f64;
+    println!(
+        "game: {:.0} shoes, {:.0} rounds per secs.",
+        played,
+        round_num as f64 / SECS as f64
+    );
+    println!("decode: {:.1}x.", decoded / played);
+}
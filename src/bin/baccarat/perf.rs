@@ -2,39 +2,70 @@ extern crate poker;
 
 use poker::games::baccarat::game::Game;
 use std::{
-    sync::atomic::{AtomicU32, Ordering},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
     thread,
     time::Duration,
 };
 
 static COUNT: AtomicU32 = AtomicU32::new(0);
+static STOP: AtomicBool = AtomicBool::new(false);
 
-fn play_loop() {
-    const DECK_NUM: usize = 8;
-    loop {
-        let mut game = Game::new(DECK_NUM);
-        for _ in 0..70 {
-            game.play_one_round();
-            COUNT.fetch_add(1, Ordering::SeqCst);
+const DECK_NUM: usize = 8;
+const ROUND_NUM: usize = 70;
+const SECS: u32 = 5;
+
+fn play_rounds(game: &mut Game) {
+    for _ in 0..ROUND_NUM {
+        if game.play_one_round().is_err() {
+            break;
         }
+        COUNT.fetch_add(1, Ordering::SeqCst);
     }
 }
 
-fn eval() {
-    let count = COUNT.fetch_and(0, Ordering::Acquire);
-    println!("{} games per secs.", count);
+// A new shuffled game for every shoe, keeping every result.
+fn new_game_loop() {
+    while !STOP.load(Ordering::Relaxed) {
+        let mut game = Game::new(DECK_NUM);
+        game.shoe.shuffle();
+        play_rounds(&mut game);
+    }
 }
 
-fn eval_loop() {
-    loop {
-        thread::sleep(Duration::from_secs(1));
-        eval();
+// One game reset in place, keeping no results.
+fn reused_game_loop() {
+    let mut game = Game::new(DECK_NUM);
+    game.skip_results = true;
+    while !STOP.load(Ordering::Relaxed) {
+        game.reset();
+        play_rounds(&mut game);
     }
 }
 
-fn main() {
+// Games per second, averaged over SECS.
+fn eval(name: &str, play_loop: fn()) -> f64 {
+    STOP.store(false, Ordering::Relaxed);
+    COUNT.store(0, Ordering::SeqCst);
     let play_handle = thread::spawn(play_loop);
-    let eval_handle = thread::spawn(eval_loop);
+    let mut total = 0;
+    for _ in 0..SECS {
+        thread::sleep(Duration::from_secs(1));
+        let count = COUNT.fetch_and(0, Ordering::Acquire);
+        println!("{}: {} games per secs.", name, count);
+        total += count;
+    }
+    STOP.store(true, Ordering::Relaxed);
     play_handle.join().unwrap();
-    eval_handle.join().unwrap();
+    total as f64 / SECS as f64
+}
+
+fn main() {
+    let new_game = eval("new game", new_game_loop);
+    let reused_game = eval("reused game", reused_game_loop);
+    println!(
+        "reused game: {:.0} vs {:.0} games per secs, {:.2}x.",
+        reused_game,
+        new_game,
+        reused_game / new_game
+    );
 }
@@ -1,53 +1,110 @@
-use std::hash::{DefaultHasher, Hash, Hasher};
+use std::fmt;
+use std::str::FromStr;
 
+use super::notation::ParseError;
 use super::number::{all_numbers, Number, ALL_NUMBER_NUM};
 use super::suit::{all_suits, Suit, ALL_SUIT_NUM};
+use super::traits::deck::FullDeck;
 use super::traits::hash::{UniqueHash, UniqueHashT, ValueHash, ValueHashT};
 use super::traits::order::Order;
-use super::traits::value::Value;
 
 pub const ALL_CARDS_NUM: usize = ALL_SUIT_NUM * ALL_NUMBER_NUM;
+pub const ALL_JOKERS_NUM: usize = 2;
+
+// The suit only tells the black joker from the red one.
+pub static JOKER_SUITS: [Suit; ALL_JOKERS_NUM] = [Suit::Spade, Suit::Heart];
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     pub suit: Suit,
     pub number: Number,
+    // identifies this instance, not the card, so a loaded card gets its own
+    #[cfg_attr(
+        feature = "serde",
+        serde(skip, default = "<Card as UniqueHash>::calculate_unique_hash")
+    )]
     unique_hash: UniqueHashT,
 }
 
 impl Card {
     pub fn new(suit: Suit, number: Number) -> Self {
         Card {
-            suit: suit,
-            number: number,
+            suit,
+            number,
             unique_hash: Self::calculate_unique_hash(),
         }
     }
+
+    // 0 is the black joker, 1 the red one, there are no others.
+    pub fn joker(idx: usize) -> Option<Self> {
+        let suit = JOKER_SUITS.get(idx)?;
+        Some(Card::new(suit.clone(), Number::Joker))
+    }
+
+    pub fn is_joker(&self) -> bool {
+        matches!(self.number, Number::Joker)
+    }
 }
 
 impl Order for Card {
     fn order(&self) -> u64 {
         // Spade Ace and Heart Ace can be seen as the same order.
         // But, here we give every card a unique order.
+        if self.is_joker() {
+            // jokers come after the 52 regular cards
+            return (ALL_CARDS_NUM as u64) + self.suit.order();
+        }
         self.suit.order() * (ALL_NUMBER_NUM as u64) + self.number.order()
     }
 }
 
-impl Value for Card {
-    fn value(&self) -> i32 {
-        // We take the number as the card value.
-        self.number.value()
+// "As", or "A♠" with {:#}. Jokers are "Xs" (black) and "Xh" (red).
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number)?;
+        fmt::Display::fmt(&self.suit, f)
+    }
+}
+
+impl fmt::Debug for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().collect();
+        if chars.len() != 2 {
+            return Err(ParseError::Length(s.to_string()));
+        }
+        let number: Number = chars[0]
+            .to_string()
+            .parse()
+            .map_err(|_| ParseError::Number(s.to_string()))?;
+        let suit: Suit = chars[1]
+            .to_string()
+            .parse()
+            .map_err(|_| ParseError::Suit(s.to_string()))?;
+        if let Number::Joker = number {
+            return JOKER_SUITS
+                .iter()
+                .position(|joker_suit| joker_suit.order() == suit.order())
+                .and_then(Card::joker)
+                .ok_or_else(|| ParseError::Suit(s.to_string()));
+        }
+        Ok(Card::new(suit, number))
     }
 }
 
 impl ValueHash for Card {
     fn value_hash(&self) -> ValueHashT {
-        // Every card should have different value.
-        // However, multiple card in a deck with the same suit and number are have same values.
-        let mut hasher = DefaultHasher::new();
-        self.suit.hash(&mut hasher);
-        self.number.hash(&mut hasher);
-        hasher.finish() as ValueHashT
+        // Multiple cards of a shoe with the same suit and number share it,
+        // jokers come after the 52 regular cards like their order.
+        self.order() as ValueHashT
     }
 }
 
@@ -60,6 +117,12 @@ impl UniqueHash for Card {
     }
 }
 
+impl FullDeck for Card {
+    fn full_deck() -> &'static [Card] {
+        all_cards()
+    }
+}
+
 pub fn all_cards() -> &'static [Card; ALL_CARDS_NUM] {
     lazy_static! {
         static ref ALL_CARDS: [Card; ALL_CARDS_NUM] = {
@@ -77,6 +140,15 @@ pub fn all_cards() -> &'static [Card; ALL_CARDS_NUM] {
     &ALL_CARDS
 }
 
+pub fn all_jokers() -> &'static [Card; ALL_JOKERS_NUM] {
+    lazy_static! {
+        static ref ALL_JOKERS: [Card; ALL_JOKERS_NUM] = JOKER_SUITS
+            .clone()
+            .map(|suit| Card::new(suit, Number::Joker));
+    }
+    &ALL_JOKERS
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -96,15 +168,27 @@ mod tests {
 
     #[test]
     fn card_value_test() {
+        // a card only has a value under a game's count
+        use crate::cards::valuation::{RankValuation, NUMBER_ORDER};
+
         let card1 = Card::new(Suit::Spade, Number::Ace);
         let card2 = Card::new(Suit::Spade, Number::Ace);
-        assert_eq!(card1.value(), card2.value());
+        assert_eq!(
+            NUMBER_ORDER.card_value(&card1),
+            NUMBER_ORDER.card_value(&card2)
+        );
         let card1 = Card::new(Suit::Spade, Number::Ace);
         let card2 = Card::new(Suit::Heart, Number::Ace);
-        assert_eq!(card1.value(), card2.value());
+        assert_eq!(
+            NUMBER_ORDER.card_value(&card1),
+            NUMBER_ORDER.card_value(&card2)
+        );
         let card1 = Card::new(Suit::Spade, Number::Ace);
         let card2 = Card::new(Suit::Spade, Number::Two);
-        assert_ne!(card1.value(), card2.value());
+        assert_ne!(
+            NUMBER_ORDER.card_value(&card1),
+            NUMBER_ORDER.card_value(&card2)
+        );
     }
 
     #[test]
@@ -116,6 +200,10 @@ mod tests {
         let card1 = Card::new(Suit::Spade, Number::Ace);
         let card2 = Card::new(Suit::Heart, Number::Ace);
         assert_ne!(card1.value_hash(), card2.value_hash());
+        // dense and stable
+        for (idx, card) in all_cards().iter().enumerate() {
+            assert_eq!(card.value_hash(), idx as ValueHashT);
+        }
         // unique_hash
         let card1 = Card::new(Suit::Spade, Number::Ace);
         let card2 = Card::new(Suit::Spade, Number::Ace);
@@ -125,6 +213,39 @@ mod tests {
         assert_ne!(card1.unique_hash(), card2.unique_hash());
     }
 
+    #[test]
+    fn card_joker_test() {
+        let jokers = all_jokers();
+        assert!(jokers.iter().all(|joker| joker.is_joker()));
+        assert!(all_cards().iter().all(|card| !card.is_joker()));
+        assert_ne!(jokers[0].value_hash(), jokers[1].value_hash());
+        assert_eq!(jokers[0].order(), ALL_CARDS_NUM as u64);
+        assert_eq!(jokers[1].order(), ALL_CARDS_NUM as u64 + 1);
+        assert_eq!(Card::joker(1).unwrap().value_hash(), jokers[1].value_hash());
+        assert!(Card::joker(ALL_JOKERS_NUM).is_none());
+    }
+
+    #[test]
+    fn card_notation_test() {
+        for card in all_cards().iter().chain(all_jokers().iter()) {
+            let parsed: Card = card.to_string().parse().unwrap();
+            assert_eq!(parsed.order(), card.order());
+            let parsed: Card = format!("{:#}", card).parse().unwrap();
+            assert_eq!(parsed.order(), card.order());
+        }
+        assert_eq!(Card::new(Suit::Diamond, Number::Ten).to_string(), "Td");
+        assert_eq!(format!("{:?}", Card::new(Suit::Club, Number::Seven)), "7c");
+        assert_eq!(format!("{:#}", Card::joker(1).unwrap()), "X♥");
+        assert_eq!(
+            "Xd".parse::<Card>().err(),
+            Some(ParseError::Suit("Xd".to_string()))
+        );
+        assert_eq!(
+            "Asd".parse::<Card>().err(),
+            Some(ParseError::Length("Asd".to_string()))
+        );
+    }
+
     #[test]
     fn card_unique_hash_test() {
         // Every card has unique value.
@@ -1,20 +1,74 @@
-use super::card::{all_cards, Card, ALL_CARDS_NUM};
-use super::traits::deck::Deck as DeckTrait;
+use super::card::{all_jokers, Card};
+use super::traits::deck::{Deck as DeckTrait, FullDeck};
+use super::traits::discard::Discard;
+use super::traits::hash::{ValueHash, ValueHashT};
+use crate::error::Error;
 
 #[derive(Clone)]
-pub struct Deck {
-    pub cards: [Card; ALL_CARDS_NUM],
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Deck<C = Card> {
+    pub cards: Vec<C>,
+    card_idx: usize,  // next draw position
+    discards: Vec<C>, // drawn and thrown away
 }
 
-impl Deck {
-    pub fn new() -> Deck {
+impl<C: FullDeck + Clone> Default for Deck<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: FullDeck + Clone> Deck<C> {
+    pub fn new() -> Deck<C> {
+        Deck::from_cards(C::full_deck().to_vec())
+    }
+}
+
+impl Deck<Card> {
+    // 53 or 54 cards, the jokers going after the regular ones. There are
+    // only two jokers, so asking for more gives both.
+    pub fn with_jokers(joker_num: usize) -> Deck {
+        let mut deck = Deck::new();
+        let jokers = all_jokers().iter().take(joker_num);
+        deck.cards.extend(jokers.cloned());
+        deck
+    }
+}
+
+impl<C: Clone> Deck<C> {
+    pub fn from_cards(cards: Vec<C>) -> Deck<C> {
         Deck {
-            cards: all_cards().clone(),
+            cards,
+            card_idx: 0,
+            discards: Vec::new(),
         }
     }
+
+    // Puts every card back, nothing drawn and nothing discarded.
+    pub fn reset(&mut self) {
+        self.card_idx = 0;
+        self.discards.clear();
+    }
+
+    pub fn draw(&mut self) -> Result<C, Error> {
+        let card = self
+            .cards
+            .get(self.card_idx)
+            .ok_or(Error::Exhausted {
+                wanted: 1,
+                remaining: 0,
+            })?
+            .clone();
+        self.card_idx += 1;
+        Ok(card)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.cards.len() - self.card_idx
+    }
 }
 
-impl DeckTrait for Deck {
+impl<C> DeckTrait for Deck<C> {
     fn card_num(&self) -> usize {
         self.cards.len()
     }
@@ -23,12 +77,58 @@ impl DeckTrait for Deck {
     }
 }
 
+impl<C: ValueHash + Clone> Discard<C> for Deck<C> {
+    fn discard(&mut self, card: C) -> Result<(), Error> {
+        let value_hash = card.value_hash();
+        let same = |other: &&C| other.value_hash() == value_hash;
+        let dealt = self.cards[..self.card_idx].iter().filter(same).count();
+        if dealt <= self.discards.iter().filter(same).count() {
+            return Err(Error::NotDealt(value_hash));
+        }
+        self.discards.push(card);
+        Ok(())
+    }
+
+    fn discards(&self) -> &[C] {
+        &self.discards
+    }
+
+    fn reshuffle_discards(&mut self) {
+        // cards drawn and still held stay in front, one dealt copy leaving
+        // play per discard
+        let mut discarded: Vec<ValueHashT> =
+            self.discards.iter().map(|card| card.value_hash()).collect();
+        let mut cards = Vec::with_capacity(self.cards.len());
+        for card in self.cards[..self.card_idx].iter() {
+            let value_hash = card.value_hash();
+            match discarded.iter().position(|hash| *hash == value_hash) {
+                Some(pos) => {
+                    discarded.swap_remove(pos);
+                }
+                None => cards.push(card.clone()),
+            }
+        }
+        let held_num = cards.len();
+        cards.extend(self.cards[self.card_idx..].iter().cloned());
+        let stub_num = cards.len();
+        cards.append(&mut self.discards);
+        self.cards = cards;
+        self.card_idx = held_num;
+        self.shuffle_from(stub_num);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cards::traits::hash::{UniqueHash, ValueHash};
+    use crate::cards::card::ALL_CARDS_NUM;
+    use crate::cards::id::CardId;
+    use crate::cards::traits::hash::{UniqueHash, UniqueHashT, ValueHash, ValueHashT};
+    use crate::cards::traits::order::Order;
     use std::collections::HashSet;
 
+    type Deck = super::Deck<Card>;
+
     #[test]
     fn deck_shuffle_test() {
         // card uniqueness
@@ -75,4 +175,162 @@ mod tests {
         }
         assert!(diff);
     }
+
+    #[test]
+    fn deck_jokers_test() {
+        assert_eq!(Deck::new().cards.len(), ALL_CARDS_NUM);
+        let deck = Deck::with_jokers(1);
+        assert_eq!(deck.cards.len(), ALL_CARDS_NUM + 1);
+        assert!(deck.cards[ALL_CARDS_NUM].is_joker());
+        let mut deck = Deck::with_jokers(2);
+        assert_eq!(deck.cards.len(), ALL_CARDS_NUM + 2);
+        deck.shuffle();
+        assert_eq!(deck.cards.iter().filter(|card| card.is_joker()).count(), 2);
+        assert_eq!(Deck::with_jokers(3).cards.len(), ALL_CARDS_NUM + 2);
+    }
+
+    #[test]
+    fn deck_cut_test() {
+        let mut deck = Deck::new();
+        deck.cut(13);
+        let orders: Vec<u64> = deck.cards.iter().map(|card| card.order()).collect();
+        let expected: Vec<u64> = (13..ALL_CARDS_NUM as u64).chain(0..13).collect();
+        assert_eq!(orders, expected);
+    }
+
+    #[test]
+    fn deck_discard_test() {
+        let mut deck = Deck::new();
+        deck.shuffle();
+        let held: Vec<Card> = (0..40).map(|_| deck.draw().unwrap()).collect();
+        for card in held[..30].iter() {
+            deck.discard(card.clone()).unwrap();
+        }
+        // a card still in the stub, or one already discarded
+        let undealt = deck.cards[40].clone();
+        assert_eq!(
+            deck.discard(undealt.clone()),
+            Err(Error::NotDealt(undealt.value_hash()))
+        );
+        assert!(deck.discard(held[0].clone()).is_err());
+        let stub: Vec<Card> = deck.cards[40..].to_vec();
+        assert_eq!(deck.remaining(), 12);
+        assert_eq!(deck.discards().len(), 30);
+
+        deck.reshuffle_discards();
+        assert_eq!(deck.cards.len(), ALL_CARDS_NUM);
+        assert_eq!(deck.remaining(), 42);
+        assert!(deck.discards().is_empty());
+        // the ten held cards are out of play, the stub comes first
+        for card in held[30..].iter() {
+            assert!(deck.cards[..10]
+                .iter()
+                .any(|held_card| held_card.order() == card.order()));
+        }
+        for card in stub.iter() {
+            assert_eq!(deck.draw().unwrap().order(), card.order());
+        }
+        let mut rest: Vec<u64> = (0..30).map(|_| deck.draw().unwrap().order()).collect();
+        rest.sort();
+        let mut discarded: Vec<u64> = held[..30].iter().map(|card| card.order()).collect();
+        discarded.sort();
+        assert_eq!(rest, discarded);
+        assert_eq!(
+            deck.draw().map(|card| card.order()),
+            Err(Error::Exhausted {
+                wanted: 1,
+                remaining: 0
+            })
+        );
+    }
+
+    #[test]
+    fn deck_discard_copies_test() {
+        // two copies of each ace, six of them drawn and held
+        let aces: Vec<Card> = Deck::new()
+            .cards
+            .into_iter()
+            .filter(|card| card.number.order() == 0)
+            .collect();
+        let mut deck = Deck::from_cards([aces.clone(), aces].concat());
+        let held: Vec<Card> = (0..6).map(|_| deck.draw().unwrap()).collect();
+        // one copy each of the two aces held twice goes back
+        deck.discard(held[0].clone()).unwrap();
+        deck.discard(held[1].clone()).unwrap();
+
+        deck.reshuffle_discards();
+        assert_eq!(deck.cards.len(), 8);
+        assert_eq!(deck.remaining(), 4);
+        // the other copies are still held
+        let mut still_held: Vec<u64> = deck.cards[..4].iter().map(|card| card.order()).collect();
+        still_held.sort();
+        let mut expected: Vec<u64> = held[2..].iter().map(|card| card.order()).collect();
+        expected.sort();
+        assert_eq!(still_held, expected);
+    }
+
+    // A bare card id, to deal something other than `Card`.
+    #[derive(Clone)]
+    struct Pip {
+        id: CardId,
+        unique_hash: UniqueHashT,
+    }
+
+    impl ValueHash for Pip {
+        fn value_hash(&self) -> ValueHashT {
+            self.id.value_hash()
+        }
+    }
+
+    impl UniqueHash for Pip {
+        fn unique_hash(&self) -> UniqueHashT {
+            self.unique_hash
+        }
+        fn set_unique_hash(&mut self, hash: UniqueHashT) {
+            self.unique_hash = hash;
+        }
+    }
+
+    impl FullDeck for Pip {
+        fn full_deck() -> &'static [Pip] {
+            lazy_static! {
+                static ref PIPS: Vec<Pip> = (0..ALL_CARDS_NUM as u8)
+                    .map(|id| Pip {
+                        id: CardId(id),
+                        unique_hash: 0,
+                    })
+                    .collect();
+            }
+            &PIPS
+        }
+    }
+
+    #[test]
+    fn deck_card_type_test() {
+        use crate::cards::shoe::Shoe;
+        use crate::cards::traits::shoe::Shoe as ShoeTrait;
+        use crate::games::baccarat::rule::id_value;
+
+        let mut deck = super::Deck::<Pip>::new();
+        assert_eq!(deck.cards.len(), ALL_CARDS_NUM);
+        // tens and faces are worth nothing in baccarat
+        let zeros = deck
+            .cards
+            .iter()
+            .filter(|pip| id_value(pip.id) == 0)
+            .count();
+        assert_eq!(zeros, 16);
+        let card = deck.draw().unwrap();
+        deck.discard(card).unwrap();
+        deck.reshuffle_discards();
+        assert_eq!(deck.remaining(), ALL_CARDS_NUM);
+
+        let mut shoe = Shoe::<Pip>::new(2);
+        shoe.shuffle();
+        let pip = shoe.draw().unwrap();
+        assert_eq!(shoe.remaining_count(&pip), 1);
+        assert_eq!(shoe.validate(), Ok(()));
+        let unique: HashSet<UniqueHashT> = shoe.cards().iter().map(|pip| pip.unique_hash).collect();
+        assert_eq!(unique.len(), ALL_CARDS_NUM * 2);
+    }
 }
@@ -0,0 +1,338 @@
+use std::fmt;
+use std::ops::{BitAnd, BitOr, Sub};
+
+use super::card::{Card, ALL_CARDS_NUM};
+use super::notation::{NUMBER_CHARS, SUIT_CHARS};
+use super::number::{Number, ALL_NUMBERS, ALL_NUMBER_NUM};
+use super::suit::{Suit, ALL_SUITS, ALL_SUIT_NUM};
+use super::traits::hash::{ValueHash, ValueHashT};
+use super::traits::order::Order;
+
+// A regular card as its order, suit * 13 + number, for loops that can't
+// afford cloning a `Card`. Jokers have none.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardId(pub u8);
+
+impl CardId {
+    // Jokers have no id, so this stays behind `TryFrom<&Card>`.
+    fn new(suit: &Suit, number: &Number) -> CardId {
+        debug_assert!(!matches!(number, Number::Joker));
+        CardId((suit.order() as usize * ALL_NUMBER_NUM + number.order() as usize) as u8)
+    }
+
+    pub fn suit_idx(self) -> usize {
+        self.0 as usize / ALL_NUMBER_NUM
+    }
+
+    pub fn number_idx(self) -> usize {
+        self.0 as usize % ALL_NUMBER_NUM
+    }
+
+    pub fn suit(self) -> Suit {
+        ALL_SUITS[self.suit_idx()].clone()
+    }
+
+    pub fn number(self) -> Number {
+        ALL_NUMBERS[self.number_idx()].clone()
+    }
+}
+
+impl ValueHash for CardId {
+    fn value_hash(&self) -> ValueHashT {
+        self.0 as ValueHashT
+    }
+}
+
+impl TryFrom<&Card> for CardId {
+    type Error = &'static str;
+
+    fn try_from(card: &Card) -> Result<Self, Self::Error> {
+        if card.is_joker() {
+            return Err("jokers have no card id");
+        }
+        Ok(CardId::new(&card.suit, &card.number))
+    }
+}
+
+impl From<CardId> for Card {
+    fn from(id: CardId) -> Self {
+        Card::new(id.suit(), id.number())
+    }
+}
+
+impl fmt::Display for CardId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            NUMBER_CHARS[self.number_idx()],
+            SUIT_CHARS[self.suit_idx()]
+        )
+    }
+}
+
+// A set of distinct cards, one bit per `CardId`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardSet(pub u64);
+
+impl CardSet {
+    pub fn new() -> CardSet {
+        CardSet(0)
+    }
+
+    // The 52 cards of a deck.
+    pub fn full() -> CardSet {
+        CardSet((1 << ALL_CARDS_NUM) - 1)
+    }
+
+    pub fn insert(&mut self, id: CardId) {
+        self.0 |= 1 << id.0;
+    }
+
+    pub fn remove(&mut self, id: CardId) {
+        self.0 &= !(1 << id.0);
+    }
+
+    pub fn contains(&self, id: CardId) -> bool {
+        self.0 & (1 << id.0) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // Lowest id first.
+    pub fn iter(&self) -> CardSetIter {
+        CardSetIter(self.0)
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = CardSet;
+
+    fn bitor(self, other: CardSet) -> CardSet {
+        CardSet(self.0 | other.0)
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = CardSet;
+
+    fn bitand(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & other.0)
+    }
+}
+
+impl Sub for CardSet {
+    type Output = CardSet;
+
+    fn sub(self, other: CardSet) -> CardSet {
+        CardSet(self.0 & !other.0)
+    }
+}
+
+impl FromIterator<CardId> for CardSet {
+    fn from_iter<I: IntoIterator<Item = CardId>>(ids: I) -> Self {
+        let mut set = CardSet::new();
+        for id in ids {
+            set.insert(id);
+        }
+        set
+    }
+}
+
+impl IntoIterator for CardSet {
+    type Item = CardId;
+    type IntoIter = CardSetIter;
+
+    fn into_iter(self) -> CardSetIter {
+        self.iter()
+    }
+}
+
+pub struct CardSetIter(u64);
+
+impl Iterator for CardSetIter {
+    type Item = CardId;
+
+    fn next(&mut self) -> Option<CardId> {
+        if self.0 == 0 {
+            return None;
+        }
+        let id = self.0.trailing_zeros() as u8;
+        self.0 &= self.0 - 1;
+        Some(CardId(id))
+    }
+}
+
+const LANE_BITS: u32 = 16;
+const LANE_MASK: u64 = (1 << ALL_NUMBER_NUM) - 1;
+
+// The same cards laid out one 16-bit lane per suit, bit `Number::order` in
+// each, so an evaluator gets a suit's numbers or all numbers in a few ops.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HandMask(pub u64);
+
+impl HandMask {
+    pub fn new() -> HandMask {
+        HandMask(0)
+    }
+
+    fn bit(id: CardId) -> u64 {
+        1 << (id.suit_idx() as u32 * LANE_BITS + id.number_idx() as u32)
+    }
+
+    pub fn insert(&mut self, id: CardId) {
+        self.0 |= Self::bit(id);
+    }
+
+    pub fn contains(&self, id: CardId) -> bool {
+        self.0 & Self::bit(id) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // Numbers held in the suit of order `suit_idx`.
+    pub fn suit(&self, suit_idx: usize) -> u16 {
+        ((self.0 >> (suit_idx as u32 * LANE_BITS)) & LANE_MASK) as u16
+    }
+
+    // Numbers held in any suit.
+    pub fn numbers(&self) -> u16 {
+        (0..ALL_SUIT_NUM).fold(0, |numbers, suit_idx| numbers | self.suit(suit_idx))
+    }
+}
+
+impl BitOr for HandMask {
+    type Output = HandMask;
+
+    fn bitor(self, other: HandMask) -> HandMask {
+        HandMask(self.0 | other.0)
+    }
+}
+
+impl BitAnd for HandMask {
+    type Output = HandMask;
+
+    fn bitand(self, other: HandMask) -> HandMask {
+        HandMask(self.0 & other.0)
+    }
+}
+
+impl From<CardId> for HandMask {
+    fn from(id: CardId) -> Self {
+        HandMask(Self::bit(id))
+    }
+}
+
+impl FromIterator<CardId> for HandMask {
+    fn from_iter<I: IntoIterator<Item = CardId>>(ids: I) -> Self {
+        let mut mask = HandMask::new();
+        for id in ids {
+            mask.insert(id);
+        }
+        mask
+    }
+}
+
+impl From<CardSet> for HandMask {
+    fn from(set: CardSet) -> Self {
+        set.iter().collect()
+    }
+}
+
+impl From<HandMask> for CardSet {
+    fn from(mask: HandMask) -> Self {
+        let mut set = CardSet::new();
+        for suit_idx in 0..ALL_SUIT_NUM {
+            set.0 |= (mask.suit(suit_idx) as u64) << (suit_idx * ALL_NUMBER_NUM);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::{all_cards, all_jokers};
+
+    #[test]
+    fn card_id_test() {
+        for card in all_cards() {
+            let id = CardId::try_from(card).unwrap();
+            assert_eq!(id.0 as u64, card.order());
+            assert_eq!(id.value_hash(), card.value_hash());
+            assert_eq!(id.to_string(), card.to_string());
+            let back: Card = id.into();
+            assert_eq!(back.order(), card.order());
+        }
+        assert!(CardId::try_from(&all_jokers()[0]).is_err());
+        assert_eq!(std::mem::size_of::<CardId>(), 1);
+    }
+
+    #[test]
+    fn card_set_test() {
+        let ids = |cards: &[&str]| -> Vec<CardId> {
+            cards
+                .iter()
+                .map(|card| CardId::try_from(&card.parse::<Card>().unwrap()).unwrap())
+                .collect()
+        };
+        let hand: CardSet = ids(&["As", "Kd", "7c"]).into_iter().collect();
+        let board: CardSet = ids(&["Kd", "2h"]).into_iter().collect();
+        assert_eq!(hand.len(), 3);
+        assert!(hand.contains(ids(&["7c"])[0]));
+        assert!(!hand.contains(ids(&["7d"])[0]));
+        assert_eq!((hand | board).len(), 4);
+        assert_eq!((hand & board).iter().collect::<Vec<CardId>>(), ids(&["Kd"]));
+        assert_eq!(
+            (hand - board).iter().collect::<Vec<CardId>>(),
+            ids(&["As", "7c"])
+        );
+        assert_eq!(CardSet::full().len(), ALL_CARDS_NUM);
+        assert_eq!((CardSet::full() - hand).len(), ALL_CARDS_NUM - 3);
+
+        let mut set = hand;
+        set.remove(ids(&["As"])[0]);
+        set.remove(ids(&["As"])[0]);
+        assert_eq!(set.len(), 2);
+        assert!(CardSet::new().is_empty());
+    }
+
+    #[test]
+    fn hand_mask_test() {
+        let cards: Vec<CardId> = ["As", "Ks", "Td", "Tc"]
+            .iter()
+            .map(|card| CardId::try_from(&card.parse::<Card>().unwrap()).unwrap())
+            .collect();
+        let mask: HandMask = cards.iter().copied().collect();
+        assert_eq!(mask.len(), 4);
+        assert_eq!(mask.suit(0), 1 | (1 << 12));
+        assert_eq!(mask.suit(1), 0);
+        assert_eq!(mask.numbers(), 1 | (1 << 9) | (1 << 12));
+        assert!(cards.iter().all(|id| mask.contains(*id)));
+
+        // the two layouts hold the same cards
+        let set: CardSet = cards.iter().copied().collect();
+        assert_eq!(CardSet::from(mask), set);
+        assert_eq!(HandMask::from(set), mask);
+        assert_eq!(HandMask::from(CardSet::full()).len(), ALL_CARDS_NUM);
+        assert_eq!(
+            CardSet::from(HandMask::from(CardSet::full())),
+            CardSet::full()
+        );
+    }
+}
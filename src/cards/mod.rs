@@ -1,6 +1,10 @@
 pub mod card;
 pub mod deck;
+pub mod id;
+pub mod notation;
 pub mod number;
 pub mod shoe;
 pub mod suit;
 pub mod traits;
+pub mod valuation;
+pub mod wild;
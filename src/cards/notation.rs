@@ -0,0 +1,183 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::card::Card;
+use super::traits::hash::ValueHash;
+
+// Indexed by Number::order(), ten is "T" as in ui/common/const.slint.
+pub static NUMBER_CHARS: [char; 13] = [
+    'A', '2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K',
+];
+pub const JOKER_CHAR: char = 'X';
+// Indexed by Suit::order().
+pub static SUIT_CHARS: [char; 4] = ['s', 'h', 'd', 'c'];
+pub static SUIT_SYMBOLS: [char; 4] = ['♠', '♥', '♦', '♣'];
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ParseError {
+    Length(String), // a card takes exactly two characters
+    Number(String),
+    Suit(String),
+    Count { expected: usize, found: usize },
+    Duplicate(String),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::Length(token) => {
+                write!(f, "\"{}\" is not a card, cards look like As or Td", token)
+            }
+            ParseError::Number(token) => {
+                write!(f, "bad rank in \"{}\", ranks are A23456789TJQK", token)
+            }
+            ParseError::Suit(token) => {
+                write!(f, "bad suit in \"{}\", suits are shdc or ♠♥♦♣", token)
+            }
+            ParseError::Count { expected, found } => {
+                write!(f, "expected {} cards, found {}", expected, found)
+            }
+            ParseError::Duplicate(token) => write!(f, "\"{}\" appears twice", token),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_number_char(c: char, token: &str) -> Result<usize, ParseError> {
+    NUMBER_CHARS
+        .iter()
+        .position(|number| *number == c)
+        .ok_or_else(|| ParseError::Number(token.to_string()))
+}
+
+pub fn parse_suit_char(c: char, token: &str) -> Result<usize, ParseError> {
+    SUIT_CHARS
+        .iter()
+        .position(|suit| *suit == c)
+        .or_else(|| SUIT_SYMBOLS.iter().position(|suit| *suit == c))
+        .ok_or_else(|| ParseError::Suit(token.to_string()))
+}
+
+// Splits "As Kd", "As,Kd" or "AsKd" into card tokens.
+pub fn split_cards(s: &str) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    for word in s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|word| !word.is_empty())
+    {
+        let chars: Vec<char> = word.chars().collect();
+        if !chars.len().is_multiple_of(2) {
+            return Err(ParseError::Length(word.to_string()));
+        }
+        tokens.extend(
+            chars
+                .chunks(2)
+                .map(|chunk| chunk.iter().collect::<String>()),
+        );
+    }
+    Ok(tokens)
+}
+
+pub fn parse_cards<C: FromStr<Err = ParseError>>(s: &str) -> Result<Vec<C>, ParseError> {
+    split_cards(s)?
+        .iter()
+        .map(|token| token.parse::<C>())
+        .collect()
+}
+
+// Exactly N cards.
+pub fn parse_hand<C: FromStr<Err = ParseError>, const N: usize>(
+    s: &str,
+) -> Result<[C; N], ParseError> {
+    let cards = parse_cards::<C>(s)?;
+    let found = cards.len();
+    cards
+        .try_into()
+        .map_err(|_| ParseError::Count { expected: N, found })
+}
+
+// No card twice, as in a single deck.
+pub fn check_distinct<C: ValueHash + fmt::Display>(cards: &[C]) -> Result<(), ParseError> {
+    for (idx, card) in cards.iter().enumerate() {
+        if cards[..idx]
+            .iter()
+            .any(|other| other.value_hash() == card.value_hash())
+        {
+            return Err(ParseError::Duplicate(card.to_string()));
+        }
+    }
+    Ok(())
+}
+
+// A flop, turn or river board, or none yet.
+pub fn parse_board(s: &str) -> Result<Vec<Card>, ParseError> {
+    let cards = parse_cards::<Card>(s)?;
+    if ![0, 3, 4, 5].contains(&cards.len()) {
+        return Err(ParseError::Count {
+            expected: 5.min(cards.len().max(3)),
+            found: cards.len(),
+        });
+    }
+    check_distinct(&cards)?;
+    Ok(cards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::traits::order::Order;
+
+    #[test]
+    fn parse_cards_test() {
+        let cards = parse_cards::<Card>("As Td,7h2c").unwrap();
+        assert_eq!(cards.len(), 4);
+        assert_eq!(cards[1].number.order(), Number::Ten.order());
+        let [first, second] = parse_hand::<Card, 2>("A♠ K♥").unwrap();
+        assert_eq!(format!("{} {}", first, second), "As Kh");
+        assert_eq!(format!("{:#} {:#}", first, second), "A♠ K♥");
+
+        assert_eq!(
+            parse_cards::<Card>("As 10d").err(),
+            Some(ParseError::Length("10d".to_string()))
+        );
+        assert_eq!(
+            parse_cards::<Card>("as").err(),
+            Some(ParseError::Number("as".to_string()))
+        );
+        assert_eq!(
+            parse_cards::<Card>("AS").err(),
+            Some(ParseError::Suit("AS".to_string()))
+        );
+        assert_eq!(
+            parse_hand::<Card, 2>("As Kd Qc").err(),
+            Some(ParseError::Count {
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            ParseError::Suit("AS".to_string()).to_string(),
+            "bad suit in \"AS\", suits are shdc or ♠♥♦♣"
+        );
+    }
+
+    #[test]
+    fn parse_board_test() {
+        assert_eq!(parse_board("").unwrap().len(), 0);
+        assert_eq!(parse_board("AsKdQh").unwrap().len(), 3);
+        assert_eq!(parse_board("As Kd Qh 2c 3c").unwrap().len(), 5);
+        assert_eq!(
+            parse_board("As Kd").err(),
+            Some(ParseError::Count {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            parse_board("As Kd As").err(),
+            Some(ParseError::Duplicate("As".to_string()))
+        );
+    }
+}
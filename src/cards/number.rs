@@ -1,8 +1,13 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::notation::{parse_number_char, ParseError, JOKER_CHAR, NUMBER_CHARS};
 use super::traits::hash::{ValueHash, ValueHashT};
 use super::traits::{order::Order, value::Value};
 use std::hash::Hash;
 
 #[derive(Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Number {
     Ace,
     Two,
@@ -17,6 +22,7 @@ pub enum Number {
     Jack,
     Queen,
     King,
+    Joker, // not part of a standard deck, see `card::all_jokers`
 }
 
 impl Order for Number {
@@ -35,6 +41,7 @@ impl Order for Number {
             Number::Jack => 10,
             Number::Queen => 11,
             Number::King => 12,
+            Number::Joker => 13,
         }
     }
 }
@@ -51,6 +58,28 @@ impl ValueHash for Number {
     }
 }
 
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Number::Joker => write!(f, "{}", JOKER_CHAR),
+            _ => write!(f, "{}", NUMBER_CHARS[self.order() as usize]),
+        }
+    }
+}
+
+impl FromStr for Number {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(JOKER_CHAR), None) => Ok(Number::Joker),
+            (Some(c), None) => Ok(ALL_NUMBERS[parse_number_char(c, s)?].clone()),
+            _ => Err(ParseError::Number(s.to_string())),
+        }
+    }
+}
+
 pub const ALL_NUMBER_NUM: usize = 13;
 
 pub static ALL_NUMBERS: [Number; ALL_NUMBER_NUM] = [
@@ -82,7 +111,23 @@ mod tests {
         assert_ne!(Number::Two.order(), Number::Three.order());
         assert!(Number::Three.order() != Number::Four.order());
         assert!(Number::King.order() != Number::Ten.order());
+        for number in all_numbers() {
+            assert_ne!(Number::Joker.order(), number.order());
+        }
     }
+    #[test]
+    fn number_notation_test() {
+        for number in all_numbers() {
+            assert_eq!(
+                number.to_string().parse::<Number>().unwrap().order(),
+                number.order()
+            );
+        }
+        assert_eq!(Number::Ten.to_string(), "T");
+        assert!("10".parse::<Number>().is_err());
+        assert!("t".parse::<Number>().is_err());
+    }
+
     #[test]
     fn number_value_test() {
         let num1 = Number::Ace;
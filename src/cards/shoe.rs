@@ -1,24 +1,134 @@
-use std::collections::HashMap;
+use rand::seq::SliceRandom;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 
-use super::card::{all_cards, Card, ALL_CARDS_NUM};
-use super::traits::deck::Deck;
-use super::traits::hash::{UniqueHash, ValueHash, ValueHashT};
+use std::sync::Arc;
+
+use super::card::{Card, ALL_CARDS_NUM};
+use super::traits::deck::{Deck, FullDeck};
+use super::traits::discard::Discard;
+use super::traits::hash::{UniqueHash, ValueHash};
 use super::traits::shoe::Shoe as ShoeTrait;
+use crate::error::Error;
 
+// Several decks of any card type dealt as one, games pick theirs, e.g.
+// `games::baccarat::shoe::Shoe`. Clones share the card order until one of
+// them reorders it, dealing alone never copies it.
 #[derive(Clone)]
-pub struct Shoe {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(
+        try_from = "ShoeState<C>",
+        bound(
+            serialize = "C: serde::Serialize",
+            deserialize = "C: serde::Deserialize<'de> + ValueHash + Clone"
+        )
+    )
+)]
+pub struct Shoe<C = Card> {
     pub deck_num: usize,
-    pub cards: Vec<Card>,
-    card_idx: usize,                           // next draw position
-    card_pos: HashMap<ValueHashT, Vec<usize>>, // card's value_hash -> vector of card position
+    cards: Arc<Vec<C>>, // shared with clones until one reorders
+    card_idx: usize,    // next draw position
+    #[cfg_attr(feature = "serde", serde(skip))]
+    card_pos: Arc<CardPos>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    remaining_counts: CardCounts, // copies of each card from card_idx on
+    burned: Vec<C>,
+    discards: Vec<C>, // dealt and mucked
+}
+
+// Positions of each card in shoe order, indexed by value_hash.
+type CardPos = [Vec<usize>; ALL_CARDS_NUM];
+type CardCounts = [usize; ALL_CARDS_NUM];
+
+// What a saved shoe holds, card_pos is rebuilt from the cards on load.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(bound(deserialize = "C: serde::Deserialize<'de>"))]
+struct ShoeState<C> {
+    deck_num: usize,
+    cards: Vec<C>,
+    card_idx: usize,
+    #[serde(default)]
+    burned: Vec<C>,
+    #[serde(default)]
+    discards: Vec<C>,
+}
+
+#[cfg(feature = "serde")]
+impl<C: ValueHash> TryFrom<ShoeState<C>> for Shoe<C> {
+    type Error = String;
+
+    fn try_from(state: ShoeState<C>) -> Result<Self, Self::Error> {
+        if state.card_idx > state.cards.len() {
+            return Err(format!(
+                "card_idx {} is past the {} cards",
+                state.card_idx,
+                state.cards.len()
+            ));
+        }
+        if let Some(message) = composition_error(&state.cards, state.deck_num) {
+            return Err(message);
+        }
+        let card_pos = card_pos(&state.cards, state.deck_num);
+        let remaining_counts = card_counts(&state.cards[state.card_idx..]);
+        Ok(Shoe {
+            deck_num: state.deck_num,
+            cards: Arc::new(state.cards),
+            card_idx: state.card_idx,
+            card_pos: Arc::new(card_pos),
+            remaining_counts,
+            burned: state.burned,
+            discards: state.discards,
+        })
+    }
+}
+
+// Why `cards` can't be `deck_num` regular decks, if it can't. card_pos and
+// the counts only have room for the 52 regular cards.
+fn composition_error<C: ValueHash>(cards: &[C], deck_num: usize) -> Option<String> {
+    if cards.len() != deck_num * ALL_CARDS_NUM {
+        return Some(format!("{} cards for {} decks", cards.len(), deck_num));
+    }
+    let mut counts = [0; ALL_CARDS_NUM];
+    for card in cards.iter() {
+        let Some(count) = counts.get_mut(card.value_hash() as usize) else {
+            return Some(format!("card {} is not a regular card", card.value_hash()));
+        };
+        *count += 1;
+    }
+    counts
+        .iter()
+        .position(|count| *count != deck_num)
+        .map(|value_hash| {
+            format!(
+                "{} copies of card {} for {} decks",
+                counts[value_hash], value_hash, deck_num
+            )
+        })
 }
 
-impl Shoe {
-    pub fn new(deck_num: usize) -> Shoe {
-        let cards = all_cards();
+fn card_pos<C: ValueHash>(cards: &[C], deck_num: usize) -> CardPos {
+    let mut card_pos: CardPos = core::array::from_fn(|_| Vec::with_capacity(deck_num));
+    for (idx, card) in cards.iter().enumerate() {
+        card_pos[card.value_hash() as usize].push(idx);
+    }
+    card_pos
+}
 
+fn card_counts<C: ValueHash>(cards: &[C]) -> CardCounts {
+    let mut counts = [0; ALL_CARDS_NUM];
+    for card in cards.iter() {
+        counts[card.value_hash() as usize] += 1;
+    }
+    counts
+}
+
+impl<C: FullDeck + ValueHash + UniqueHash + Clone> Shoe<C> {
+    pub fn new(deck_num: usize) -> Shoe<C> {
         // repeatly copy deck
-        let mut cards: Vec<Card> = cards
+        let mut cards: Vec<C> = C::full_deck()
             .iter()
             .cloned()
             .cycle()
@@ -30,83 +140,286 @@ impl Shoe {
             card.update_unique_hash();
         }
 
-        // calculate card_pos
-        let mut card_pos = HashMap::new();
-        for (idx, card) in cards.iter().enumerate() {
-            let card_idxes = card_pos
-                .entry(card.value_hash())
-                .or_insert_with(|| Vec::with_capacity(deck_num));
-            card_idxes.push(idx);
-        }
+        let card_pos = card_pos(&cards, deck_num);
+        let remaining_counts = card_counts(&cards);
         Shoe {
-            deck_num: deck_num,
-            cards: cards,
+            deck_num,
+            cards: Arc::new(cards),
             card_idx: 0,
-            card_pos: card_pos,
+            card_pos: Arc::new(card_pos),
+            remaining_counts,
+            burned: Vec::new(),
+            discards: Vec::new(),
         }
     }
+}
+
+impl<C: ValueHash + Clone> Shoe<C> {
+    // Every card in shoe order, dealt or not. Reorders go through the shoe
+    // so the card positions keep up.
+    pub fn cards(&self) -> &[C] {
+        &self.cards
+    }
 
     pub fn shuffle(&mut self) {
         (self as &mut dyn Deck).shuffle();
         self.reset_card_idx();
     }
+
+    // Same seed, same order, across rand releases too since ChaCha20 is
+    // fixed where `StdRng` is not.
+    pub fn shuffle_with_seed(&mut self, seed: u64) {
+        self.shuffle_from_with_rng(0, &mut ChaCha20Rng::seed_from_u64(seed));
+        self.reset_card_idx();
+    }
+
+    // Every card back in and shuffled, reusing the card and position
+    // buffers. Unlike `shuffle` it reindexes once instead of on every swap,
+    // and allocates nothing unless a clone still shares the order.
+    pub fn reshuffle(&mut self) {
+        self.reshuffle_with_rng(&mut rand::thread_rng());
+    }
+
+    pub fn reshuffle_with_rng(&mut self, rng: &mut dyn RngCore) {
+        let cards = Arc::make_mut(&mut self.cards);
+        cards.shuffle(rng);
+        let card_pos = Arc::make_mut(&mut self.card_pos);
+        for positions in card_pos.iter_mut() {
+            positions.clear();
+        }
+        for (idx, card) in cards.iter().enumerate() {
+            card_pos[card.value_hash() as usize].push(idx);
+        }
+        self.reset_card_idx();
+    }
+}
+
+impl<C: ValueHash + Clone> Shoe<C> {
+    // Checks card_pos and the remaining counts against the cards, for tests
+    // and debugging after hand-made reorders.
+    pub fn validate(&self) -> Result<(), Error> {
+        let card_num = self.cards.len();
+        if self.card_idx > card_num {
+            return Err(Error::Corrupted(format!(
+                "card_idx {} is past the {} cards",
+                self.card_idx, card_num
+            )));
+        }
+        if let Some(message) = composition_error(&self.cards, self.deck_num) {
+            return Err(Error::Corrupted(message));
+        }
+        let mut position_num = 0;
+        for (value_hash, positions) in self.card_pos.iter().enumerate() {
+            if positions.windows(2).any(|pair| pair[0] >= pair[1]) {
+                return Err(Error::Corrupted(format!(
+                    "positions of card {} are out of order",
+                    value_hash
+                )));
+            }
+            for &idx in positions.iter() {
+                let card = self.cards.get(idx);
+                if card.map(|card| card.value_hash() as usize) != Some(value_hash) {
+                    return Err(Error::Corrupted(format!(
+                        "position {} is listed under card {}",
+                        idx, value_hash
+                    )));
+                }
+            }
+            position_num += positions.len();
+        }
+        if position_num != card_num {
+            return Err(Error::Corrupted(format!(
+                "{} positions for {} cards",
+                position_num, card_num
+            )));
+        }
+        if self.remaining_counts != card_counts(self.undealt()) {
+            return Err(Error::Corrupted(
+                "remaining counts disagree with the undealt cards".to_string(),
+            ));
+        }
+        Ok(())
+    }
 }
 
-impl Deck for Shoe {
+impl<C: ValueHash + Clone> Deck for Shoe<C> {
     fn card_num(&self) -> usize {
         self.cards.len()
     }
     fn swap(&mut self, idx1: usize, idx2: usize) {
-        if (idx1 == idx2) || (self.cards[idx1].value_hash() == self.cards[idx2].value_hash()) {
+        let hash1 = self.cards[idx1].value_hash() as usize;
+        let hash2 = self.cards[idx2].value_hash() as usize;
+        if (idx1 == idx2) || (hash1 == hash2) {
             return;
         }
-        self.move_idx(idx1, idx2);
-        self.move_idx(idx2, idx1);
-        self.cards.swap(idx1, idx2);
+        // a card crossing the next draw position joins or leaves the rest
+        match (idx1 < self.card_idx, idx2 < self.card_idx) {
+            (true, false) => {
+                self.remaining_counts[hash1] += 1;
+                self.remaining_counts[hash2] -= 1;
+            }
+            (false, true) => {
+                self.remaining_counts[hash1] -= 1;
+                self.remaining_counts[hash2] += 1;
+            }
+            _ => {}
+        }
+        let moved = self
+            .move_idx(idx1, idx2)
+            .and_then(|_| self.move_idx(idx2, idx1));
+        Arc::make_mut(&mut self.cards).swap(idx1, idx2);
+        // a lost position is a bookkeeping bug, release builds reindex and go on
+        debug_assert!(moved.is_ok(), "{:?}", moved);
+        if moved.is_err() {
+            self.card_pos = Arc::new(card_pos(&self.cards, self.deck_num));
+        }
     }
 }
 
-impl ShoeTrait<Card> for Shoe {
-    fn draw_n<const SIZE: usize>(&mut self) -> [Card; SIZE] {
-        let cards = core::array::from_fn(|idx: usize| self.cards[self.card_idx + idx].clone());
+impl<C: ValueHash + Clone> ShoeTrait<C> for Shoe<C> {
+    fn draw_n<const SIZE: usize>(&mut self) -> Result<[C; SIZE], Error> {
+        let remaining = self.remaining();
+        if remaining < SIZE {
+            return Err(Error::Exhausted {
+                wanted: SIZE,
+                remaining,
+            });
+        }
+        let cards: [C; SIZE] =
+            core::array::from_fn(|idx: usize| self.cards[self.card_idx + idx].clone());
+        for card in cards.iter() {
+            self.remaining_counts[card.value_hash() as usize] -= 1;
+        }
         self.card_idx += SIZE;
-        cards
+        Ok(cards)
+    }
+
+    fn burn(&mut self, n: usize) -> Result<(), Error> {
+        let remaining = self.remaining();
+        if remaining < n {
+            return Err(Error::Exhausted {
+                wanted: n,
+                remaining,
+            });
+        }
+        for _ in 0..n {
+            self.burned.push(self.cards[self.card_idx].clone());
+            self.inc_card_idx();
+        }
+        Ok(())
+    }
+
+    fn burned(&self) -> &[C] {
+        &self.burned
+    }
+
+    fn undealt(&self) -> &[C] {
+        &self.cards[self.card_idx..]
+    }
+
+    fn remaining_count(&self, card: &C) -> usize {
+        self.remaining_counts
+            .get(card.value_hash() as usize)
+            .copied()
+            .unwrap_or(0)
     }
 
     fn get_card_idx(&self) -> usize {
         self.card_idx
     }
 
+    // Every card back in the shoe, the piles emptied.
     fn reset_card_idx(&mut self) {
         self.card_idx = 0;
+        self.burned.clear();
+        self.discards.clear();
+        for (count, positions) in self.remaining_counts.iter_mut().zip(self.card_pos.iter()) {
+            *count = positions.len();
+        }
     }
 
     fn inc_card_idx(&mut self) {
+        let card = &self.cards[self.card_idx];
+        self.remaining_counts[card.value_hash() as usize] -= 1;
         self.card_idx += 1;
     }
 
-    fn get_card_ref(&self, idx: usize) -> &Card {
+    fn get_card_ref(&self, idx: usize) -> &C {
         &self.cards[idx]
     }
 
-    fn get_card_map(&self, card: &Card) -> Option<&Vec<usize>> {
-        self.card_pos.get(&card.value_hash())
+    fn get_card_map(&self, card: &C) -> Option<&Vec<usize>> {
+        self.card_pos.get(card.value_hash() as usize)
     }
 
     fn get_mut_card_map_from_idx(&mut self, idx: usize) -> Option<&mut Vec<usize>> {
         let card = &self.cards[idx];
-        self.card_pos.get_mut(&card.value_hash())
+        Arc::make_mut(&mut self.card_pos).get_mut(card.value_hash() as usize)
+    }
+}
+
+impl<C: ValueHash + Clone> Discard<C> for Shoe<C> {
+    fn discard(&mut self, card: C) -> Result<(), Error> {
+        let value_hash = card.value_hash();
+        let same = |other: &&C| other.value_hash() == value_hash;
+        let dealt = self.cards[..self.card_idx].iter().filter(same).count();
+        let out = self
+            .discards
+            .iter()
+            .chain(self.burned.iter())
+            .filter(same)
+            .count();
+        if dealt <= out {
+            return Err(Error::NotDealt(value_hash));
+        }
+        self.discards.push(card);
+        Ok(())
+    }
+
+    fn discards(&self) -> &[C] {
+        &self.discards
+    }
+
+    // The burn pile goes back in with the discards.
+    fn reshuffle_discards(&mut self) {
+        self.discards.append(&mut self.burned);
+        // one dealt copy leaves play per discard, the other copies are held
+        let mut discarded = [0usize; ALL_CARDS_NUM];
+        for card in self.discards.iter() {
+            discarded[card.value_hash() as usize] += 1;
+        }
+        let mut cards = Vec::with_capacity(self.cards.len());
+        for card in self.cards[..self.card_idx].iter() {
+            let count = &mut discarded[card.value_hash() as usize];
+            if *count > 0 {
+                *count -= 1;
+            } else {
+                cards.push(card.clone());
+            }
+        }
+        let held_num = cards.len();
+        cards.extend(self.cards[self.card_idx..].iter().cloned());
+        let stub_num = cards.len();
+        cards.append(&mut self.discards);
+        self.card_pos = Arc::new(card_pos(&cards, self.deck_num));
+        self.cards = Arc::new(cards);
+        self.card_idx = held_num;
+        self.remaining_counts = card_counts(&self.cards[held_num..]);
+        self.shuffle_from(stub_num);
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::cards::card::all_cards;
     use crate::cards::number::Number;
     use crate::cards::suit::Suit;
-    use crate::cards::traits::hash::UniqueHash;
+    use crate::cards::traits::hash::{UniqueHash, ValueHashT};
 
     use super::*;
 
+    type Shoe = super::Shoe<Card>;
+
     #[test]
     fn shoe_value_hash_test() {
         use std::collections::HashMap;
@@ -131,7 +444,7 @@ mod tests {
         // Spade Ace
         let card_pos = shoe
             .card_pos
-            .get(&Card::new(Suit::Spade, Number::Ace).value_hash())
+            .get(Card::new(Suit::Spade, Number::Ace).value_hash() as usize)
             .unwrap();
         assert_eq!(card_pos.len(), DECK_NUM);
         for i in 1..card_pos.len() {
@@ -141,7 +454,7 @@ mod tests {
         // Heart 3
         let card_pos = shoe
             .card_pos
-            .get(&Card::new(Suit::Heart, Number::Three).value_hash())
+            .get(Card::new(Suit::Heart, Number::Three).value_hash() as usize)
             .unwrap();
         assert_eq!(card_pos.len(), DECK_NUM);
         for i in 1..card_pos.len() {
@@ -209,16 +522,16 @@ mod tests {
         let mut shoe = Shoe::new(DECK_NUM);
         let ref_cards = shoe.cards.clone();
 
-        let card = shoe.draw();
+        let card = shoe.draw().unwrap();
         assert_eq!(card.unique_hash(), ref_cards[0].unique_hash());
         assert_eq!(shoe.card_idx, 1);
 
-        let cards = shoe.draw_two();
+        let cards = shoe.draw_two().unwrap();
         assert_eq!(cards[0].unique_hash(), ref_cards[1].unique_hash());
         assert_eq!(cards[1].unique_hash(), ref_cards[2].unique_hash());
         assert_eq!(shoe.card_idx, 3);
 
-        let cards = shoe.draw_three();
+        let cards = shoe.draw_three().unwrap();
         assert_eq!(cards[0].unique_hash(), ref_cards[3].unique_hash());
         assert_eq!(cards[1].unique_hash(), ref_cards[4].unique_hash());
         assert_eq!(cards[2].unique_hash(), ref_cards[5].unique_hash());
@@ -231,13 +544,13 @@ mod tests {
         let mut shoe = Shoe::new(DECK_NUM);
         assert_eq!(shoe.get_card_idx(), 0);
 
-        shoe.draw();
+        shoe.draw().unwrap();
         assert_eq!(shoe.get_card_idx(), 1);
 
         shoe.shuffle();
         assert_eq!(shoe.get_card_idx(), 0);
 
-        shoe.draw();
+        shoe.draw().unwrap();
         assert_eq!(shoe.get_card_idx(), 1);
     }
 
@@ -255,6 +568,8 @@ mod tests {
         assert_eq!(shoe.find_card_idx_after(card, 52).unwrap(), 52);
         // first card after index 53
         assert_eq!(shoe.find_card_idx_after(card, 53).unwrap(), 52 * 2);
+        // no copy left after the last one
+        assert_eq!(shoe.find_card_idx_after(card, 52 * 3 + 1), None);
     }
 
     #[test]
@@ -267,14 +582,26 @@ mod tests {
         shoe.swap(0, 1);
         assert_eq!(shoe.cards[0].unique_hash(), card1.unique_hash());
         assert_eq!(shoe.cards[1].unique_hash(), card0.unique_hash());
-        assert_eq!(shoe.card_pos.get(&card0.value_hash()).unwrap()[0], 1);
-        assert_eq!(shoe.card_pos.get(&card1.value_hash()).unwrap()[0], 0);
+        assert_eq!(
+            shoe.card_pos.get(card0.value_hash() as usize).unwrap()[0],
+            1
+        );
+        assert_eq!(
+            shoe.card_pos.get(card1.value_hash() as usize).unwrap()[0],
+            0
+        );
         // 1, 0
         shoe.swap(1, 0);
         assert_eq!(shoe.cards[0].unique_hash(), card0.unique_hash());
         assert_eq!(shoe.cards[1].unique_hash(), card1.unique_hash());
-        assert_eq!(shoe.card_pos.get(&card0.value_hash()).unwrap()[0], 0);
-        assert_eq!(shoe.card_pos.get(&card1.value_hash()).unwrap()[0], 1);
+        assert_eq!(
+            shoe.card_pos.get(card0.value_hash() as usize).unwrap()[0],
+            0
+        );
+        assert_eq!(
+            shoe.card_pos.get(card1.value_hash() as usize).unwrap()[0],
+            1
+        );
         // 0, 52
         let card0 = shoe.cards[0].clone();
         let card52 = shoe.cards[52].clone();
@@ -282,10 +609,22 @@ mod tests {
         assert_eq!(card0.value_hash(), card52.value_hash());
         assert_eq!(shoe.cards[0].value_hash(), card52.value_hash());
         assert_eq!(shoe.cards[52].value_hash(), card0.value_hash());
-        assert_eq!(shoe.card_pos.get(&card0.value_hash()).unwrap()[0], 0);
-        assert_eq!(shoe.card_pos.get(&card0.value_hash()).unwrap()[1], 52);
-        assert_eq!(shoe.card_pos.get(&card52.value_hash()).unwrap()[0], 0);
-        assert_eq!(shoe.card_pos.get(&card52.value_hash()).unwrap()[1], 52);
+        assert_eq!(
+            shoe.card_pos.get(card0.value_hash() as usize).unwrap()[0],
+            0
+        );
+        assert_eq!(
+            shoe.card_pos.get(card0.value_hash() as usize).unwrap()[1],
+            52
+        );
+        assert_eq!(
+            shoe.card_pos.get(card52.value_hash() as usize).unwrap()[0],
+            0
+        );
+        assert_eq!(
+            shoe.card_pos.get(card52.value_hash() as usize).unwrap()[1],
+            52
+        );
     }
 
     #[test]
@@ -293,7 +632,7 @@ mod tests {
         const DECK_NUM: usize = 4;
         let mut shoe = Shoe::new(DECK_NUM);
 
-        let mut inserted_cards =Vec::new();
+        let mut inserted_cards = Vec::new();
         // get first card
         let card0 = shoe.cards.last().unwrap().clone();
 
@@ -316,8 +655,417 @@ mod tests {
         }
 
         // check first DECK_NUM * 2 values
-        for idx in 0..(DECK_NUM * 2) {
-            assert_eq!(shoe.cards[idx].value_hash(), inserted_cards[idx].value_hash());
+        for (idx, inserted_card) in inserted_cards.iter().enumerate() {
+            assert_eq!(shoe.cards[idx].value_hash(), inserted_card.value_hash());
+        }
+    }
+
+    #[test]
+    fn shoe_condition_on_test() {
+        const DECK_NUM: usize = 2;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        let observed: Vec<Card> = shoe.cards[..30].to_vec();
+
+        let mut conditioned = Shoe::new(DECK_NUM);
+        conditioned.condition_on(&observed).unwrap();
+        assert_eq!(conditioned.get_card_idx(), 30);
+        for (card, observed_card) in conditioned.cards.iter().zip(observed.iter()) {
+            assert_eq!(card.value_hash(), observed_card.value_hash());
+        }
+        // the unseen cards are the rest of the shoe
+        let mut unseen: Vec<ValueHashT> = conditioned.cards[30..]
+            .iter()
+            .map(|card| card.value_hash())
+            .collect();
+        let mut rest: Vec<ValueHashT> = shoe.cards[30..]
+            .iter()
+            .map(|card| card.value_hash())
+            .collect();
+        unseen.sort();
+        rest.sort();
+        assert_eq!(unseen, rest);
+
+        // a third ace of spades in two decks
+        let aces = vec![Card::new(Suit::Spade, Number::Ace); 3];
+        assert_eq!(
+            conditioned.condition_on(&aces),
+            Err(Error::CardNotFound(aces[0].value_hash()))
+        );
+        assert_eq!(
+            Error::CardNotFound(aces[0].value_hash()).to_string(),
+            "no As left in the shoe"
+        );
+    }
+
+    #[test]
+    fn shoe_remaining_test() {
+        const DECK_NUM: usize = 1;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        let ace = Card::new(Suit::Spade, Number::Ace);
+        shoe.set_card(&ace).unwrap();
+        shoe.reset_card_idx();
+        assert_eq!(shoe.remaining(), ALL_CARDS_NUM);
+        assert_eq!(shoe.remaining_count(&ace), 1);
+
+        let next = shoe.peek(2).unwrap()[1].clone();
+        let [drawn] = shoe.draw_n::<1>().unwrap();
+        assert_eq!(drawn.value_hash(), ace.value_hash());
+        assert_eq!(shoe.remaining_count(&ace), 0);
+        assert_eq!(shoe.undealt()[0].value_hash(), next.value_hash());
+        assert_eq!(shoe.iter_undealt().count(), shoe.remaining());
+
+        // swapping across the next card keeps the counts
+        shoe.swap(0, 10);
+        assert_eq!(shoe.remaining_count(&ace), 1);
+        assert_eq!(shoe.remaining_count(&shoe.cards[0].clone()), 0);
+
+        while shoe.remaining() > 2 {
+            shoe.draw().unwrap();
+        }
+        assert_eq!(
+            shoe.draw_three().map(|_| ()),
+            Err(Error::Exhausted {
+                wanted: 3,
+                remaining: 2
+            })
+        );
+        assert_eq!(shoe.remaining(), 2);
+        assert!(shoe.peek(3).is_err());
+        shoe.reset_card_idx();
+        assert_eq!(shoe.remaining_count(&ace), DECK_NUM);
+    }
+
+    // card_pos and the counts match a shoe rebuilt from its cards
+    fn assert_tracked(shoe: &Shoe) {
+        assert_eq!(shoe.validate(), Ok(()));
+        assert_eq!(*shoe.card_pos, card_pos(&shoe.cards, shoe.deck_num));
+    }
+
+    #[test]
+    fn shoe_cut_test() {
+        const DECK_NUM: usize = 2;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        let order: Vec<ValueHashT> = shoe.cards.iter().map(|card| card.value_hash()).collect();
+        shoe.cut(30);
+        assert_tracked(&shoe);
+        let cut: Vec<ValueHashT> = shoe.cards.iter().map(|card| card.value_hash()).collect();
+        assert_eq!(cut[..order.len() - 30], order[30..]);
+        assert_eq!(cut[order.len() - 30..], order[..30]);
+
+        shoe.cut(0);
+        shoe.cut(shoe.cards.len());
+        assert!(shoe
+            .cards
+            .iter()
+            .zip(cut.iter())
+            .all(|(card, value_hash)| card.value_hash() == *value_hash));
+    }
+
+    #[test]
+    fn shoe_deal_test() {
+        const DECK_NUM: usize = 1;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        let order: Vec<Card> = shoe.cards.to_vec();
+        shoe.burn(1).unwrap();
+        assert_eq!(shoe.burned()[0].value_hash(), order[0].value_hash());
+
+        let seats = shoe.deal_round_robin(3, 2).unwrap();
+        assert_eq!(seats.len(), 3);
+        for (seat_idx, seat) in seats.iter().enumerate() {
+            assert_eq!(seat.len(), 2);
+            assert_eq!(seat[0].value_hash(), order[1 + seat_idx].value_hash());
+            assert_eq!(seat[1].value_hash(), order[4 + seat_idx].value_hash());
+        }
+        assert_eq!(shoe.remaining(), ALL_CARDS_NUM - 7);
+        assert_tracked(&shoe);
+
+        // nothing dealt when the shoe can't cover every seat
+        assert!(shoe.deal_round_robin(10, 5).is_err());
+        assert!(shoe.burn(ALL_CARDS_NUM).is_err());
+        assert_eq!(shoe.remaining(), ALL_CARDS_NUM - 7);
+
+        shoe.reset_card_idx();
+        assert!(shoe.burned().is_empty());
+        assert_tracked(&shoe);
+    }
+
+    #[test]
+    fn shoe_discard_test() {
+        const DECK_NUM: usize = 2;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        shoe.burn(1).unwrap();
+        let held: Vec<Card> = (0..40).map(|_| shoe.draw().unwrap()).collect();
+        for card in held[..30].iter() {
+            shoe.discard(card.clone()).unwrap();
+        }
+        let stub: Vec<Card> = shoe.undealt().to_vec();
+        assert_eq!(shoe.discards().len(), 30);
+
+        shoe.reshuffle_discards();
+        assert_tracked(&shoe);
+        assert_eq!(shoe.cards.len(), DECK_NUM * ALL_CARDS_NUM);
+        assert_eq!(shoe.get_card_idx(), 10);
+        assert_eq!(shoe.remaining(), stub.len() + 31);
+        assert!(shoe.discards().is_empty() && shoe.burned().is_empty());
+        // the stub deals first, then the discards and the burn card
+        for card in stub.iter() {
+            assert_eq!(shoe.draw().unwrap().value_hash(), card.value_hash());
+        }
+        assert_eq!(shoe.remaining(), 31);
+        assert_tracked(&shoe);
+    }
+
+    #[test]
+    fn shoe_discard_reject_test() {
+        // As, 2s, ... in each of the two decks
+        let mut shoe = Shoe::new(2);
+        let ace = shoe.draw().unwrap();
+        shoe.burn(1).unwrap();
+        shoe.discard(ace.clone()).unwrap();
+        // each dealt copy goes once, and the other ace is still undealt
+        assert_eq!(
+            shoe.discard(ace.clone()),
+            Err(Error::NotDealt(ace.value_hash()))
+        );
+        // the burn card is already out of play
+        let two = shoe.burned()[0].clone();
+        assert!(shoe.discard(two).is_err());
+        let joker = Card::joker(0).unwrap();
+        assert_eq!(
+            shoe.discard(joker.clone()),
+            Err(Error::NotDealt(joker.value_hash()))
+        );
+        assert_eq!(
+            Error::NotDealt(ace.value_hash()).to_string(),
+            "no As dealt to discard"
+        );
+        assert_eq!(shoe.discards().len(), 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn shoe_serde_test() {
+        const DECK_NUM: usize = 4;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        shoe.burn(1).unwrap();
+        shoe.draw_three().unwrap();
+
+        let saved = serde_json::to_string(&shoe).unwrap();
+        let mut loaded: Shoe = serde_json::from_str(&saved).unwrap();
+        assert_eq!(loaded.get_card_idx(), 4);
+        assert_eq!(loaded.card_pos, shoe.card_pos);
+        assert_eq!(loaded.remaining_counts, shoe.remaining_counts);
+        assert_eq!(loaded.burned().len(), shoe.burned().len());
+        assert_eq!(
+            loaded.draw().unwrap().value_hash(),
+            shoe.draw().unwrap().value_hash()
+        );
+
+        // card_idx past the end
+        let mut state: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        state["card_idx"] = serde_json::json!(DECK_NUM * ALL_CARDS_NUM + 1);
+        assert!(serde_json::from_value::<Shoe>(state).is_err());
+
+        // a joker, then a card too many
+        let mut state: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        state["cards"][0] = serde_json::to_value(Card::joker(0).unwrap()).unwrap();
+        assert!(serde_json::from_value::<Shoe>(state).is_err());
+        let mut state: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        let other = (1..)
+            .find(|idx| state["cards"][idx] != state["cards"][0])
+            .unwrap();
+        state["cards"][other] = state["cards"][0].clone();
+        assert!(serde_json::from_value::<Shoe>(state).is_err());
+        let mut state: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        state["deck_num"] = serde_json::json!(DECK_NUM + 1);
+        assert!(serde_json::from_value::<Shoe>(state).is_err());
+    }
+
+    #[test]
+    fn shoe_reshuffle_test() {
+        const DECK_NUM: usize = 2;
+        let mut shoe = Shoe::new(DECK_NUM);
+        shoe.shuffle();
+        shoe.burn(1).unwrap();
+        shoe.draw_three().unwrap();
+        let cards = Arc::as_ptr(&shoe.cards);
+        let capacities: Vec<usize> = shoe.card_pos.iter().map(|pos| pos.capacity()).collect();
+
+        shoe.reshuffle();
+        assert_tracked(&shoe);
+        assert_eq!(shoe.get_card_idx(), 0);
+        assert!(shoe.burned().is_empty());
+        assert_eq!(shoe.remaining(), DECK_NUM * ALL_CARDS_NUM);
+        // the same buffers
+        assert_eq!(Arc::as_ptr(&shoe.cards), cards);
+        let reused: Vec<usize> = shoe.card_pos.iter().map(|pos| pos.capacity()).collect();
+        assert_eq!(reused, capacities);
+
+        // same seed, same order
+        let mut other = shoe.clone();
+        shoe.reshuffle_with_rng(&mut ChaCha20Rng::seed_from_u64(9));
+        other.reshuffle_with_rng(&mut ChaCha20Rng::seed_from_u64(9));
+        assert!(shoe
+            .cards
+            .iter()
+            .zip(other.cards.iter())
+            .all(|(card, other)| card.value_hash() == other.value_hash()));
+    }
+
+    #[test]
+    fn shoe_validate_test() {
+        let mut shoe = Shoe::new(2);
+        shoe.shuffle();
+        shoe.draw_three().unwrap();
+        assert_eq!(shoe.validate(), Ok(()));
+
+        let mut unsorted = shoe.clone();
+        Arc::make_mut(&mut unsorted.card_pos)[0].reverse();
+        assert!(matches!(unsorted.validate(), Err(Error::Corrupted(_))));
+
+        let mut misplaced = shoe.clone();
+        let card_pos = Arc::make_mut(&mut misplaced.card_pos);
+        let idx = card_pos[0][0];
+        card_pos[0][0] = card_pos[1][0];
+        card_pos[1][0] = idx;
+        card_pos[0].sort();
+        card_pos[1].sort();
+        assert!(misplaced.validate().is_err());
+
+        let mut miscounted = shoe.clone();
+        miscounted.remaining_counts[0] += 1;
+        assert!(miscounted.validate().is_err());
+
+        // a copy too many of one card, one too few of another
+        let mut duplicated = shoe.clone();
+        let first = duplicated.cards[0].clone();
+        let other = (1..)
+            .find(|idx| duplicated.cards[*idx].value_hash() != first.value_hash())
+            .unwrap();
+        Arc::make_mut(&mut duplicated.cards)[other] = first;
+        assert!(matches!(duplicated.validate(), Err(Error::Corrupted(_))));
+
+        let mut short = shoe.clone();
+        short.deck_num = 3;
+        assert!(short.validate().is_err());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "PositionLost")]
+    fn shoe_swap_lost_position_test() {
+        let mut shoe = Shoe::new(1);
+        let value_hash = shoe.cards[0].value_hash() as usize;
+        Arc::make_mut(&mut shoe.card_pos)[value_hash].clear();
+        shoe.swap(0, 1);
+    }
+
+    #[derive(Clone, Debug)]
+    enum Op {
+        Swap(usize, usize),
+        ShuffleFrom(usize, u64),
+        SetCard(ValueHashT),
+        Draw,
+        Reset,
+    }
+
+    fn op() -> impl proptest::strategy::Strategy<Value = Op> {
+        use proptest::prelude::*;
+        prop_oneof![
+            (any::<usize>(), any::<usize>()).prop_map(|(idx1, idx2)| Op::Swap(idx1, idx2)),
+            (any::<usize>(), any::<u64>()).prop_map(|(idx, seed)| Op::ShuffleFrom(idx, seed)),
+            (0..ALL_CARDS_NUM as ValueHashT).prop_map(Op::SetCard),
+            Just(Op::Draw),
+            Just(Op::Reset),
+        ]
+    }
+
+    // The shoe as a plain list of value hashes, searched linearly.
+    struct Model {
+        cards: Vec<ValueHashT>,
+        card_idx: usize,
+    }
+
+    impl Model {
+        fn set_card(&mut self, value_hash: ValueHashT) -> bool {
+            let found = self.cards[self.card_idx..]
+                .iter()
+                .position(|card| *card == value_hash);
+            match found {
+                Some(offset) => {
+                    self.cards.swap(self.card_idx, self.card_idx + offset);
+                    self.card_idx += 1;
+                    true
+                }
+                None => false,
+            }
+        }
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn shoe_model_test(
+            deck_num in 1..3usize,
+            ops in proptest::collection::vec(op(), 1..200),
+        ) {
+            let mut shoe = Shoe::new(deck_num);
+            let mut model = Model {
+                cards: shoe.cards.iter().map(|card| card.value_hash()).collect(),
+                card_idx: 0,
+            };
+            let card_num = model.cards.len();
+            let all_cards = all_cards();
+            for op in ops.iter() {
+                match *op {
+                    Op::Swap(idx1, idx2) => {
+                        let (idx1, idx2) = (idx1 % card_num, idx2 % card_num);
+                        shoe.swap(idx1, idx2);
+                        model.cards.swap(idx1, idx2);
+                    }
+                    Op::ShuffleFrom(idx, seed) => {
+                        let idx = idx % (card_num + 1);
+                        shoe.shuffle_from_with_rng(idx, &mut ChaCha20Rng::seed_from_u64(seed));
+                        // any order of the same cards after idx, the ones before stay
+                        let cards: Vec<ValueHashT> =
+                            shoe.cards.iter().map(|card| card.value_hash()).collect();
+                        proptest::prop_assert_eq!(&cards[..idx], &model.cards[..idx]);
+                        let mut shuffled = cards[idx..].to_vec();
+                        let mut expected = model.cards[idx..].to_vec();
+                        shuffled.sort();
+                        expected.sort();
+                        proptest::prop_assert_eq!(shuffled, expected);
+                        model.cards = cards;
+                    }
+                    Op::SetCard(value_hash) => {
+                        let card = &all_cards[value_hash as usize];
+                        let set = shoe.set_card(card).is_ok();
+                        let expected = model.card_idx < card_num && model.set_card(value_hash);
+                        proptest::prop_assert_eq!(set, expected);
+                    }
+                    Op::Draw => {
+                        let drawn = shoe.draw().map(|card| card.value_hash()).ok();
+                        let expected = model.cards.get(model.card_idx).copied();
+                        if expected.is_some() {
+                            model.card_idx += 1;
+                        }
+                        proptest::prop_assert_eq!(drawn, expected);
+                    }
+                    Op::Reset => {
+                        shoe.reset_card_idx();
+                        model.card_idx = 0;
+                    }
+                }
+                proptest::prop_assert_eq!(shoe.validate(), Ok(()));
+                proptest::prop_assert_eq!(shoe.get_card_idx(), model.card_idx);
+                let cards: Vec<ValueHashT> = shoe.cards.iter().map(|card| card.value_hash()).collect();
+                proptest::prop_assert_eq!(&cards, &model.cards);
+            }
         }
     }
 }
@@ -1,8 +1,13 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::notation::{parse_suit_char, ParseError, SUIT_CHARS, SUIT_SYMBOLS};
 use super::traits::hash::{ValueHash, ValueHashT};
 use super::traits::{order::Order, value::Value};
 use std::hash::Hash;
 
 #[derive(Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Suit {
     Spade,
     Heart,
@@ -33,6 +38,31 @@ impl ValueHash for Suit {
     }
 }
 
+// "s", or "♠" with {:#}.
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let idx = self.order() as usize;
+        let c = if f.alternate() {
+            SUIT_SYMBOLS[idx]
+        } else {
+            SUIT_CHARS[idx]
+        };
+        write!(f, "{}", c)
+    }
+}
+
+impl FromStr for Suit {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(ALL_SUITS[parse_suit_char(c, s)?].clone()),
+            _ => Err(ParseError::Suit(s.to_string())),
+        }
+    }
+}
+
 pub const ALL_SUIT_NUM: usize = 4;
 
 pub static ALL_SUITS: [Suit; ALL_SUIT_NUM] = [Suit::Spade, Suit::Heart, Suit::Diamond, Suit::Club];
@@ -63,6 +93,22 @@ mod tests {
         assert_eq!(suit1.value(), suit2.value());
     }
 
+    #[test]
+    fn suit_notation_test() {
+        for suit in all_suits() {
+            assert_eq!(
+                suit.to_string().parse::<Suit>().unwrap().order(),
+                suit.order()
+            );
+            assert_eq!(
+                format!("{:#}", suit).parse::<Suit>().unwrap().order(),
+                suit.order()
+            );
+        }
+        assert_eq!(format!("{:#}", Suit::Diamond), "♦");
+        assert!("S".parse::<Suit>().is_err());
+    }
+
     #[test]
     fn suit_hash_test() {
         // value_hash
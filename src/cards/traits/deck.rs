@@ -1,3 +1,5 @@
+use rand::{Rng, RngCore};
+
 pub trait Deck {
     fn card_num(&self) -> usize;
     fn swap(&mut self, idx1: usize, idx2: usize);
@@ -5,11 +7,34 @@ pub trait Deck {
         self.shuffle_from(0);
     }
     fn shuffle_from(&mut self, start_idx: usize) {
-        use rand::Rng;
+        self.shuffle_from_with_rng(start_idx, &mut rand::thread_rng());
+    }
+    // A seeded rng gives a shuffle that can be replayed.
+    fn shuffle_from_with_rng(&mut self, start_idx: usize, rng: &mut dyn RngCore) {
         let card_num = self.card_num();
         for idx in start_idx..card_num {
-            let target_idx = rand::thread_rng().gen_range(idx..card_num);
+            let target_idx = rng.gen_range(idx..card_num);
             self.swap(idx, target_idx)
         }
     }
+    // Cards from idx on go to the front, the rest after them in order. A cut
+    // at 0 or past the end changes nothing.
+    fn cut(&mut self, idx: usize) {
+        let card_num = self.card_num();
+        if idx == 0 || idx >= card_num {
+            return;
+        }
+        // rotating is reversing both parts, then the whole
+        for (from, to) in [(0, idx), (idx, card_num), (0, card_num)] {
+            for offset in 0..(to - from) / 2 {
+                self.swap(from + offset, to - 1 - offset);
+            }
+        }
+    }
+}
+
+// A card type that comes in a regular 52-card deck, value_hash running 0..52
+// in deck order.
+pub trait FullDeck: Sized + 'static {
+    fn full_deck() -> &'static [Self];
 }
@@ -0,0 +1,11 @@
+use crate::error::Error;
+
+// A deck that keeps its discards, to be shuffled back in once the stub runs
+// out, as in draw games.
+pub trait Discard<Card> {
+    // Fails unless a dealt copy of the card is still out of the pile.
+    fn discard(&mut self, card: Card) -> Result<(), Error>;
+    fn discards(&self) -> &[Card];
+    // Shuffles the discards in under what is left of the stub.
+    fn reshuffle_discards(&mut self);
+}
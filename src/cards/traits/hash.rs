@@ -1,5 +1,7 @@
 pub type ValueHashT = u32;
 
+// Identity of what a card shows, shared by every copy of it. Dense from 0,
+// suit order * 13 + number order for cards, so it can index arrays.
 pub trait ValueHash {
     fn value_hash(&self) -> ValueHashT;
 }
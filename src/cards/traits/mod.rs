@@ -1,4 +1,5 @@
 pub mod deck;
+pub mod discard;
 pub mod hash;
 pub mod order;
 pub mod shoe;
@@ -1,31 +1,89 @@
 use super::deck::Deck;
 use super::hash::ValueHash;
-use std::usize;
+use crate::error::Error;
 
 pub trait Shoe<Card: Sized + ValueHash>: Deck {
     //
     // Draw cards
     //
 
-    fn draw_n<const SIZE: usize>(&mut self) -> [Card; SIZE];
+    // Err without dealing anything when fewer than SIZE cards are left.
+    fn draw_n<const SIZE: usize>(&mut self) -> Result<[Card; SIZE], Error>;
 
-    fn draw(&mut self) -> Card {
-        let [card] = self.draw_n::<1>();
-        card
+    fn draw(&mut self) -> Result<Card, Error> {
+        let [card] = self.draw_n::<1>()?;
+        Ok(card)
     }
 
-    fn draw_two(&mut self) -> [Card; 2] {
+    fn draw_two(&mut self) -> Result<[Card; 2], Error> {
         self.draw_n::<2>()
     }
 
-    fn draw_three(&mut self) -> [Card; 3] {
+    fn draw_three(&mut self) -> Result<[Card; 3], Error> {
         self.draw_n::<3>()
     }
 
-    fn draw_four(&mut self) -> [Card; 4] {
+    fn draw_four(&mut self) -> Result<[Card; 4], Error> {
         self.draw_n::<4>()
     }
 
+    // Deals n cards face down to the burn pile.
+    fn burn(&mut self, n: usize) -> Result<(), Error>;
+
+    fn burned(&self) -> &[Card];
+
+    // One card to each of `seat_num` seats in turn until each has
+    // `card_num`, dealing nothing when the shoe can't cover them all.
+    fn deal_round_robin(
+        &mut self,
+        seat_num: usize,
+        card_num: usize,
+    ) -> Result<Vec<Vec<Card>>, Error> {
+        let wanted = seat_num * card_num;
+        if self.remaining() < wanted {
+            return Err(Error::Exhausted {
+                wanted,
+                remaining: self.remaining(),
+            });
+        }
+        let mut seats: Vec<Vec<Card>> = (0..seat_num)
+            .map(|_| Vec::with_capacity(card_num))
+            .collect();
+        for _ in 0..card_num {
+            for seat in seats.iter_mut() {
+                seat.push(self.draw()?);
+            }
+        }
+        Ok(seats)
+    }
+
+    //
+    // Look at what is left
+    //
+
+    // The cards still to deal, next first.
+    fn undealt(&self) -> &[Card];
+
+    fn remaining(&self) -> usize {
+        self.undealt().len()
+    }
+
+    fn iter_undealt(&self) -> std::slice::Iter<'_, Card> {
+        self.undealt().iter()
+    }
+
+    // The next n cards, without dealing them.
+    fn peek(&self, n: usize) -> Result<&[Card], Error> {
+        let undealt = self.undealt();
+        undealt.get(..n).ok_or(Error::Exhausted {
+            wanted: n,
+            remaining: undealt.len(),
+        })
+    }
+
+    // Copies of `card` still to deal.
+    fn remaining_count(&self, card: &Card) -> usize;
+
     //
     // Manipulate card
     //
@@ -41,35 +99,28 @@ pub trait Shoe<Card: Sized + ValueHash>: Deck {
     // inclusive
     fn find_card_idx_after(&self, card: &Card, after: usize) -> Option<usize> {
         let card_map = self.get_card_map(card);
-        match card_map {
-            Some(card_map) => Some(match card_map.binary_search(&after) {
-                Ok(idx) => card_map[idx],
-                Err(idx) => card_map[idx],
-            }),
-            None => None,
-        }
+        card_map.and_then(|card_map| match card_map.binary_search(&after) {
+            Ok(idx) => Some(card_map[idx]),
+            // None once every copy is before `after`
+            Err(idx) => card_map.get(idx).copied(),
+        })
     }
 
-    fn move_idx(&mut self, idx_from: usize, idx_to: usize) {
+    // Err when the card at idx_from isn't where the map says.
+    fn move_idx(&mut self, idx_from: usize, idx_to: usize) -> Result<(), Error> {
         if idx_from == idx_to {
-            return;
+            return Ok(());
         }
-        let card_map = match self.get_mut_card_map_from_idx(idx_from) {
-            None => {
-                return;
-            }
-            Some(card_map) => card_map,
+        let Some(card_map) = self.get_mut_card_map_from_idx(idx_from) else {
+            return Ok(());
         };
-        let map_idx_from = match card_map.binary_search(&idx_from) {
-            Ok(idx) => idx,
-            Err(_) => {
-                panic!("Should find card.");
-            }
+        let Ok(map_idx_from) = card_map.binary_search(&idx_from) else {
+            return Err(Error::PositionLost(idx_from));
         };
         let map_idx_to = match card_map.binary_search(&idx_to) {
             Ok(_) => {
                 // idx_from and idx_to are identical cards.
-                return;
+                return Ok(());
             }
             Err(idx) => idx,
         };
@@ -87,20 +138,51 @@ pub trait Shoe<Card: Sized + ValueHash>: Deck {
             // map_idx_from == map_idx_to
             card_map[map_idx_from] = idx_to;
         }
+        Ok(())
     }
 
-    fn set_card(&mut self, target_card: &Card) -> Result<(), &'static str> {
+    // Brings the next copy of `target_card` up to be dealt next.
+    fn put_next(&mut self, target_card: &Card) -> Result<(), Error> {
         let card_idx = self.get_card_idx();
+        if card_idx == self.card_num() {
+            return Err(Error::Exhausted {
+                wanted: 1,
+                remaining: 0,
+            });
+        }
         if target_card.value_hash() == self.get_card_ref(card_idx).value_hash() {
-            self.inc_card_idx();
             return Ok(());
         }
         let target_idx = self.find_card_idx_after(target_card, card_idx);
         let Some(target_idx) = target_idx else {
-            return Err("card does not exist");
+            return Err(Error::CardNotFound(target_card.value_hash()));
         };
         self.swap(card_idx, target_idx);
+        Ok(())
+    }
+
+    // Brings the next copy of `target_card` up and deals it.
+    fn set_card(&mut self, target_card: &Card) -> Result<(), Error> {
+        self.put_next(target_card)?;
         self.inc_card_idx();
         Ok(())
     }
+
+    // Makes `observed` the dealt prefix and shuffles only the unseen cards
+    // after it, leaving the shoe ready to deal the next one.
+    fn condition_on(&mut self, observed: &[Card]) -> Result<(), Error> {
+        self.reset_card_idx();
+        if observed.len() > self.card_num() {
+            return Err(Error::Exhausted {
+                wanted: observed.len(),
+                remaining: self.card_num(),
+            });
+        }
+        for card in observed.iter() {
+            self.set_card(card)?;
+        }
+        let card_idx = self.get_card_idx();
+        self.shuffle_from(card_idx);
+        Ok(())
+    }
 }
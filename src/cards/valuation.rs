@@ -0,0 +1,46 @@
+use super::card::Card;
+use super::number::{Number, ALL_NUMBER_NUM};
+use super::traits::order::Order;
+
+// How a game counts each number. Every game deals the same `Number` and looks
+// values up through its own valuation.
+pub trait RankValuation {
+    fn value(&self, number: &Number) -> i32;
+
+    fn card_value(&self, card: &Card) -> i32 {
+        self.value(&card.number)
+    }
+}
+
+// Values indexed by `Number::order`, jokers count nothing.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ValueTable(pub [i32; ALL_NUMBER_NUM]);
+
+impl RankValuation for ValueTable {
+    fn value(&self, number: &Number) -> i32 {
+        self.0.get(number.order() as usize).copied().unwrap_or(0)
+    }
+}
+
+// What `Number::value` gives, Ace 0 up to King 12.
+pub static NUMBER_ORDER: ValueTable = ValueTable([0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::all_numbers;
+    use crate::cards::suit::Suit;
+    use crate::cards::traits::value::Value;
+
+    #[test]
+    fn value_table_test() {
+        for number in all_numbers() {
+            assert_eq!(NUMBER_ORDER.value(number), number.value());
+        }
+        // faces count ten, the ace one
+        let ten_count = ValueTable([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 10, 10, 10]);
+        assert_eq!(ten_count.value(&Number::Queen), 10);
+        assert_eq!(ten_count.card_value(&Card::new(Suit::Club, Number::Ace)), 1);
+        assert_eq!(ten_count.value(&Number::Joker), 0);
+    }
+}
@@ -0,0 +1,75 @@
+use super::card::Card;
+use super::number::Number;
+use super::traits::order::Order;
+
+// Which cards a game lets stand in for any other card.
+#[derive(Clone)]
+pub struct Wild {
+    pub jokers: bool,
+    pub numbers: Vec<Number>,
+}
+
+impl Wild {
+    pub fn none() -> Wild {
+        Wild {
+            jokers: false,
+            numbers: Vec::new(),
+        }
+    }
+
+    pub fn jokers() -> Wild {
+        Wild {
+            jokers: true,
+            numbers: Vec::new(),
+        }
+    }
+
+    // Declared wild ranks, e.g. deuces wild. Jokers stay plain cards unless
+    // `and_jokers` makes them wild too.
+    pub fn numbers(numbers: &[Number]) -> Wild {
+        Wild {
+            jokers: false,
+            numbers: numbers.to_vec(),
+        }
+    }
+
+    pub fn and_jokers(mut self) -> Wild {
+        self.jokers = true;
+        self
+    }
+
+    pub fn is_wild(&self, card: &Card) -> bool {
+        if card.is_joker() {
+            return self.jokers;
+        }
+        let order = card.number.order();
+        self.numbers.iter().any(|number| number.order() == order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::suit::Suit;
+
+    #[test]
+    fn wild_test() {
+        let deuce = Card::new(Suit::Club, Number::Two);
+        let ace = Card::new(Suit::Club, Number::Ace);
+        let joker = Card::joker(0).unwrap();
+
+        assert!(!Wild::none().is_wild(&joker));
+        assert!(Wild::jokers().is_wild(&joker));
+        assert!(!Wild::jokers().is_wild(&deuce));
+
+        let deuces = Wild::numbers(&[Number::Two]);
+        assert!(deuces.is_wild(&deuce));
+        assert!(!deuces.is_wild(&joker));
+        assert!(!deuces.is_wild(&ace));
+
+        let deuces = deuces.and_jokers();
+        assert!(deuces.is_wild(&deuce));
+        assert!(deuces.is_wild(&joker));
+        assert!(!deuces.is_wild(&ace));
+    }
+}
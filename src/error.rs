@@ -0,0 +1,103 @@
+use std::fmt;
+
+use crate::cards::card::ALL_CARDS_NUM;
+use crate::cards::id::CardId;
+use crate::cards::notation::ParseError;
+use crate::cards::traits::hash::ValueHashT;
+use crate::games::baccarat::builder::ScenarioError;
+use crate::games::baccarat::history::HistoryError;
+use crate::games::poker::history::hand::ImportError;
+
+// What dealing and setting up cards can run into, for the whole crate. The
+// errors of the text formats convert into it, so callers mixing them can use
+// `?` throughout.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Error {
+    // asked for more cards than are left to deal
+    Exhausted { wanted: usize, remaining: usize },
+    // no copy of the card, by value_hash, left at or after the next card
+    CardNotFound(ValueHashT),
+    // a discard with no dealt copy, by value_hash, still out of the pile
+    NotDealt(ValueHashT),
+    // the shoe lost track of where a card sits
+    PositionLost(usize),
+    // the shoe's bookkeeping disagrees with its cards, see `Shoe::validate`
+    Corrupted(String),
+    Parse(ParseError),
+    Scenario(ScenarioError),
+    History(HistoryError),
+    Import(ImportError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Exhausted { wanted, remaining } => {
+                write!(f, "wanted {} cards, {} left", wanted, remaining)
+            }
+            Error::CardNotFound(value_hash) if (*value_hash as usize) < ALL_CARDS_NUM => {
+                write!(f, "no {} left in the shoe", CardId(*value_hash as u8))
+            }
+            Error::CardNotFound(value_hash) => {
+                write!(f, "no card {} left in the shoe", value_hash)
+            }
+            Error::NotDealt(value_hash) if (*value_hash as usize) < ALL_CARDS_NUM => {
+                write!(f, "no {} dealt to discard", CardId(*value_hash as u8))
+            }
+            Error::NotDealt(value_hash) => write!(f, "no card {} dealt to discard", value_hash),
+            Error::PositionLost(idx) => write!(f, "lost track of the card at {}", idx),
+            Error::Corrupted(message) => write!(f, "corrupted shoe: {}", message),
+            Error::Parse(error) => write!(f, "{}", error),
+            Error::Scenario(error) => write!(f, "{}", error),
+            Error::History(error) => write!(f, "{}", error),
+            Error::Import(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<ParseError> for Error {
+    fn from(error: ParseError) -> Self {
+        Error::Parse(error)
+    }
+}
+
+impl From<ScenarioError> for Error {
+    fn from(error: ScenarioError) -> Self {
+        Error::Scenario(error)
+    }
+}
+
+impl From<HistoryError> for Error {
+    fn from(error: HistoryError) -> Self {
+        Error::History(error)
+    }
+}
+
+impl From<ImportError> for Error {
+    fn from(error: ImportError) -> Self {
+        Error::Import(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::Card;
+    use crate::games::baccarat::builder::ShoeBuilder;
+    use crate::games::poker::history::phh;
+
+    #[test]
+    fn error_from_test() {
+        let card = || -> Result<Card, Error> { Ok("Zs".parse::<Card>()?) };
+        assert!(matches!(card(), Err(Error::Parse(_))));
+        let scenario = || -> Result<ShoeBuilder, Error> { Ok(ShoeBuilder::new(8).scenario("?")?) };
+        assert!(matches!(scenario(), Err(Error::Scenario(_))));
+        let import = || -> Result<(), Error> {
+            phh::parse("variant = ")?;
+            Ok(())
+        };
+        assert!(matches!(import(), Err(Error::Import(_))));
+    }
+}
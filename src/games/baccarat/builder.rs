@@ -0,0 +1,390 @@
+use std::fmt;
+use std::str::FromStr;
+
+use super::rule::{banker_hits, card_value, is_natural_value, player_hits, VALUATION};
+use super::shoe::Shoe;
+use crate::cards::card::Card;
+use crate::cards::notation::ParseError;
+use crate::cards::number::Number;
+use crate::cards::suit::all_suits;
+use crate::cards::traits::deck::Deck;
+use crate::cards::traits::shoe::Shoe as ShoeTrait;
+use crate::cards::valuation::RankValuation;
+
+// A card to deal. Only the number counts in baccarat, so the suit can be
+// left to the shoe.
+#[derive(Clone)]
+pub enum Pick {
+    Any(Number),
+    Exact(Card),
+}
+
+// "4" or "K" for any suit, "4h" for that card.
+impl FromStr for Pick {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.chars().count() == 1 {
+            Ok(Pick::Any(s.parse()?))
+        } else {
+            Ok(Pick::Exact(s.parse()?))
+        }
+    }
+}
+
+impl From<Number> for Pick {
+    fn from(number: Number) -> Self {
+        Pick::Any(number)
+    }
+}
+
+impl From<Card> for Pick {
+    fn from(card: Card) -> Self {
+        Pick::Exact(card)
+    }
+}
+
+// The cards of one round. Third cards have to be there exactly when the
+// drawing rule draws them, see `ShoeBuilder::build`.
+#[derive(Clone)]
+pub struct RoundCards {
+    pub player: [Pick; 2],
+    pub banker: [Pick; 2],
+    pub player_third: Option<Pick>,
+    pub banker_third: Option<Pick>,
+}
+
+impl RoundCards {
+    // As they leave the shoe.
+    fn dealt(&self) -> impl Iterator<Item = &Pick> {
+        [
+            &self.player[0],
+            &self.banker[0],
+            &self.player[1],
+            &self.banker[1],
+        ]
+        .into_iter()
+        .chain(self.player_third.iter())
+        .chain(self.banker_third.iter())
+    }
+
+    // Why the third cards disagree with the drawing rule, if they do.
+    fn third_card_error(&self) -> Option<String> {
+        let player = (pick_value(&self.player[0]) + pick_value(&self.player[1])) % 10;
+        let banker = (pick_value(&self.banker[0]) + pick_value(&self.banker[1])) % 10;
+        let natural = is_natural_value(player) || is_natural_value(banker);
+        let player_draws = !natural && player_hits(player);
+        if self.player_third.is_some() != player_draws {
+            return Some(if player_draws {
+                format!("the player draws on {}", player)
+            } else {
+                format!("the player stands on {} against {}", player, banker)
+            });
+        }
+        let player_third = self.player_third.as_ref().map(pick_value);
+        let banker_draws = !natural && banker_hits(player_third, banker);
+        if self.banker_third.is_some() != banker_draws {
+            return Some(if banker_draws {
+                format!("the banker draws on {}", banker)
+            } else {
+                format!("the banker stands on {}", banker)
+            });
+        }
+        None
+    }
+}
+
+fn pick_value(pick: &Pick) -> i32 {
+    match pick {
+        Pick::Any(number) => VALUATION.value(number),
+        Pick::Exact(card) => card_value(card),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ScenarioError {
+    Syntax(String),
+    Card(ParseError),
+    Exhausted(String),   // no copy of the card left in the shoe
+    ThirdCard(String),   // a third card the rule doesn't draw, or a missing one
+    TooManyCards(usize), // the rounds don't fit in a shoe of that many cards
+}
+
+impl fmt::Display for ScenarioError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScenarioError::Syntax(message) => write!(f, "{}", message),
+            ScenarioError::Card(error) => write!(f, "{}", error),
+            ScenarioError::Exhausted(card) => write!(f, "no {} left in the shoe", card),
+            ScenarioError::ThirdCard(message) => write!(f, "{}", message),
+            ScenarioError::TooManyCards(card_num) => {
+                write!(
+                    f,
+                    "the rounds take more than the {} cards of the shoe",
+                    card_num
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScenarioError {}
+
+impl From<ParseError> for ScenarioError {
+    fn from(error: ParseError) -> Self {
+        ScenarioError::Card(error)
+    }
+}
+
+fn parse_picks(s: &str) -> Result<Vec<Pick>, ScenarioError> {
+    s.split(',')
+        .map(|pick| pick.trim().parse::<Pick>().map_err(ScenarioError::from))
+        .collect()
+}
+
+fn parse_pair(s: &str) -> Result<[Pick; 2], ScenarioError> {
+    let picks = parse_picks(s)?;
+    let found = picks.len();
+    picks
+        .try_into()
+        .map_err(|_| ScenarioError::Card(ParseError::Count { expected: 2, found }))
+}
+
+fn parse_third(s: &str) -> Result<Pick, ScenarioError> {
+    Ok(s.trim().parse::<Pick>()?)
+}
+
+// "player: 4,3; banker: 6,K; player third: 9"
+impl FromStr for RoundCards {
+    type Err = ScenarioError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut player, mut banker, mut player_third, mut banker_third) = (None, None, None, None);
+        for part in s.split(';').filter(|part| !part.trim().is_empty()) {
+            let Some((name, cards)) = part.split_once(':') else {
+                return Err(ScenarioError::Syntax(format!(
+                    "\"{}\" has no ':'",
+                    part.trim()
+                )));
+            };
+            match name.split_whitespace().collect::<Vec<&str>>()[..] {
+                ["player"] => player = Some(parse_pair(cards)?),
+                ["banker"] => banker = Some(parse_pair(cards)?),
+                ["player", "third"] => player_third = Some(parse_third(cards)?),
+                ["banker", "third"] => banker_third = Some(parse_third(cards)?),
+                _ => {
+                    return Err(ScenarioError::Syntax(format!(
+                        "\"{}\" is not player, banker, player third or banker third",
+                        name.trim()
+                    )))
+                }
+            }
+        }
+        let (Some(player), Some(banker)) = (player, banker) else {
+            return Err(ScenarioError::Syntax(
+                "a round needs player and banker cards".to_string(),
+            ));
+        };
+        Ok(RoundCards {
+            player,
+            banker,
+            player_third,
+            banker_third,
+        })
+    }
+}
+
+// Stacks a shoe so the given rounds come out first, the rest shuffled.
+pub struct ShoeBuilder {
+    deck_num: usize,
+    rounds: Vec<RoundCards>,
+}
+
+impl ShoeBuilder {
+    pub fn new(deck_num: usize) -> ShoeBuilder {
+        ShoeBuilder {
+            deck_num,
+            rounds: Vec::new(),
+        }
+    }
+
+    pub fn round(mut self, round: RoundCards) -> ShoeBuilder {
+        self.rounds.push(round);
+        self
+    }
+
+    pub fn scenario(self, s: &str) -> Result<ShoeBuilder, ScenarioError> {
+        Ok(self.round(s.parse()?))
+    }
+
+    // Errs when a round's third cards disagree with the drawing rule, since
+    // one the rule doesn't draw would open the next round instead.
+    pub fn build(&self) -> Result<Shoe, ScenarioError> {
+        if let Some(message) = self.rounds.iter().find_map(RoundCards::third_card_error) {
+            return Err(ScenarioError::ThirdCard(message));
+        }
+        let mut shoe = Shoe::new(self.deck_num);
+        for pick in self.rounds.iter().flat_map(|round| round.dealt()) {
+            if shoe.get_card_idx() == shoe.cards().len() {
+                return Err(ScenarioError::TooManyCards(shoe.cards().len()));
+            }
+            set_pick(&mut shoe, pick)?;
+        }
+        let card_idx = shoe.get_card_idx();
+        shoe.shuffle_from(card_idx);
+        shoe.reset_card_idx();
+        Ok(shoe)
+    }
+}
+
+fn set_pick(shoe: &mut Shoe, pick: &Pick) -> Result<(), ScenarioError> {
+    match pick {
+        Pick::Exact(card) => shoe
+            .set_card(card)
+            .map_err(|_| ScenarioError::Exhausted(card.to_string())),
+        Pick::Any(number) => {
+            for suit in all_suits() {
+                if shoe
+                    .set_card(&Card::new(suit.clone(), number.clone()))
+                    .is_ok()
+                {
+                    return Ok(());
+                }
+            }
+            Err(ScenarioError::Exhausted(number.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::traits::order::Order;
+
+    #[test]
+    fn round_cards_parse_test() {
+        let round: RoundCards = "player: 4,3; banker: 6,K; player third: 9".parse().unwrap();
+        let values: Vec<i32> = round.dealt().map(pick_value).collect();
+        assert_eq!(values, vec![4, 6, 3, 0, 9]);
+
+        let round: RoundCards = "banker: Qh, 9c; player: As,As".parse().unwrap();
+        assert!(round.player_third.is_none());
+        assert!(matches!(round.banker[1], Pick::Exact(_)));
+
+        assert_eq!(
+            "player: 4; banker: 6,K".parse::<RoundCards>().err(),
+            Some(ScenarioError::Card(ParseError::Count {
+                expected: 2,
+                found: 1
+            }))
+        );
+        assert!(matches!(
+            "player: 4,3".parse::<RoundCards>(),
+            Err(ScenarioError::Syntax(_))
+        ));
+        assert!(matches!(
+            "player: 4,3; dealer: 1,2".parse::<RoundCards>(),
+            Err(ScenarioError::Syntax(_))
+        ));
+        assert!(matches!(
+            "player: 4,3; banker: 6,Z".parse::<RoundCards>(),
+            Err(ScenarioError::Card(ParseError::Number(_)))
+        ));
+    }
+
+    #[test]
+    fn shoe_builder_test() {
+        let shoe = ShoeBuilder::new(1)
+            .scenario("player: 4,A; banker: 6,K; player third: 9")
+            .unwrap()
+            .scenario("player: Ah,7c; banker: Ah,Kd")
+            .unwrap()
+            .build();
+        // one deck has a single ace of hearts
+        assert_eq!(shoe.err(), Some(ScenarioError::Exhausted("Ah".to_string())));
+
+        let mut shoe = ShoeBuilder::new(1)
+            .scenario("player: 4,A; banker: 6,K; player third: 9")
+            .unwrap()
+            .scenario("player: 7,K; banker: 2,2; banker third: 9h")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(shoe.get_card_idx(), 0);
+        let values: Vec<i32> = (0..10).map(|_| card_value(&shoe.draw().unwrap())).collect();
+        assert_eq!(values, vec![4, 6, 1, 0, 9, 7, 2, 0, 2, 9]);
+        assert_eq!(shoe.cards()[9].suit.order(), 1);
+
+        // a fifth nine of one deck
+        let shoe = ShoeBuilder::new(1)
+            .scenario("player: 9,9; banker: 9,9")
+            .unwrap()
+            .scenario("player: 9,K; banker: K,K")
+            .unwrap()
+            .build();
+        assert_eq!(shoe.err(), Some(ScenarioError::Exhausted("9".to_string())));
+
+        // every card of a deck once, in hands of 6 to 9 that settle on four
+        // cards, then one round more
+        let mut hands: Vec<String> = Vec::new();
+        for zero in ["T", "J", "Q", "K"] {
+            for other in ["6", "7", "8", "9"] {
+                hands.push(format!("{},{}", zero, other));
+            }
+        }
+        for (hand, num) in [("A,5", 4), ("2,4", 4), ("3,3", 2)] {
+            hands.extend((0..num).map(|_| hand.to_string()));
+        }
+        let mut builder = ShoeBuilder::new(1);
+        for round in hands.chunks(2) {
+            let scenario = format!("player: {}; banker: {}", round[0], round[1]);
+            builder = builder.scenario(&scenario).unwrap();
+        }
+        assert!(builder.build().is_ok());
+        let builder = builder.scenario("player: T,9; banker: T,9").unwrap();
+        assert_eq!(builder.build().err(), Some(ScenarioError::TooManyCards(52)));
+    }
+
+    #[test]
+    fn shoe_builder_third_card_test() {
+        let error = |scenario: &str| {
+            ShoeBuilder::new(8)
+                .scenario(scenario)
+                .unwrap()
+                .build()
+                .err()
+        };
+        // the player stands on 7, so the 9 would open the next round
+        assert_eq!(
+            error("player: 4,3; banker: 6,K; player third: 9"),
+            Some(ScenarioError::ThirdCard(
+                "the player stands on 7 against 6".to_string()
+            ))
+        );
+        // naturals stand
+        assert!(error("player: 9,9; banker: 9,9; player third: 9").is_some());
+        // the player draws on 2, and the banker on 3 unless the third is an 8
+        assert_eq!(
+            error("player: A,A; banker: 2,K"),
+            Some(ScenarioError::ThirdCard(
+                "the player draws on 2".to_string()
+            ))
+        );
+        assert_eq!(
+            error("player: A,A; banker: 3,K; player third: 8; banker third: 4"),
+            Some(ScenarioError::ThirdCard(
+                "the banker stands on 3".to_string()
+            ))
+        );
+        assert_eq!(
+            error("player: A,A; banker: 3,K; player third: 7"),
+            Some(ScenarioError::ThirdCard(
+                "the banker draws on 3".to_string()
+            ))
+        );
+        assert_eq!(
+            error("player: A,A; banker: 3,K; player third: 7; banker third: 4"),
+            None
+        );
+    }
+}
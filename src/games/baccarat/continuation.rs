@@ -0,0 +1,154 @@
+use rand::seq::SliceRandom;
+
+use super::game::Winner;
+use super::lookup::lookup;
+use super::rule::id_value;
+use super::shoe::Shoe;
+use crate::cards::card::Card;
+use crate::cards::id::CardId;
+use crate::cards::traits::shoe::Shoe as ShoeTrait;
+use crate::error::Error;
+
+// Four dealt and a third each at most.
+pub const MAX_ROUND_CARD_NUM: usize = 6;
+
+// The winners of the rounds left in the shoe, one list per continuation.
+pub struct Distribution {
+    pub continuations: Vec<Vec<Winner>>,
+}
+
+impl Distribution {
+    pub fn round_num(&self) -> usize {
+        self.continuations.iter().map(|winners| winners.len()).sum()
+    }
+
+    // Share of all the rounds left that `winner` takes.
+    pub fn probability(&self, winner: Winner) -> f64 {
+        let count = self
+            .continuations
+            .iter()
+            .flatten()
+            .filter(|other| **other == winner)
+            .count();
+        count as f64 / self.round_num() as f64
+    }
+
+    // Chance `winner` takes the very next round.
+    pub fn next_probability(&self, winner: Winner) -> f64 {
+        let count = self
+            .continuations
+            .iter()
+            .filter(|winners| winners.first() == Some(&winner))
+            .count();
+        count as f64 / self.continuations.len() as f64
+    }
+
+    // How many continuations `winner` takes 0, 1, 2, ... rounds in.
+    pub fn histogram(&self, winner: Winner) -> Vec<usize> {
+        let mut histogram = Vec::new();
+        for winners in self.continuations.iter() {
+            let count = winners.iter().filter(|other| **other == winner).count();
+            if histogram.len() <= count {
+                histogram.resize(count + 1, 0);
+            }
+            histogram[count] += 1;
+        }
+        histogram
+    }
+}
+
+// Plays the rest of `shoe` from its current card `continuation_num` times,
+// shuffling the undealt cards anew each time. A continuation stops once the
+// cut card is out, `reserve` cards before the end, or the shoe can't cover
+// another round. The shoe should sit between rounds, as after `condition_on`
+// with the cards of whole rounds.
+pub fn continuations(shoe: &Shoe, reserve: usize, continuation_num: usize) -> Distribution {
+    let undealt: Vec<CardId> = shoe.cards()[shoe.get_card_idx()..]
+        .iter()
+        .map(|card| CardId::try_from(card).expect("baccarat has no jokers"))
+        .collect();
+    let mut rng = rand::thread_rng();
+    let lookup = lookup();
+    let continuations = (0..continuation_num)
+        .map(|_| {
+            let mut ids = undealt.clone();
+            ids.shuffle(&mut rng);
+            let mut values = ids.iter().map(|id| id_value(*id));
+            let mut winners = Vec::new();
+            while values.len() > reserve && values.len() >= MAX_ROUND_CARD_NUM {
+                winners.extend(lookup.resolve(&mut values));
+            }
+            winners
+        })
+        .collect();
+    Distribution { continuations }
+}
+
+// The outcomes left in a fresh shoe of `deck_num` decks once `observed` has
+// been dealt.
+pub fn continuations_after(
+    deck_num: usize,
+    observed: &[Card],
+    reserve: usize,
+    continuation_num: usize,
+) -> Result<Distribution, Error> {
+    let mut shoe = Shoe::new(deck_num);
+    shoe.condition_on(observed)?;
+    Ok(continuations(&shoe, reserve, continuation_num))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+    use crate::cards::traits::order::Order;
+
+    #[test]
+    fn continuations_test() {
+        let distribution = continuations_after(8, &[], 14, 200).unwrap();
+        assert_eq!(distribution.continuations.len(), 200);
+        let total: f64 = [Winner::PLAYER, Winner::BANKER, Winner::TIE]
+            .iter()
+            .map(|winner| distribution.probability(*winner))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+        // banker 45.9%, player 44.6%
+        assert!((0.43..0.49).contains(&distribution.probability(Winner::BANKER)));
+        assert!((0.42..0.48).contains(&distribution.probability(Winner::PLAYER)));
+        let histogram = distribution.histogram(Winner::TIE);
+        assert_eq!(histogram.iter().sum::<usize>(), 200);
+    }
+
+    #[test]
+    fn continuations_after_test() {
+        // all of a deck seen but three nines and three tens: one round
+        // whatever the order, always a natural
+        let left = |card: &Card| {
+            let number = card.number.order() == Number::Nine.order()
+                || card.number.order() == Number::Ten.order();
+            number && card.suit.order() != Suit::Club.order()
+        };
+        let observed: Vec<Card> = Shoe::new(1)
+            .cards()
+            .iter()
+            .filter(|card| !left(card))
+            .cloned()
+            .collect();
+        assert_eq!(observed.len(), 46);
+        let distribution = continuations_after(1, &observed, 0, 50).unwrap();
+        assert!(distribution
+            .continuations
+            .iter()
+            .all(|winners| winners.len() == 1));
+        let total: f64 = [Winner::PLAYER, Winner::BANKER, Winner::TIE]
+            .iter()
+            .map(|winner| distribution.next_probability(*winner))
+            .sum();
+        assert!((total - 1.0).abs() < 1e-9);
+
+        let mut too_many = observed.clone();
+        too_many.push(observed[0].clone());
+        assert!(continuations_after(1, &too_many, 0, 1).is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use super::game::Winner;
+use super::lookup::{lookup, Lookup};
+use super::rule::card_value;
+use crate::cards::card::Card;
+
+// One round of a decoded shoe.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Round {
+    pub start: usize, // position of its first card
+    pub card_num: u8, // 4 to 6
+    pub player: u8,   // final totals
+    pub banker: u8,
+    pub winner: Winner,
+}
+
+// The rounds of a known shoe order, worked out straight from point values
+// without dealing. It allocates nothing, so a search can decode candidate
+// orders in a loop.
+pub struct Decoder<'a> {
+    values: &'a [u8],
+    card_idx: usize,
+    reserve: usize,
+    lookup: &'static Lookup,
+}
+
+// Decodes `values`, each card's point value in shoe order, until no more
+// than `reserve` cards are left before a round or a round runs out of cards.
+// The values have to be 0 to 9 as `point_values` gives, others index past
+// the lookup tables.
+pub fn decode(values: &[u8], reserve: usize) -> Decoder<'_> {
+    Decoder {
+        values,
+        card_idx: 0,
+        reserve,
+        lookup: lookup(),
+    }
+}
+
+// The point values of a shoe order, e.g. `shoe.cards()`.
+pub fn point_values(cards: &[Card]) -> Vec<u8> {
+    cards.iter().map(|card| card_value(card) as u8).collect()
+}
+
+impl<'a> Decoder<'a> {
+    // Where the next round starts.
+    pub fn card_idx(&self) -> usize {
+        self.card_idx
+    }
+}
+
+impl<'a> Iterator for Decoder<'a> {
+    type Item = Round;
+
+    #[inline]
+    fn next(&mut self) -> Option<Round> {
+        let start = self.card_idx;
+        let values = self.values.get(start..)?;
+        if values.len() <= self.reserve {
+            return None;
+        }
+        let mut rest = values.iter().map(|value| {
+            debug_assert!(*value < 10, "{} is not a point value", value);
+            *value as i32
+        });
+        let (player, banker) = self.lookup.totals(&mut rest)?;
+        let card_num = values.len() - rest.len();
+        self.card_idx += card_num;
+        Some(Round {
+            start,
+            card_num: card_num as u8,
+            player,
+            banker,
+            winner: self.lookup.winners[player as usize][banker as usize],
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::traits::shoe::Shoe as ShoeTrait;
+    use crate::cards::traits::value::Value;
+    use crate::games::baccarat::game::Game;
+    use crate::games::baccarat::rule::resolve;
+
+    #[test]
+    fn decode_game_test() {
+        for seed in 0..20 {
+            let mut game = Game::new(8);
+            game.shoe.shuffle_with_seed(seed);
+            let values = point_values(game.shoe.cards());
+            let mut decoder = decode(&values, 0);
+            let mut round_num = 0;
+            while game.play_one_round().is_ok() {
+                let result = game.results.last().unwrap();
+                let round = decoder.next().unwrap();
+                let player = result.hands.player.as_ref().unwrap();
+                let banker = result.hands.banker.as_ref().unwrap();
+                assert_eq!(round.winner, result.winner);
+                assert_eq!(round.player as i32, player.value());
+                assert_eq!(round.banker as i32, banker.value());
+                let third_num = player.third.is_some() as usize + banker.third.is_some() as usize;
+                assert_eq!(round.card_num as usize, 4 + third_num);
+                assert_eq!(
+                    round.start + round.card_num as usize,
+                    game.shoe.get_card_idx()
+                );
+                round_num += 1;
+            }
+            // the game ran out where the decoder did
+            assert!(decoder.next().is_none());
+            assert_eq!(game.results.len(), round_num);
+        }
+    }
+
+    #[test]
+    fn decode_reserve_test() {
+        let mut game = Game::new(8);
+        game.shoe.shuffle_with_seed(1);
+        let values = point_values(game.shoe.cards());
+        const RESERVE: usize = 14;
+        let mut decoder = decode(&values, RESERVE);
+        let mut rule_values = values.iter().map(|value| *value as i32);
+        for round in decoder.by_ref() {
+            assert!(values.len() - round.start > RESERVE);
+            assert_eq!(Some(round.winner), resolve(&mut rule_values));
+        }
+        assert!(values.len() - decoder.card_idx() <= RESERVE);
+        assert!(decode(&values[..3], 0).next().is_none());
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "not a point value")]
+    fn decode_value_test() {
+        decode(&[1, 2, 10, 4, 5, 6], 0).next();
+    }
+}
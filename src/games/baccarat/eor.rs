@@ -0,0 +1,398 @@
+use std::fmt;
+
+use super::game::Winner;
+use super::hand::Hand;
+use super::rule::{
+    contain_natural, payout, should_banker_hit, should_player_hit, PAIR_PAYS, VALUATION,
+};
+use super::shoe::Shoe;
+use crate::cards::card::Card;
+use crate::cards::number::{all_numbers, Number, ALL_NUMBER_NUM};
+use crate::cards::suit::{Suit, ALL_SUIT_NUM};
+use crate::cards::traits::order::Order;
+use crate::cards::traits::shoe::Shoe as ShoeTrait;
+use crate::cards::traits::value::Value;
+use crate::cards::valuation::RankValuation;
+
+// Hand values 0 to 9.
+const POINT_NUM: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Bet {
+    Player,
+    Banker,
+    Tie,
+    PlayerPair,
+    BankerPair,
+}
+
+pub const ALL_BET_NUM: usize = 5;
+
+pub static ALL_BETS: [Bet; ALL_BET_NUM] = [
+    Bet::Player,
+    Bet::Banker,
+    Bet::Tie,
+    Bet::PlayerPair,
+    Bet::BankerPair,
+];
+
+impl fmt::Display for Bet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Bet::Player => "player",
+            Bet::Banker => "banker",
+            Bet::Tie => "tie",
+            Bet::PlayerPair => "player pair",
+            Bet::BankerPair => "banker pair",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// Undealt cards of each number, indexed by `Number::order`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Composition {
+    pub counts: [u32; ALL_NUMBER_NUM],
+}
+
+impl Composition {
+    pub fn new(deck_num: usize) -> Composition {
+        Composition {
+            counts: [(ALL_SUIT_NUM * deck_num) as u32; ALL_NUMBER_NUM],
+        }
+    }
+
+    // What is left behind the shoe's current card.
+    pub fn from_shoe(shoe: &Shoe) -> Composition {
+        let mut counts = [0; ALL_NUMBER_NUM];
+        for card in shoe.cards()[shoe.get_card_idx()..].iter() {
+            counts[card.number.order() as usize] += 1;
+        }
+        Composition { counts }
+    }
+
+    pub fn card_num(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+
+    // None once no `number` is left.
+    pub fn remove(&self, number: &Number) -> Option<Composition> {
+        let mut composition = self.clone();
+        let count = &mut composition.counts[number.order() as usize];
+        *count = count.checked_sub(1)?;
+        Some(composition)
+    }
+
+    fn point_counts(&self) -> [u32; POINT_NUM] {
+        let mut counts = [0; POINT_NUM];
+        for number in all_numbers() {
+            counts[VALUATION.value(number) as usize] += self.counts[number.order() as usize];
+        }
+        counts
+    }
+}
+
+// Chances of each outcome of the next round.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    pub player: f64,
+    pub banker: f64,
+    pub tie: f64,
+    pub player_pair: f64,
+    pub banker_pair: f64,
+}
+
+impl Outcome {
+    // Deals every order of values the round could take, weighted by its
+    // chance. The pairs only look at two cards and don't need the rules.
+    pub fn exact(composition: &Composition) -> Outcome {
+        let mut draw = Draw::new(composition);
+        let mut winners = [0.0; 3];
+        draw.each(1.0, &mut |draw, p1, weight| {
+            draw.each(weight, &mut |draw, b1, weight| {
+                draw.each(weight, &mut |draw, p2, weight| {
+                    draw.each(weight, &mut |draw, b2, weight| {
+                        let player = Hand::new(p1.clone(), p2.clone());
+                        let banker = Hand::new(b1.clone(), b2.clone());
+                        draw.play(player, banker, weight, &mut winners);
+                    })
+                })
+            })
+        });
+
+        let card_num = composition.card_num() as f64;
+        let pair = composition
+            .counts
+            .iter()
+            .map(|count| *count as f64 * (*count as f64 - 1.0).max(0.0))
+            .sum::<f64>()
+            / (card_num * (card_num - 1.0));
+        Outcome {
+            player: winners[0],
+            banker: winners[1],
+            tie: winners[2],
+            player_pair: pair,
+            banker_pair: pair,
+        }
+    }
+
+    fn winner(&self, winner: Winner) -> f64 {
+        match winner {
+            Winner::PLAYER => self.player,
+            Winner::BANKER => self.banker,
+            Winner::TIE => self.tie,
+        }
+    }
+
+    // Net win per unit bet.
+    pub fn expectation(&self, bet: Bet) -> f64 {
+        let winners = [Winner::PLAYER, Winner::BANKER, Winner::TIE];
+        let main = |on: Winner| {
+            winners
+                .iter()
+                .map(|winner| self.winner(*winner) * payout(on, *winner))
+                .sum()
+        };
+        let pair = |chance: f64| chance * PAIR_PAYS - (1.0 - chance);
+        match bet {
+            Bet::Player => main(Winner::PLAYER),
+            Bet::Banker => main(Winner::BANKER),
+            Bet::Tie => main(Winner::TIE),
+            Bet::PlayerPair => pair(self.player_pair),
+            Bet::BankerPair => pair(self.banker_pair),
+        }
+    }
+}
+
+// Takes cards of one value at a time off a composition, one card standing in
+// for all numbers of its value.
+struct Draw {
+    counts: [u32; POINT_NUM],
+    card_num: u32,
+    cards: [Card; POINT_NUM],
+}
+
+impl Draw {
+    fn new(composition: &Composition) -> Draw {
+        let card = |value: usize| {
+            let number = if value == 0 {
+                Number::Ten
+            } else {
+                all_numbers()[value - 1].clone()
+            };
+            Card::new(Suit::Spade, number)
+        };
+        Draw {
+            counts: composition.point_counts(),
+            card_num: composition.card_num(),
+            cards: std::array::from_fn(card),
+        }
+    }
+
+    fn each(&mut self, weight: f64, f: &mut dyn FnMut(&mut Draw, &Card, f64)) {
+        for value in 0..POINT_NUM {
+            let count = self.counts[value];
+            if count == 0 {
+                continue;
+            }
+            let chance = count as f64 / self.card_num as f64;
+            self.counts[value] -= 1;
+            self.card_num -= 1;
+            let card = self.cards[value].clone();
+            f(self, &card, weight * chance);
+            self.counts[value] += 1;
+            self.card_num += 1;
+        }
+    }
+
+    fn play(&mut self, mut player: Hand, banker: Hand, weight: f64, winners: &mut [f64; 3]) {
+        if contain_natural(&player, &banker) {
+            settle(&player, &banker, weight, winners);
+            return;
+        }
+        if !should_player_hit(&player) {
+            self.banker_third(&player, banker, weight, winners);
+            return;
+        }
+        self.each(weight, &mut |draw, card, weight| {
+            player.third = Some(card.clone());
+            draw.banker_third(&player, banker.clone(), weight, winners);
+        });
+    }
+
+    fn banker_third(
+        &mut self,
+        player: &Hand,
+        mut banker: Hand,
+        weight: f64,
+        winners: &mut [f64; 3],
+    ) {
+        if !should_banker_hit(&player.third, &banker) {
+            settle(player, &banker, weight, winners);
+            return;
+        }
+        self.each(weight, &mut |_, card, weight| {
+            banker.third = Some(card.clone());
+            settle(player, &banker, weight, winners);
+        });
+    }
+}
+
+fn settle(player: &Hand, banker: &Hand, weight: f64, winners: &mut [f64; 3]) {
+    let idx = match player.value().cmp(&banker.value()) {
+        std::cmp::Ordering::Greater => 0,
+        std::cmp::Ordering::Less => 1,
+        std::cmp::Ordering::Equal => 2,
+    };
+    winners[idx] += weight;
+}
+
+// How removing one card of each number moves each bet's expectation, the
+// table card counting systems take their tags from.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EorTable {
+    pub composition: Composition,
+    pub rows: Vec<EorRow>,
+}
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EorRow {
+    pub bet: Bet,
+    pub expectation: f64,
+    // indexed by `Number::order`, None when no card of the number is left
+    pub effects: [Option<f64>; ALL_NUMBER_NUM],
+}
+
+impl EorTable {
+    pub fn new(deck_num: usize) -> EorTable {
+        EorTable::from_composition(&Composition::new(deck_num))
+    }
+
+    pub fn from_composition(composition: &Composition) -> EorTable {
+        let base = Outcome::exact(composition);
+        let removed: Vec<Option<Outcome>> = all_numbers()
+            .iter()
+            .map(|number| composition.remove(number).map(|rest| Outcome::exact(&rest)))
+            .collect();
+        let rows = ALL_BETS
+            .iter()
+            .map(|bet| {
+                let expectation = base.expectation(*bet);
+                EorRow {
+                    bet: *bet,
+                    expectation,
+                    effects: std::array::from_fn(|idx| {
+                        removed[idx]
+                            .as_ref()
+                            .map(|outcome| outcome.expectation(*bet) - expectation)
+                    }),
+                }
+            })
+            .collect();
+        EorTable {
+            composition: composition.clone(),
+            rows,
+        }
+    }
+
+    pub fn row(&self, bet: Bet) -> &EorRow {
+        self.rows.iter().find(|row| row.bet == bet).unwrap()
+    }
+}
+
+// One line per bet, expectations and effects in percent.
+impl fmt::Display for EorTable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:<12} {:>9}", "bet", "ev")?;
+        for number in all_numbers() {
+            write!(f, " {:>8}", number.to_string())?;
+        }
+        writeln!(f)?;
+        for row in self.rows.iter() {
+            write!(
+                f,
+                "{:<12} {:>9.4}",
+                row.bet.to_string(),
+                row.expectation * 100.0
+            )?;
+            for effect in row.effects.iter() {
+                match effect {
+                    Some(effect) => write!(f, " {:>8.4}", effect * 100.0)?,
+                    None => write!(f, " {:>8}", "-")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcome_exact_test() {
+        // the well known 8 deck figures
+        let outcome = Outcome::exact(&Composition::new(8));
+        assert!((outcome.player + outcome.banker + outcome.tie - 1.0).abs() < 1e-12);
+        assert!((outcome.banker - 0.458597).abs() < 1e-6);
+        assert!((outcome.player - 0.446247).abs() < 1e-6);
+        assert!((outcome.tie - 0.095156).abs() < 1e-6);
+        assert!((outcome.expectation(Bet::Banker) + 0.010579).abs() < 1e-6);
+        assert!((outcome.expectation(Bet::Player) + 0.012351).abs() < 1e-6);
+        assert!((outcome.expectation(Bet::Tie) + 0.143596).abs() < 1e-6);
+        assert!((outcome.expectation(Bet::PlayerPair) + 0.103614).abs() < 1e-6);
+    }
+
+    #[test]
+    fn eor_table_test() {
+        let table = EorTable::new(8);
+        let composition = &table.composition;
+        for row in table.rows.iter() {
+            // removing a random card leaves the expectation alone
+            let total: f64 = row
+                .effects
+                .iter()
+                .zip(composition.counts.iter())
+                .map(|(effect, count)| effect.unwrap() * *count as f64)
+                .sum();
+            assert!(total.abs() < 1e-9, "{}", row.bet);
+            // ten and faces count the same
+            let ten = row.effects[Number::Ten.order() as usize].unwrap();
+            let king = row.effects[Number::King.order() as usize].unwrap();
+            assert!((ten - king).abs() < 1e-12);
+        }
+        // small cards help the banker, fives and sixes the player
+        let banker = table.row(Bet::Banker);
+        assert!(banker.effects[Number::Four.order() as usize].unwrap() > 0.0);
+        assert!(banker.effects[Number::Six.order() as usize].unwrap() < 0.0);
+        let player = table.row(Bet::Player);
+        assert!(player.effects[Number::Four.order() as usize].unwrap() < 0.0);
+        assert!(player.effects[Number::Six.order() as usize].unwrap() > 0.0);
+
+        let text = table.to_string();
+        assert_eq!(text.lines().count(), ALL_BET_NUM + 1);
+        assert!(text.lines().nth(2).unwrap().starts_with("banker"));
+    }
+
+    #[test]
+    fn eor_table_exhausted_test() {
+        let mut shoe = Shoe::new(1);
+        let kings: Vec<Card> = (0..ALL_SUIT_NUM)
+            .map(|idx| Card::new(crate::cards::suit::all_suits()[idx].clone(), Number::King))
+            .collect();
+        shoe.condition_on(&kings).unwrap();
+        let composition = Composition::from_shoe(&shoe);
+        assert_eq!(composition.card_num(), 48);
+        assert!(composition.remove(&Number::King).is_none());
+        let table = EorTable::from_composition(&composition);
+        assert!(table.row(Bet::Tie).effects[Number::King.order() as usize].is_none());
+        assert!(table.to_string().contains(" -"));
+    }
+}
@@ -1,19 +1,70 @@
-use super::rule::{contain_natural, should_banker_hit, should_player_hit};
+use super::observer::{Observer, Observers, Side};
+use super::rule::{banker_hits, is_natural_value, player_hits, VALUATION};
 use super::{hand::Hand, hand::Hands, shoe::Shoe};
+use crate::cards::card::Card;
 use crate::cards::traits::shoe::Shoe as ShoeTrait;
-use crate::cards::traits::value::Value;
+use crate::cards::valuation::RankValuation;
+use crate::error::Error;
+use std::sync::Arc;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     pub shoe: Shoe,
     pub hands: Hands,
     pub results: Vec<Result>,
+    // Rounds only go to the observers, so a long run keeps no history.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub skip_results: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub observers: Observers,
+    // How each number counts, `rule::VALUATION` unless set. A saved game
+    // comes back with the standard count.
+    #[cfg_attr(feature = "serde", serde(skip, default = "standard_valuation"))]
+    pub valuation: &'static (dyn RankValuation + Sync),
 }
+
+fn standard_valuation() -> &'static (dyn RankValuation + Sync) {
+    &VALUATION
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Result {
     pub hands: Hands,
     pub winner: Winner,
 }
 
-#[derive(PartialEq, Eq, Debug)]
+// A game as it stood between rounds. It shares the shoe order with the game
+// and copies the results once, which its clones share, so it stands on its
+// own whatever the game does next.
+#[derive(Clone)]
+pub struct Snapshot {
+    shoe: Shoe,
+    results: Arc<Vec<Result>>,
+    valuation: &'static (dyn RankValuation + Sync),
+}
+
+impl Snapshot {
+    pub fn round_num(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn results(&self) -> &[Result] {
+        &self.results
+    }
+
+    // A game to play on from here, the snapshot kept as it is.
+    pub fn branch(&self) -> Game {
+        let mut branch = Game::with_shoe(self.shoe.clone());
+        branch.results = self.results.to_vec();
+        branch.valuation = self.valuation;
+        branch
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Winner {
     PLAYER,
     BANKER,
@@ -22,51 +73,148 @@ pub enum Winner {
 
 impl Game {
     pub fn new(deck_num: usize) -> Game {
+        Game::with_shoe(Shoe::new(deck_num))
+    }
+
+    // Plays a prepared shoe, see `builder::ShoeBuilder`.
+    pub fn with_shoe(shoe: Shoe) -> Game {
         Game {
-            shoe: Shoe::new(deck_num),
+            shoe,
             hands: Hands {
                 player: None,
                 banker: None,
             },
             results: Vec::new(),
+            skip_results: false,
+            observers: Observers::default(),
+            valuation: standard_valuation(),
         }
     }
 
-    fn draw_first_two(&mut self) {
-        let [card0, card1, card2, card3] = self.shoe.draw_four();
+    // A new shoe from the same cards, results cleared and observers kept.
+    // Allocates nothing once the game has been played through.
+    pub fn reset(&mut self) {
+        self.shoe.reshuffle();
+        self.hands = Hands {
+            player: None,
+            banker: None,
+        };
+        self.results.clear();
+    }
+
+    pub fn add_observer(&mut self, observer: impl Observer + Send + 'static) {
+        self.observers.push(Box::new(observer));
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            shoe: self.shoe.clone(),
+            results: Arc::new(self.results.clone()),
+            valuation: self.valuation,
+        }
+    }
+
+    // Back to the snapshot, any round in play dropped. Observers and
+    // `skip_results` stay as they are.
+    pub fn rollback(&mut self, snapshot: &Snapshot) {
+        self.shoe = snapshot.shoe.clone();
+        self.results.clone_from(&snapshot.results);
+        self.valuation = snapshot.valuation;
+        self.hands = Hands {
+            player: None,
+            banker: None,
+        };
+    }
+
+    // The same game to explore without touching this one, e.g. a what-if
+    // with `set_next_card`. The branch starts with no observers.
+    pub fn branch(&self) -> Game {
+        let mut branch = Game::with_shoe(self.shoe.clone());
+        branch.results = self.results.clone();
+        branch.valuation = self.valuation;
+        branch
+    }
+
+    // Makes `card` the next one dealt, the card it replaces going where
+    // `card` was.
+    pub fn set_next_card(&mut self, card: &Card) -> std::result::Result<(), Error> {
+        self.shoe.put_next(card)
+    }
+
+    fn draw_first_two(&mut self) -> std::result::Result<(), Error> {
+        let card_idx = self.shoe.get_card_idx();
+        let cards: [Card; 4] = draw_n(&mut self.shoe, &mut self.observers)?;
+        // player, banker, player, banker
+        if !self.observers.is_empty() {
+            for (offset, card) in cards.iter().enumerate() {
+                let side = if offset % 2 == 0 {
+                    Side::PLAYER
+                } else {
+                    Side::BANKER
+                };
+                self.observers
+                    .notify(|observer| observer.card_dealt(card_idx + offset, side, card));
+            }
+        }
+        let [card0, card1, card2, card3] = cards;
         let player_hand = Hand::new(card0, card2);
         let bander_hand = Hand::new(card1, card3);
         self.hands.player = Some(player_hand);
         self.hands.banker = Some(bander_hand);
+        Ok(())
     }
 
-    fn draw_third(&mut self) {
+    fn draw_third(&mut self) -> std::result::Result<(), Error> {
         if let (Some(player_hand), Some(banker_hand)) =
             (self.hands.player.as_mut(), self.hands.banker.as_mut())
         {
-            if !contain_natural(player_hand, banker_hand) {
-                if should_player_hit(player_hand) {
-                    let card = self.shoe.draw();
-                    player_hand.third = Some(card);
+            let player_value = player_hand.value_with(self.valuation);
+            let banker_value = banker_hand.value_with(self.valuation);
+            if is_natural_value(player_value) || is_natural_value(banker_value) {
+                self.observers
+                    .notify(|observer| observer.natural_declared(player_value, banker_value));
+            } else {
+                let hits = player_hits(player_value);
+                self.observers
+                    .notify(|observer| observer.third_card_decided(Side::PLAYER, hits));
+                if hits {
+                    player_hand.third = Some(draw_third_card(
+                        &mut self.shoe,
+                        &mut self.observers,
+                        Side::PLAYER,
+                    )?);
                 }
-                if should_banker_hit(&player_hand.third, banker_hand) {
-                    let card = self.shoe.draw();
-                    banker_hand.third = Some(card);
+                let player_third = player_hand
+                    .third
+                    .as_ref()
+                    .map(|card| self.valuation.card_value(card));
+                let hits = banker_hits(player_third, banker_value);
+                self.observers
+                    .notify(|observer| observer.third_card_decided(Side::BANKER, hits));
+                if hits {
+                    banker_hand.third = Some(draw_third_card(
+                        &mut self.shoe,
+                        &mut self.observers,
+                        Side::BANKER,
+                    )?);
                 }
                 // let [card0, card1] = self.shoe.draw_two();
                 // player_hand.third = Some(card0);
                 // banker_hand.third = Some(card1);
             }
         }
+        Ok(())
     }
 
     fn get_winner(&self) -> Option<Winner> {
         if let (Some(player_hand), Some(banker_hand)) =
             (self.hands.player.as_ref(), self.hands.banker.as_ref())
         {
-            Some(if player_hand.value() > banker_hand.value() {
+            let player_value = player_hand.value_with(self.valuation);
+            let banker_value = banker_hand.value_with(self.valuation);
+            Some(if player_value > banker_value {
                 Winner::PLAYER
-            } else if player_hand.value() < banker_hand.value() {
+            } else if player_value < banker_value {
                 Winner::BANKER
             } else {
                 Winner::TIE
@@ -76,11 +224,12 @@ impl Game {
         }
     }
 
-    pub fn play_one_round(&mut self) {
-        self.draw_first_two();
-        self.draw_third();
+    // Errs when the shoe runs out mid round, leaving the dealt hands in place.
+    pub fn play_one_round(&mut self) -> std::result::Result<(), Error> {
+        self.draw_first_two()?;
+        self.draw_third()?;
         if let Some(winner) = self.get_winner() {
-            self.results.push(Result {
+            let result = Result {
                 hands: std::mem::replace(
                     &mut self.hands,
                     Hands {
@@ -88,15 +237,54 @@ impl Game {
                         banker: None,
                     },
                 ),
-                winner: winner,
-            });
+                winner,
+            };
+            self.observers
+                .notify(|observer| observer.round_resolved(&result));
+            if !self.skip_results {
+                self.results.push(result);
+            }
         }
+        Ok(())
     }
 }
 
+// Draws from the shoe, telling the observers when it runs out.
+fn draw_n<const SIZE: usize>(
+    shoe: &mut Shoe,
+    observers: &mut Observers,
+) -> std::result::Result<[Card; SIZE], Error> {
+    shoe.draw_n::<SIZE>()
+        .inspect_err(|error| observers.notify(|observer| observer.shoe_exhausted(error)))
+}
+
+fn draw_third_card(
+    shoe: &mut Shoe,
+    observers: &mut Observers,
+    side: Side,
+) -> std::result::Result<Card, Error> {
+    let card_idx = shoe.get_card_idx();
+    let [card] = draw_n(shoe, observers)?;
+    observers.notify(|observer| observer.card_dealt(card_idx, side, &card));
+    Ok(card)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::games::baccarat::builder::ShoeBuilder;
+
+    fn play(scenarios: &[&str]) -> Game {
+        let mut builder = ShoeBuilder::new(8);
+        for scenario in scenarios.iter() {
+            builder = builder.scenario(scenario).unwrap();
+        }
+        let mut game = Game::with_shoe(builder.build().unwrap());
+        for _ in scenarios.iter() {
+            game.play_one_round().unwrap();
+        }
+        game
+    }
 
     #[test]
     fn game_test() {
@@ -104,45 +292,297 @@ mod tests {
         let mut game = Game::new(deck_num);
 
         // A, 3, 5 vs 2, 4
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::PLAYER);
 
         // 6, 8, T vs 7, 9
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::BANKER);
 
         // J, K, 2 vs Q, A, 3
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::BANKER);
 
         // 4, 6, 8 vs 5, 7, 9
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::PLAYER);
 
         // T, Q, A vs J, K, 2
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::BANKER);
 
         // 3, 5 vs 4, 6
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::PLAYER);
 
         // 7, 9 vs 8, T
-        game.play_one_round();
-        assert!(!game.results.last().is_none());
+        game.play_one_round().unwrap();
+        assert!(game.results.last().is_some());
         let result = game.results.last().unwrap();
         assert_eq!(result.winner, Winner::BANKER);
     }
+
+    #[test]
+    fn game_scenario_test() {
+        let game = play(&[
+            // naturals stand
+            "player: 4,4; banker: 9,K",
+            "player: 6,2; banker: 5,3",
+            // player draws on 5, banker on 3 unless the third is an 8
+            "player: 2,3; banker: 3,K; player third: 9; banker third: 2",
+            "player: K,Q; banker: 3,K; player third: 8",
+            // banker draws on 5 when the player stands
+            "player: 7,K; banker: 2,3; banker third: A",
+            // tie
+            "player: 6,K; banker: 3,3",
+        ]);
+        let winners: Vec<Winner> = game.results.iter().map(|result| result.winner).collect();
+        assert_eq!(
+            winners,
+            vec![
+                Winner::BANKER,
+                Winner::TIE,
+                Winner::BANKER,
+                Winner::PLAYER,
+                Winner::PLAYER,
+                Winner::TIE,
+            ]
+        );
+        let thirds: Vec<(bool, bool)> = game
+            .results
+            .iter()
+            .map(|result| {
+                (
+                    result.hands.player.as_ref().unwrap().third.is_some(),
+                    result.hands.banker.as_ref().unwrap().third.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            thirds,
+            vec![
+                (false, false),
+                (false, false),
+                (true, true),
+                (true, false),
+                (false, true),
+                (false, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn game_valuation_test() {
+        use crate::cards::valuation::ValueTable;
+
+        // with every card counting nothing both sides draw and tie
+        static NOTHING: ValueTable = ValueTable([0; 13]);
+        let mut game = Game::new(8);
+        game.valuation = &NOTHING;
+        for _ in 0..3 {
+            game.play_one_round().unwrap();
+        }
+        assert!(game
+            .results
+            .iter()
+            .all(|result| result.winner == Winner::TIE));
+        assert_eq!(game.shoe.get_card_idx(), 18);
+        // clones and branches count the same way
+        for mut branch in [game.clone(), game.branch()] {
+            branch.play_one_round().unwrap();
+            assert_eq!(branch.results[3].winner, Winner::TIE);
+        }
+    }
+
+    #[test]
+    fn game_snapshot_test() {
+        use crate::cards::number::Number;
+        use crate::cards::suit::Suit;
+        use crate::cards::traits::hash::ValueHash;
+
+        let winners = |game: &Game| -> Vec<Winner> {
+            game.results.iter().map(|result| result.winner).collect()
+        };
+        let mut game = Game::new(8);
+        game.shoe.shuffle_with_seed(3);
+        for _ in 0..5 {
+            game.play_one_round().unwrap();
+        }
+        let snapshot = game.snapshot();
+        assert_eq!(snapshot.round_num(), 5);
+        for _ in 0..5 {
+            game.play_one_round().unwrap();
+        }
+        // dealing leaves the order shared
+        assert!(std::ptr::eq(snapshot.shoe.cards(), game.shoe.cards()));
+        let played = winners(&game);
+
+        game.rollback(&snapshot);
+        assert_eq!(game.results.len(), 5);
+        assert_eq!(game.shoe.get_card_idx(), snapshot.shoe.get_card_idx());
+        for _ in 0..5 {
+            game.play_one_round().unwrap();
+        }
+        assert_eq!(winners(&game), played);
+
+        // what if the next card is a nine
+        game.rollback(&snapshot);
+        let nine = Card::new(Suit::Heart, Number::Nine);
+        let next = game.shoe.get_card_ref(game.shoe.get_card_idx()).clone();
+        let mut branch = game.branch();
+        branch.set_next_card(&nine).unwrap();
+        let card_idx = branch.shoe.get_card_idx();
+        assert_eq!(
+            branch.shoe.get_card_ref(card_idx).value_hash(),
+            nine.value_hash()
+        );
+        assert!(!std::ptr::eq(branch.shoe.cards(), game.shoe.cards()));
+        branch.play_one_round().unwrap();
+        let player = branch
+            .results
+            .last()
+            .unwrap()
+            .hands
+            .player
+            .as_ref()
+            .unwrap();
+        assert_eq!(player.first.value_hash(), nine.value_hash());
+
+        // the live game and the snapshot are untouched
+        assert_eq!(game.results.len(), 5);
+        assert_eq!(
+            game.shoe.get_card_ref(card_idx).value_hash(),
+            next.value_hash()
+        );
+        assert!(std::ptr::eq(snapshot.shoe.cards(), game.shoe.cards()));
+        assert_eq!(snapshot.branch().results.len(), 5);
+    }
+
+    #[test]
+    fn game_snapshot_order_test() {
+        use crate::cards::number::Number;
+        use crate::cards::suit::Suit;
+
+        let winners = |results: &[Result]| -> Vec<Winner> {
+            results.iter().map(|result| result.winner).collect()
+        };
+        let mut game = Game::new(8);
+        game.shoe.shuffle_with_seed(5);
+        game.play_one_round().unwrap();
+        let older = game.snapshot();
+        for _ in 0..4 {
+            game.play_one_round().unwrap();
+        }
+        let newer = game.snapshot();
+        let played = winners(&game.results);
+
+        // older, a different line of play, then newer again
+        game.rollback(&older);
+        let nine = Card::new(Suit::Heart, Number::Nine);
+        game.set_next_card(&nine).unwrap();
+        for _ in 0..6 {
+            game.play_one_round().unwrap();
+        }
+        game.rollback(&newer);
+        assert_eq!(winners(&game.results), played);
+        assert_eq!(game.shoe.get_card_idx(), newer.shoe.get_card_idx());
+        assert!(std::ptr::eq(newer.shoe.cards(), game.shoe.cards()));
+
+        // a reset game comes back with its results
+        game.reset();
+        game.rollback(&newer);
+        assert_eq!(winners(&game.results), played);
+        assert_eq!(winners(newer.results()), played);
+
+        // any game can go back to it, or branch off with nothing to hand
+        let mut other = Game::new(1);
+        other.rollback(&older);
+        assert_eq!(other.results.len(), 1);
+        let mut branch = newer.branch();
+        assert_eq!(winners(&branch.results), played);
+        branch.play_one_round().unwrap();
+        assert_eq!(newer.round_num(), 5);
+    }
+
+    #[test]
+    fn game_reset_test() {
+        use crate::games::baccarat::observer::Observer;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Arc;
+
+        struct Counter(Arc<AtomicUsize>);
+        impl Observer for Counter {
+            fn round_resolved(&mut self, _result: &Result) {
+                self.0.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        let rounds = Arc::new(AtomicUsize::new(0));
+        let mut game = Game::new(8);
+        game.add_observer(Counter(rounds.clone()));
+        for _ in 0..3 {
+            game.reset();
+            for _ in 0..50 {
+                game.play_one_round().unwrap();
+            }
+            assert_eq!(game.results.len(), 50);
+        }
+        assert_eq!(rounds.load(Ordering::Relaxed), 150);
+
+        game.skip_results = true;
+        game.reset();
+        assert!(game.results.is_empty());
+        for _ in 0..50 {
+            game.play_one_round().unwrap();
+        }
+        assert!(game.results.is_empty());
+        assert_eq!(rounds.load(Ordering::Relaxed), 200);
+        assert_eq!(game.shoe.validate(), Ok(()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn game_serde_test() {
+        use crate::cards::traits::hash::ValueHash;
+
+        let mut game = Game::new(8);
+        game.shoe.shuffle();
+        for _ in 0..10 {
+            game.play_one_round().unwrap();
+        }
+
+        // resume mid-shoe
+        let saved = serde_json::to_string(&game).unwrap();
+        let mut resumed: Game = serde_json::from_str(&saved).unwrap();
+        assert_eq!(resumed.shoe.get_card_idx(), game.shoe.get_card_idx());
+        assert_eq!(resumed.results.len(), 10);
+        for _ in 0..10 {
+            game.play_one_round().unwrap();
+            resumed.play_one_round().unwrap();
+        }
+        for (result, resumed_result) in game.results.iter().zip(resumed.results.iter()) {
+            assert_eq!(result.winner, resumed_result.winner);
+            let player = result.hands.player.as_ref().unwrap();
+            let resumed_player = resumed_result.hands.player.as_ref().unwrap();
+            assert_eq!(player.first.value_hash(), resumed_player.first.value_hash());
+        }
+
+        // results export
+        let exported = serde_json::to_value(&game.results).unwrap();
+        assert_eq!(exported.as_array().unwrap().len(), 20);
+        let winner = exported[0]["winner"].as_str().unwrap();
+        assert!(["PLAYER", "BANKER", "TIE"].contains(&winner));
+    }
 }
@@ -1,8 +1,11 @@
+use crate::cards::card::Card;
 use crate::cards::traits::value::Value;
+use crate::cards::valuation::RankValuation;
 
-use super::card::Card;
+use super::rule::VALUATION;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hand {
     pub first: Card,
     pub second: Card,
@@ -12,24 +15,34 @@ pub struct Hand {
 impl Hand {
     pub fn new(first: Card, second: Card) -> Hand {
         Hand {
-            first: first,
-            second: second,
+            first,
+            second,
             third: None,
         }
     }
 }
 
-impl Value for Hand {
-    fn value(&self) -> i32 {
-        let first = self.first.value();
-        let second = self.second.value();
+impl Hand {
+    // The total under another count of the numbers, see `Game::valuation`.
+    pub fn value_with(&self, valuation: &dyn RankValuation) -> i32 {
+        let first = valuation.card_value(&self.first);
+        let second = valuation.card_value(&self.second);
         let third = match self.third {
-            Some(ref card) => card.value(),
+            Some(ref card) => valuation.card_value(card),
             None => 0,
         };
         (first + second + third) % 10
     }
 }
+
+impl Value for Hand {
+    fn value(&self) -> i32 {
+        self.value_with(&VALUATION)
+    }
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hands {
     pub player: Option<Hand>,
     pub banker: Option<Hand>,
@@ -47,8 +60,8 @@ impl Hands {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cards::number::Number;
     use crate::cards::suit::Suit;
-    use crate::games::baccarat::number::Number;
 
     #[test]
     fn hand_value_test() {
@@ -85,6 +98,22 @@ mod tests {
         assert_eq!(hand.value(), 4);
     }
 
+    #[test]
+    fn hand_value_with_test() {
+        use crate::cards::valuation::ValueTable;
+
+        // A, K, 3 with the jack to king counting 11 to 13
+        let mut hand = Hand::new(
+            Card::new(Suit::Spade, Number::Ace),
+            Card::new(Suit::Spade, Number::King),
+        );
+        hand.third = Some(Card::new(Suit::Spade, Number::Three));
+        let valuation = ValueTable([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]);
+        assert_eq!(hand.value_with(&valuation), 7);
+        assert_eq!(hand.value_with(&VALUATION), hand.value());
+        assert_eq!(hand.value(), 4);
+    }
+
     #[test]
     fn hand_value_compare_test() {
         // A, 2 vs 2, J
@@ -0,0 +1,599 @@
+use std::fmt;
+use std::io::{self, Write};
+
+use super::game::{Game, Result as GameResult, Winner};
+use super::hand::Hand;
+use super::rule::{contain_natural, payout};
+use crate::cards::card::Card;
+use crate::cards::notation::ParseError;
+use crate::cards::traits::hash::ValueHash;
+use crate::cards::traits::shoe::Shoe as ShoeTrait;
+use crate::cards::traits::value::Value;
+
+// A session is a shoe line followed by one block per round:
+//
+//   shoe table-7 decks 8 seed 42
+//   round 1
+//   deal 4s 9h Kd 2c
+//   player draws 7h
+//   banker draws 5h
+//   winner banker
+//   bet player 100 payout -100
+//
+// The deal is listed as it leaves the shoe, player, banker, player, banker.
+// A natural is written as `natural` in place of the two draw lines, and
+// `seed -` is a shoe shuffled without one. Blank lines and lines starting
+// with '#' are skipped.
+
+pub struct Header {
+    pub shoe_id: String,
+    pub deck_num: usize,
+    pub seed: Option<u64>,
+}
+
+pub struct Bet {
+    pub on: Winner,
+    pub amount: f64,
+    pub payout: f64, // net, negative when lost
+}
+
+impl Bet {
+    pub fn settle(on: Winner, amount: f64, winner: Winner) -> Bet {
+        Bet {
+            on,
+            amount,
+            payout: amount * payout(on, winner),
+        }
+    }
+}
+
+pub struct Round {
+    pub number: usize,
+    pub line: usize, // of the `round` line
+    pub deal: [Card; 4],
+    pub natural: bool,
+    pub player_third: Option<Card>,
+    pub banker_third: Option<Card>,
+    pub winner: Winner,
+    pub bets: Vec<Bet>,
+}
+
+pub struct History {
+    pub header: Header,
+    pub rounds: Vec<Round>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HistoryError {
+    Syntax { line: usize, message: String },
+    Card { line: usize, error: ParseError },
+    Rule { line: usize, message: String }, // line of the round
+}
+
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HistoryError::Syntax { line, message } | HistoryError::Rule { line, message } => {
+                write!(f, "line {}: {}", line, message)
+            }
+            HistoryError::Card { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+fn winner_name(winner: Winner) -> &'static str {
+    match winner {
+        Winner::PLAYER => "player",
+        Winner::BANKER => "banker",
+        Winner::TIE => "tie",
+    }
+}
+
+fn parse_winner(token: &str, line: usize) -> Result<Winner, HistoryError> {
+    match token {
+        "player" => Ok(Winner::PLAYER),
+        "banker" => Ok(Winner::BANKER),
+        "tie" => Ok(Winner::TIE),
+        _ => Err(HistoryError::Syntax {
+            line,
+            message: format!("\"{}\" is not player, banker or tie", token),
+        }),
+    }
+}
+
+fn two_cards(hand: &Hand) -> Hand {
+    Hand::new(hand.first.clone(), hand.second.clone())
+}
+
+// Judged on the deal, before any third card.
+fn dealt_natural(player: &Hand, banker: &Hand) -> bool {
+    contain_natural(&two_cards(player), &two_cards(banker))
+}
+
+// Streams rounds as they are played.
+pub struct HistoryWriter<W: Write> {
+    out: W,
+    round: usize,
+}
+
+impl<W: Write> HistoryWriter<W> {
+    pub fn new(mut out: W, header: &Header) -> io::Result<Self> {
+        if header.shoe_id.is_empty() || header.shoe_id.contains(char::is_whitespace) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the shoe id must be a single word",
+            ));
+        }
+        let seed = header.seed.map_or("-".to_string(), |seed| seed.to_string());
+        writeln!(
+            out,
+            "shoe {} decks {} seed {}",
+            header.shoe_id, header.deck_num, seed
+        )?;
+        Ok(HistoryWriter { out, round: 0 })
+    }
+
+    pub fn write_round(&mut self, result: &GameResult, bets: &[Bet]) -> io::Result<()> {
+        let (Some(player), Some(banker)) = (&result.hands.player, &result.hands.banker) else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the round was not dealt",
+            ));
+        };
+        self.round += 1;
+        writeln!(self.out, "round {}", self.round)?;
+        writeln!(
+            self.out,
+            "deal {} {} {} {}",
+            player.first, banker.first, player.second, banker.second
+        )?;
+        if dealt_natural(player, banker) {
+            writeln!(self.out, "natural")?;
+        } else {
+            for (name, third) in [("player", &player.third), ("banker", &banker.third)] {
+                match third {
+                    Some(card) => writeln!(self.out, "{} draws {}", name, card)?,
+                    None => writeln!(self.out, "{} stands", name)?,
+                }
+            }
+        }
+        writeln!(self.out, "winner {}", winner_name(result.winner))?;
+        for bet in bets.iter() {
+            writeln!(
+                self.out,
+                "bet {} {} payout {}",
+                winner_name(bet.on),
+                bet.amount,
+                bet.payout
+            )?;
+        }
+        self.out.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.out
+    }
+}
+
+// Non-empty lines as (line number, words).
+struct Lines<'a> {
+    lines: Vec<(usize, Vec<&'a str>)>,
+    idx: usize,
+    last: usize,
+}
+
+impl<'a> Lines<'a> {
+    fn new(text: &'a str) -> Lines<'a> {
+        let lines = text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| (idx + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(line, words)| (line, words.split_whitespace().collect()))
+            .collect();
+        Lines {
+            lines,
+            idx: 0,
+            last: 0,
+        }
+    }
+
+    fn peek_keyword(&self) -> Option<&'a str> {
+        self.lines.get(self.idx).map(|(_, words)| words[0])
+    }
+
+    // The words after `keyword`, which has to start the next line.
+    fn expect(&mut self, keyword: &str) -> Result<(usize, Vec<&'a str>), HistoryError> {
+        let Some((line, words)) = self.lines.get(self.idx).cloned() else {
+            return Err(HistoryError::Syntax {
+                line: self.last + 1,
+                message: format!("expected `{}`, found the end", keyword),
+            });
+        };
+        self.idx += 1;
+        self.last = line;
+        if words[0] != keyword {
+            return Err(HistoryError::Syntax {
+                line,
+                message: format!("expected `{}`, found `{}`", keyword, words[0]),
+            });
+        }
+        Ok((line, words[1..].to_vec()))
+    }
+}
+
+fn syntax(line: usize, message: &str) -> HistoryError {
+    HistoryError::Syntax {
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn parse_card(token: &str, line: usize) -> Result<Card, HistoryError> {
+    token
+        .parse::<Card>()
+        .map_err(|error| HistoryError::Card { line, error })
+}
+
+fn parse_draw(lines: &mut Lines, name: &str) -> Result<Option<Card>, HistoryError> {
+    let (line, words) = lines.expect(name)?;
+    match words[..] {
+        ["stands"] => Ok(None),
+        ["draws", card] => Ok(Some(parse_card(card, line)?)),
+        _ => Err(syntax(
+            line,
+            &format!("expected `{0} stands` or `{0} draws <card>`", name),
+        )),
+    }
+}
+
+fn parse_header(lines: &mut Lines) -> Result<Header, HistoryError> {
+    let (line, words) = lines.expect("shoe")?;
+    let [shoe_id, "decks", deck_num, "seed", seed] = words[..] else {
+        return Err(syntax(line, "expected `shoe <id> decks <n> seed <n|->`"));
+    };
+    let deck_num = match deck_num.parse::<usize>() {
+        Ok(deck_num) if deck_num > 0 => deck_num,
+        _ => return Err(syntax(line, "bad deck count")),
+    };
+    let seed = match seed {
+        "-" => None,
+        seed => Some(seed.parse::<u64>().map_err(|_| syntax(line, "bad seed"))?),
+    };
+    Ok(Header {
+        shoe_id: shoe_id.to_string(),
+        deck_num,
+        seed,
+    })
+}
+
+fn parse_round(lines: &mut Lines, number: usize) -> Result<Round, HistoryError> {
+    let (round_line, words) = lines.expect("round")?;
+    if words[..] != [number.to_string().as_str()] {
+        return Err(syntax(round_line, &format!("expected `round {}`", number)));
+    }
+
+    let (line, words) = lines.expect("deal")?;
+    if words.len() != 4 {
+        return Err(syntax(line, "a deal is four cards"));
+    }
+    let deal: [Card; 4] = words
+        .iter()
+        .map(|token| parse_card(token, line))
+        .collect::<Result<Vec<Card>, HistoryError>>()?
+        .try_into()
+        .ok()
+        .unwrap();
+
+    let natural = lines.peek_keyword() == Some("natural");
+    let (player_third, banker_third) = if natural {
+        lines.expect("natural")?;
+        (None, None)
+    } else {
+        (parse_draw(lines, "player")?, parse_draw(lines, "banker")?)
+    };
+
+    let (line, words) = lines.expect("winner")?;
+    let [winner] = words[..] else {
+        return Err(syntax(line, "expected `winner <side>`"));
+    };
+    let winner = parse_winner(winner, line)?;
+
+    let mut bets = Vec::new();
+    while lines.peek_keyword() == Some("bet") {
+        let (line, words) = lines.expect("bet")?;
+        let [on, amount, "payout", payout] = words[..] else {
+            return Err(syntax(
+                line,
+                "expected `bet <side> <amount> payout <amount>`",
+            ));
+        };
+        let amount = amount.parse().map_err(|_| syntax(line, "bad amount"))?;
+        let payout = payout.parse().map_err(|_| syntax(line, "bad payout"))?;
+        bets.push(Bet {
+            on: parse_winner(on, line)?,
+            amount,
+            payout,
+        });
+    }
+
+    Ok(Round {
+        number,
+        line: round_line,
+        deal,
+        natural,
+        player_third,
+        banker_third,
+        winner,
+        bets,
+    })
+}
+
+impl Round {
+    // In the order they leave the shoe.
+    pub fn cards(&self) -> impl Iterator<Item = &Card> {
+        self.deal
+            .iter()
+            .chain(self.player_third.iter())
+            .chain(self.banker_third.iter())
+    }
+
+    fn rule_error(&self, message: String) -> HistoryError {
+        HistoryError::Rule {
+            line: self.line,
+            message: format!("round {}: {}", self.number, message),
+        }
+    }
+
+    // `result` is the replay of this round's cards.
+    fn check(&self, result: &GameResult) -> Result<(), HistoryError> {
+        let player = result.hands.player.as_ref().unwrap();
+        let banker = result.hands.banker.as_ref().unwrap();
+        let natural = dealt_natural(player, banker);
+        if natural != self.natural {
+            return Err(self.rule_error(if natural {
+                "a natural stands, nobody draws".to_string()
+            } else {
+                "there is no natural on the deal".to_string()
+            }));
+        }
+        for (name, hand, third) in [
+            ("player", player, &self.player_third),
+            ("banker", banker, &self.banker_third),
+        ] {
+            if hand.third.is_some() != third.is_some() {
+                let two_cards = two_cards(hand).value();
+                return Err(self.rule_error(format!(
+                    "{} {} on {}, the rule {}",
+                    name,
+                    if third.is_some() { "draws" } else { "stands" },
+                    two_cards,
+                    if hand.third.is_some() {
+                        "draws"
+                    } else {
+                        "stands"
+                    },
+                )));
+            }
+        }
+        if result.winner != self.winner {
+            return Err(self.rule_error(format!(
+                "{} wins, not {}",
+                winner_name(result.winner),
+                winner_name(self.winner)
+            )));
+        }
+        for bet in self.bets.iter() {
+            let expected = bet.amount * payout(bet.on, result.winner);
+            if (bet.payout - expected).abs() > 1e-9 {
+                return Err(self.rule_error(format!(
+                    "a {} bet on {} pays {}, not {}",
+                    bet.amount,
+                    winner_name(bet.on),
+                    expected,
+                    bet.payout
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl History {
+    pub fn parse(text: &str) -> Result<History, HistoryError> {
+        let mut lines = Lines::new(text);
+        let header = parse_header(&mut lines)?;
+        let mut rounds = Vec::new();
+        while lines.peek_keyword().is_some() {
+            rounds.push(parse_round(&mut lines, rounds.len() + 1)?);
+        }
+        Ok(History { header, rounds })
+    }
+
+    // Stacks the shoe with the recorded cards and plays it back, checking
+    // every round against the rule. A seeded shoe has to match its seed.
+    pub fn replay(&self) -> Result<Game, HistoryError> {
+        let mut game = Game::new(self.header.deck_num);
+        if let Some(seed) = self.header.seed {
+            game.shoe.shuffle_with_seed(seed);
+        }
+        let card_num = game.shoe.cards().len();
+        for round in self.rounds.iter() {
+            for card in round.cards() {
+                let card_idx = game.shoe.get_card_idx();
+                if card_idx >= card_num {
+                    return Err(round.rule_error("the shoe has run out".to_string()));
+                }
+                if let Some(seed) = self.header.seed {
+                    if game.shoe.get_card_ref(card_idx).value_hash() != card.value_hash() {
+                        return Err(round.rule_error(format!(
+                            "{} is not the next card of seed {}",
+                            card, seed
+                        )));
+                    }
+                }
+                game.shoe
+                    .set_card(card)
+                    .map_err(|error| round.rule_error(error.to_string()))?;
+            }
+        }
+        game.shoe.reset_card_idx();
+        for round in self.rounds.iter() {
+            game.play_one_round()
+                .map_err(|error| round.rule_error(error.to_string()))?;
+            round.check(game.results.last().unwrap())?;
+        }
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION: &str = "\
+# table 7, first shoe
+shoe table-7 decks 8 seed -
+round 1
+deal 4s 9h Kd 2c
+player draws 7h
+banker draws 5h
+winner banker
+bet player 100 payout -100
+bet banker 100 payout 95
+
+round 2
+deal 9s 2d Qh 3c
+natural
+winner player
+";
+
+    #[test]
+    fn history_parse_test() {
+        let history = History::parse(SESSION).unwrap();
+        assert_eq!(history.header.shoe_id, "table-7");
+        assert_eq!(history.header.deck_num, 8);
+        assert_eq!(history.header.seed, None);
+        assert_eq!(history.rounds.len(), 2);
+        assert_eq!(history.rounds[0].line, 3);
+        assert_eq!(history.rounds[0].cards().count(), 6);
+        assert_eq!(history.rounds[0].bets.len(), 2);
+        assert!(history.rounds[1].natural);
+
+        let game = history.replay().unwrap();
+        assert_eq!(game.results[0].winner, Winner::BANKER);
+        assert_eq!(game.results[1].winner, Winner::PLAYER);
+
+        // line diagnostics
+        let text = SESSION.replace("7h", "7x");
+        assert_eq!(
+            History::parse(&text).err(),
+            Some(HistoryError::Card {
+                line: 5,
+                error: ParseError::Suit("7x".to_string())
+            })
+        );
+        let text = SESSION.replace("round 2", "round 3");
+        assert!(matches!(
+            History::parse(&text),
+            Err(HistoryError::Syntax { line: 11, .. })
+        ));
+        let text = SESSION.replace("winner player\n", "");
+        assert!(matches!(
+            History::parse(&text),
+            Err(HistoryError::Syntax { line: 14, .. })
+        ));
+    }
+
+    #[test]
+    fn history_rule_test() {
+        let replay = |text: String| History::parse(&text).unwrap().replay().err();
+
+        // 4 + K stands
+        let text = SESSION.replace(
+            "player draws 7h\nbanker draws 5h",
+            "player stands\nbanker draws 5h",
+        );
+        assert_eq!(
+            replay(text).unwrap().to_string(),
+            "line 3: round 1: player stands on 4, the rule draws"
+        );
+        let text = SESSION.replace("winner banker", "winner tie");
+        assert_eq!(
+            replay(text).unwrap().to_string(),
+            "line 3: round 1: banker wins, not tie"
+        );
+        let text = SESSION.replace("payout 95", "payout 100");
+        assert!(matches!(
+            replay(text),
+            Some(HistoryError::Rule { line: 3, .. })
+        ));
+        let text = SESSION.replace("natural", "player stands\nbanker stands");
+        assert_eq!(
+            replay(text).unwrap().to_string(),
+            "line 11: round 2: a natural stands, nobody draws"
+        );
+        // a single deck has one 4 of spades
+        let text = SESSION
+            .replace("decks 8", "decks 1")
+            .replace("9s 2d", "4s 2d");
+        assert_eq!(
+            replay(text).unwrap().to_string(),
+            "line 11: round 2: no 4s left in the shoe"
+        );
+        // cards not from seed 1
+        let text = SESSION.replace("seed -", "seed 1");
+        assert!(matches!(
+            replay(text),
+            Some(HistoryError::Rule { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn history_write_replay_test() {
+        const SEED: u64 = 7;
+        let header = Header {
+            shoe_id: "sim".to_string(),
+            deck_num: 8,
+            seed: Some(SEED),
+        };
+        let mut writer = HistoryWriter::new(Vec::new(), &header).unwrap();
+        let mut game = Game::new(header.deck_num);
+        game.shoe.shuffle_with_seed(SEED);
+        for _ in 0..60 {
+            game.play_one_round().unwrap();
+            let result = game.results.last().unwrap();
+            let bets = [
+                Bet::settle(Winner::BANKER, 10.0, result.winner),
+                Bet::settle(Winner::TIE, 1.0, result.winner),
+            ];
+            writer.write_round(result, &bets).unwrap();
+        }
+        let text = String::from_utf8(writer.into_inner()).unwrap();
+
+        for text in [text.clone(), text.replace("seed 7", "seed -")] {
+            let replayed = History::parse(&text).unwrap().replay().unwrap();
+            assert_eq!(replayed.results.len(), game.results.len());
+            for (result, replayed) in game.results.iter().zip(replayed.results.iter()) {
+                assert_eq!(result.winner, replayed.winner);
+                let banker = result.hands.banker.as_ref().unwrap();
+                let replayed_banker = replayed.hands.banker.as_ref().unwrap();
+                assert_eq!(
+                    banker.second.value_hash(),
+                    replayed_banker.second.value_hash()
+                );
+            }
+        }
+        assert!(HistoryWriter::new(
+            Vec::new(),
+            &Header {
+                shoe_id: "two words".to_string(),
+                deck_num: 8,
+                seed: None,
+            }
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use super::game::Winner;
+use super::rule::{banker_hits, is_natural_value, player_hits};
+
+// Point values 0 to 9.
+pub const VALUE_NUM: usize = 10;
+// Four cards, player, banker, player, banker.
+pub const DEAL_NUM: usize = VALUE_NUM * VALUE_NUM * VALUE_NUM * VALUE_NUM;
+// Where `banker_draws` keeps a player who stood.
+pub const PLAYER_STOOD: usize = VALUE_NUM;
+
+// What the first four cards leave to do, with the two-card totals.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Deal {
+    // a natural, or both stand
+    Settled { player: u8, banker: u8 },
+    PlayerDraws { player: u8, banker: u8 },
+    // the player stood, the banker draws
+    BankerDraws { player: u8, banker: u8 },
+}
+
+// The tableau of `rule` flattened into tables, for simulations that resolve
+// rounds on point values by the million.
+pub struct Lookup {
+    pub deals: [Deal; DEAL_NUM],
+    // by banker total, then the player's third value or PLAYER_STOOD
+    pub banker_draws: [[bool; VALUE_NUM + 1]; VALUE_NUM],
+    // by player total, then banker total
+    pub winners: [[Winner; VALUE_NUM]; VALUE_NUM],
+}
+
+pub fn deal_idx(p1: i32, b1: i32, p2: i32, b2: i32) -> usize {
+    (((p1 as usize * VALUE_NUM + b1 as usize) * VALUE_NUM + p2 as usize) * VALUE_NUM) + b2 as usize
+}
+
+impl Lookup {
+    fn new() -> Lookup {
+        let winners: [[Winner; VALUE_NUM]; VALUE_NUM] = core::array::from_fn(|player| {
+            core::array::from_fn(|banker| match player.cmp(&banker) {
+                std::cmp::Ordering::Greater => Winner::PLAYER,
+                std::cmp::Ordering::Less => Winner::BANKER,
+                std::cmp::Ordering::Equal => Winner::TIE,
+            })
+        });
+        let banker_draws = core::array::from_fn(|banker| {
+            core::array::from_fn(|player_third| {
+                let player_third = (player_third != PLAYER_STOOD).then_some(player_third as i32);
+                banker_hits(player_third, banker as i32)
+            })
+        });
+        let deals = core::array::from_fn(|idx| {
+            let value = |place: u32| (idx / VALUE_NUM.pow(place) % VALUE_NUM) as i32;
+            let player = (value(3) + value(1)) % 10;
+            let banker = (value(2) + value(0)) % 10;
+            let (player, banker) = (player as u8, banker as u8);
+            if is_natural_value(player as i32) || is_natural_value(banker as i32) {
+                Deal::Settled { player, banker }
+            } else if player_hits(player as i32) {
+                Deal::PlayerDraws { player, banker }
+            } else if banker_hits(None, banker as i32) {
+                Deal::BankerDraws { player, banker }
+            } else {
+                Deal::Settled { player, banker }
+            }
+        });
+        Lookup {
+            deals,
+            banker_draws,
+            winners,
+        }
+    }
+
+    // The final player and banker totals of a round on point values 0 to 9,
+    // taking only the values the round uses. None when they run out first.
+    #[inline]
+    pub fn totals<I: Iterator<Item = i32>>(&self, values: &mut I) -> Option<(u8, u8)> {
+        let [p1, b1, p2, b2] = [
+            values.next()?,
+            values.next()?,
+            values.next()?,
+            values.next()?,
+        ];
+        match self.deals[deal_idx(p1, b1, p2, b2)] {
+            Deal::Settled { player, banker } => Some((player, banker)),
+            Deal::PlayerDraws { player, banker } => {
+                let third = values.next()?;
+                let player = (player as i32 + third) % 10;
+                let mut banker = banker as i32;
+                if self.banker_draws[banker as usize][third as usize] {
+                    banker = (banker + values.next()?) % 10;
+                }
+                Some((player as u8, banker as u8))
+            }
+            Deal::BankerDraws { player, banker } => {
+                let banker = (banker as i32 + values.next()?) % 10;
+                Some((player, banker as u8))
+            }
+        }
+    }
+
+    // Same as `rule::resolve`, by table.
+    #[inline]
+    pub fn resolve<I: Iterator<Item = i32>>(&self, values: &mut I) -> Option<Winner> {
+        let (player, banker) = self.totals(values)?;
+        Some(self.winners[player as usize][banker as usize])
+    }
+}
+
+pub fn lookup() -> &'static Lookup {
+    lazy_static! {
+        static ref LOOKUP: Lookup = Lookup::new();
+    }
+    &LOOKUP
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::Card;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+    use crate::games::baccarat::hand::Hand;
+    use crate::games::baccarat::rule::{
+        contain_natural, resolve, should_banker_hit, should_player_hit,
+    };
+
+    // A card counting `value`, tens for zero.
+    fn card(value: i32) -> Card {
+        let numbers = [
+            Number::Ten,
+            Number::Ace,
+            Number::Two,
+            Number::Three,
+            Number::Four,
+            Number::Five,
+            Number::Six,
+            Number::Seven,
+            Number::Eight,
+            Number::Nine,
+        ];
+        Card::new(Suit::Spade, numbers[value as usize].clone())
+    }
+
+    #[test]
+    fn lookup_rule_test() {
+        let lookup = lookup();
+        for idx in 0..DEAL_NUM {
+            let value = |place: u32| (idx / VALUE_NUM.pow(place) % VALUE_NUM) as i32;
+            let [p1, b1, p2, b2] = [value(3), value(2), value(1), value(0)];
+            assert_eq!(deal_idx(p1, b1, p2, b2), idx);
+            let player = Hand::new(card(p1), card(p2));
+            let banker = Hand::new(card(b1), card(b2));
+            let deal = lookup.deals[idx];
+            if contain_natural(&player, &banker) {
+                assert!(matches!(deal, Deal::Settled { .. }));
+                continue;
+            }
+            assert_eq!(
+                matches!(deal, Deal::PlayerDraws { .. }),
+                should_player_hit(&player)
+            );
+            let banker_value = (b1 + b2) as usize % 10;
+            if should_player_hit(&player) {
+                for third in 0..VALUE_NUM as i32 {
+                    assert_eq!(
+                        lookup.banker_draws[banker_value][third as usize],
+                        should_banker_hit(&Some(card(third)), &banker)
+                    );
+                }
+            } else {
+                assert_eq!(
+                    matches!(deal, Deal::BankerDraws { .. }),
+                    should_banker_hit(&None, &banker)
+                );
+                assert_eq!(
+                    lookup.banker_draws[banker_value][PLAYER_STOOD],
+                    should_banker_hit(&None, &banker)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn lookup_resolve_test() {
+        // every six values, whatever the round leaves unused
+        let lookup = lookup();
+        for idx in 0..DEAL_NUM * VALUE_NUM * VALUE_NUM {
+            let values: [i32; 6] = core::array::from_fn(|place| {
+                (idx / VALUE_NUM.pow(place as u32) % VALUE_NUM) as i32
+            });
+            let mut by_rule = values.iter().copied();
+            let mut by_table = values.iter().copied();
+            assert_eq!(lookup.resolve(&mut by_table), resolve(&mut by_rule));
+            assert_eq!(by_table.len(), by_rule.len());
+        }
+        // short of cards
+        assert_eq!(lookup.resolve(&mut [1, 2, 3].into_iter()), None);
+        assert_eq!(lookup.resolve(&mut [1, 2, 3, 4].into_iter()), None);
+    }
+}
@@ -1,6 +1,11 @@
-pub mod card;
+pub mod builder;
+pub mod continuation;
+pub mod decode;
+pub mod eor;
 pub mod game;
 pub mod hand;
-pub mod number;
+pub mod history;
+pub mod lookup;
+pub mod observer;
 pub mod rule;
 pub mod shoe;
@@ -0,0 +1,147 @@
+use super::game::Result as GameResult;
+use crate::cards::card::Card;
+use crate::error::Error;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Side {
+    PLAYER,
+    BANKER,
+}
+
+// Watches a round being played, see `Game::add_observer`. Every callback
+// does nothing unless overridden.
+pub trait Observer {
+    // `position` is where the card sat in the shoe.
+    fn card_dealt(&mut self, _position: usize, _side: Side, _card: &Card) {}
+
+    // Either two-card total is 8 or 9, so nobody draws.
+    fn natural_declared(&mut self, _player_value: i32, _banker_value: i32) {}
+
+    // Whether a side draws a third card, asked player first.
+    fn third_card_decided(&mut self, _side: Side, _hits: bool) {}
+
+    fn round_resolved(&mut self, _result: &GameResult) {}
+
+    // The round stops here, its cards stay in the game's hands.
+    fn shoe_exhausted(&mut self, _error: &Error) {}
+}
+
+// The observers of a game, told in the order they were added. With none the
+// game only checks an empty list.
+#[derive(Default)]
+pub struct Observers(Vec<Box<dyn Observer + Send>>);
+
+// A cloned game starts with no observers, like a branch.
+impl Clone for Observers {
+    fn clone(&self) -> Self {
+        Observers::default()
+    }
+}
+
+impl Observers {
+    pub fn push(&mut self, observer: Box<dyn Observer + Send>) {
+        self.0.push(observer);
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    #[inline]
+    pub fn notify(&mut self, mut event: impl FnMut(&mut dyn Observer)) {
+        for observer in self.0.iter_mut() {
+            event(observer.as_mut());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::traits::shoe::Shoe as ShoeTrait;
+    use crate::games::baccarat::game::{Game, Winner};
+    use std::sync::{Arc, Mutex};
+
+    struct Recorder(Arc<Mutex<Vec<String>>>);
+
+    impl Observer for Recorder {
+        fn card_dealt(&mut self, position: usize, side: Side, card: &Card) {
+            let event = format!("{} {:?} {}", position, side, card);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn natural_declared(&mut self, player_value: i32, banker_value: i32) {
+            let event = format!("natural {} {}", player_value, banker_value);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn third_card_decided(&mut self, side: Side, hits: bool) {
+            let event = format!("{:?} hits {}", side, hits);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn round_resolved(&mut self, result: &GameResult) {
+            let event = format!("{:?} wins", result.winner);
+            self.0.lock().unwrap().push(event);
+        }
+
+        fn shoe_exhausted(&mut self, error: &Error) {
+            self.0.lock().unwrap().push(error.to_string());
+        }
+    }
+
+    #[test]
+    fn observer_test() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let mut game = Game::new(8);
+        game.add_observer(Recorder(events.clone()));
+        game.add_observer(Recorder(events.clone()));
+        assert_eq!(game.observers.len(), 2);
+
+        // A, 3, 5 vs 2, 4, from the top of an unshuffled shoe
+        game.play_one_round().unwrap();
+        assert_eq!(game.results[0].winner, Winner::PLAYER);
+        let expected = [
+            "0 PLAYER As",
+            "1 BANKER 2s",
+            "2 PLAYER 3s",
+            "3 BANKER 4s",
+            "PLAYER hits true",
+            "4 PLAYER 5s",
+            "BANKER hits false",
+            "PLAYER wins",
+        ];
+        let recorded = events.lock().unwrap().clone();
+        assert_eq!(recorded.len(), expected.len() * 2);
+        // each event goes to both, in the order they were added
+        for (idx, event) in expected.iter().enumerate() {
+            assert_eq!(recorded[idx * 2], *event);
+            assert_eq!(recorded[idx * 2 + 1], *event);
+        }
+
+        events.lock().unwrap().clear();
+        game.observers.clear();
+        game.add_observer(Recorder(events.clone()));
+        let left = game.shoe.remaining();
+        game.shoe.burn(left - 3).unwrap();
+        assert!(game.play_one_round().is_err());
+        assert_eq!(*events.lock().unwrap(), ["wanted 4 cards, 3 left"]);
+
+        // a branch reports to nobody
+        let mut branch = game.branch();
+        assert!(branch.observers.is_empty());
+        assert!(game.clone().observers.is_empty());
+        branch.shoe.reset_card_idx();
+        branch.play_one_round().unwrap();
+        assert_eq!(events.lock().unwrap().len(), 1);
+    }
+}
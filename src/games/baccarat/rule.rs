@@ -1,33 +1,62 @@
-use super::card::Card;
+use super::game::Winner;
 use super::hand::Hand;
+use crate::cards::card::Card;
+use crate::cards::id::CardId;
+use crate::cards::traits::order::Order;
 use crate::cards::traits::value::Value;
+use crate::cards::valuation::{RankValuation, ValueTable};
 
-pub fn is_natural(hand: &Hand) -> bool {
-    let value = hand.value();
+pub const BANKER_COMMISSION: f64 = 0.05;
+pub const TIE_PAYS: f64 = 8.0;
+pub const PAIR_PAYS: f64 = 11.0;
+
+// Tens and faces count nothing, the ace one.
+pub static VALUATION: ValueTable = ValueTable([1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 0]);
+
+pub fn card_value(card: &Card) -> i32 {
+    VALUATION.card_value(card)
+}
+
+pub fn id_value(id: CardId) -> i32 {
+    VALUATION.0[id.number_idx()]
+}
+
+pub fn is_natural_value(value: i32) -> bool {
     value == 8 || value == 9
 }
 
+pub fn is_natural(hand: &Hand) -> bool {
+    is_natural_value(hand.value())
+}
+
+// The first two cards share a number, for the pair side bets.
+pub fn is_pair(hand: &Hand) -> bool {
+    hand.first.number.order() == hand.second.number.order()
+}
+
 pub fn contain_natural(hand1: &Hand, hand2: &Hand) -> bool {
     is_natural(hand1) || is_natural(hand2)
 }
 
+pub fn player_hits(value: i32) -> bool {
+    value <= 5
+}
+
 pub fn should_player_hit(hand: &Hand) -> bool {
-    hand.value() <= 5
+    player_hits(hand.value())
 }
 
-pub fn should_banker_hit(player_third: &Option<Card>, hand: &Hand) -> bool {
-    let value = hand.value();
+pub fn banker_hits(player_third: Option<i32>, value: i32) -> bool {
     match player_third {
-        Some(ref card) => {
-            let player_third = card.value();
+        Some(player_third) => {
             if value <= 2 {
                 true
             } else if value == 3 {
                 player_third != 8
             } else if value == 4 {
-                player_third >= 2 && player_third <= 7
+                (2..=7).contains(&player_third)
             } else if value == 5 {
-                player_third >= 4 && player_third <= 7
+                (4..=7).contains(&player_third)
             } else if value == 6 {
                 player_third == 6 || player_third == 7
             } else {
@@ -38,18 +67,72 @@ pub fn should_banker_hit(player_third: &Option<Card>, hand: &Hand) -> bool {
     }
 }
 
+pub fn should_banker_hit(player_third: &Option<Card>, hand: &Hand) -> bool {
+    banker_hits(player_third.as_ref().map(card_value), hand.value())
+}
+
+// Plays a round on card values in shoe order without building hands, taking
+// only the cards the round uses. None when the values run out first.
+pub fn resolve<I: Iterator<Item = i32>>(values: &mut I) -> Option<Winner> {
+    let [p1, b1, p2, b2] = [
+        values.next()?,
+        values.next()?,
+        values.next()?,
+        values.next()?,
+    ];
+    let mut player = (p1 + p2) % 10;
+    let mut banker = (b1 + b2) % 10;
+    if !is_natural_value(player) && !is_natural_value(banker) {
+        let mut player_third = None;
+        if player_hits(player) {
+            let third = values.next()?;
+            player = (player + third) % 10;
+            player_third = Some(third);
+        }
+        if banker_hits(player_third, banker) {
+            banker = (banker + values.next()?) % 10;
+        }
+    }
+    Some(match player.cmp(&banker) {
+        std::cmp::Ordering::Greater => Winner::PLAYER,
+        std::cmp::Ordering::Less => Winner::BANKER,
+        std::cmp::Ordering::Equal => Winner::TIE,
+    })
+}
+
+// Net win per unit bet on `bet`. Player and banker bets push on a tie.
+pub fn payout(bet: Winner, winner: Winner) -> f64 {
+    match (bet, winner) {
+        (Winner::TIE, Winner::TIE) => TIE_PAYS,
+        (Winner::TIE, _) => -1.0,
+        (_, Winner::TIE) => 0.0,
+        (Winner::PLAYER, Winner::PLAYER) => 1.0,
+        (Winner::BANKER, Winner::BANKER) => 1.0 - BANKER_COMMISSION,
+        _ => -1.0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cards::card::all_cards;
+    use crate::cards::notation::parse_cards;
+    use crate::cards::number::Number;
     use crate::cards::suit::Suit;
-    use crate::games::baccarat::number::Number;
 
     #[test]
     fn rule_card_value_test() {
-        assert_eq!(Card::new(Suit::Spade, Number::Ace).value(), 1);
-        assert_eq!(Card::new(Suit::Heart, Number::Four).value(), 4);
-        assert_eq!(Card::new(Suit::Diamond, Number::Ten).value(), 0);
-        assert_eq!(Card::new(Suit::Club, Number::King).value(), 0);
+        assert_eq!(card_value(&Card::new(Suit::Spade, Number::Ace)), 1);
+        assert_eq!(card_value(&Card::new(Suit::Heart, Number::Four)), 4);
+        assert_eq!(card_value(&Card::new(Suit::Diamond, Number::Ten)), 0);
+        assert_eq!(card_value(&Card::new(Suit::Club, Number::King)), 0);
+        // a baccarat hand: A, 3 vs T, 4
+        let cards = parse_cards::<Card>("As 3h Td 4c").unwrap();
+        let values: Vec<i32> = cards.iter().map(card_value).collect();
+        assert_eq!(values, vec![1, 3, 0, 4]);
+        for card in all_cards() {
+            assert_eq!(id_value(CardId::try_from(card).unwrap()), card_value(card));
+        }
     }
 
     #[test]
@@ -60,7 +143,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Four),
             third: None,
         };
-        assert_eq!(is_natural(&hand), true);
+        assert!(is_natural(&hand));
 
         // 4. 5
         let hand = Hand {
@@ -68,7 +151,7 @@ mod tests {
             second: Card::new(Suit::Spade, Number::Five),
             third: None,
         };
-        assert_eq!(is_natural(&hand), true);
+        assert!(is_natural(&hand));
 
         // 4. 3
         let hand = Hand {
@@ -76,7 +159,26 @@ mod tests {
             second: Card::new(Suit::Spade, Number::Three),
             third: None,
         };
-        assert_eq!(is_natural(&hand), false);
+        assert!(!is_natural(&hand));
+    }
+
+    #[test]
+    fn rule_pair_test() {
+        let mut hand = Hand::new(
+            Card::new(Suit::Spade, Number::King),
+            Card::new(Suit::Heart, Number::King),
+        );
+        assert!(is_pair(&hand));
+        // a third card doesn't count
+        hand.third = Some(Card::new(Suit::Club, Number::Two));
+        assert!(is_pair(&hand));
+
+        // same value, different numbers
+        let hand = Hand::new(
+            Card::new(Suit::Spade, Number::King),
+            Card::new(Suit::Spade, Number::Ten),
+        );
+        assert!(!is_pair(&hand));
     }
 
     #[test]
@@ -87,7 +189,7 @@ mod tests {
             second: Card::new(Suit::Spade, Number::Three),
             third: None,
         };
-        assert_eq!(should_player_hit(&hand), true);
+        assert!(should_player_hit(&hand));
 
         // 3, 3
         let hand = Hand {
@@ -95,7 +197,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Three),
             third: None,
         };
-        assert_eq!(should_player_hit(&hand), false);
+        assert!(!should_player_hit(&hand));
 
         // _, _, 7 and 1, 2
         let player_third = Some(Card::new(Suit::Spade, Number::Seven));
@@ -104,7 +206,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Two),
             third: None,
         };
-        assert_eq!(should_banker_hit(&player_third, &hand), true);
+        assert!(should_banker_hit(&player_third, &hand));
 
         // _, _, 8 and 1, 2
         let player_third = Some(Card::new(Suit::Spade, Number::Eight));
@@ -113,7 +215,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Two),
             third: None,
         };
-        assert_eq!(should_banker_hit(&player_third, &hand), false);
+        assert!(!should_banker_hit(&player_third, &hand));
 
         // _, _, 3 and 2, 3
         let player_third = Some(Card::new(Suit::Spade, Number::Three));
@@ -122,7 +224,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Three),
             third: None,
         };
-        assert_eq!(should_banker_hit(&player_third, &hand), false);
+        assert!(!should_banker_hit(&player_third, &hand));
 
         // _, _, 4 and 2, 3
         let player_third = Some(Card::new(Suit::Spade, Number::Four));
@@ -131,7 +233,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Three),
             third: None,
         };
-        assert_eq!(should_banker_hit(&player_third, &hand), true);
+        assert!(should_banker_hit(&player_third, &hand));
 
         // _, _ and 3, 3
         let hand = Hand {
@@ -139,7 +241,7 @@ mod tests {
             second: Card::new(Suit::Heart, Number::Three),
             third: None,
         };
-        assert_eq!(should_banker_hit(&None, &hand), false);
+        assert!(!should_banker_hit(&None, &hand));
 
         // _, _ and 2, 3
         let hand = Hand {
@@ -147,6 +249,34 @@ mod tests {
             second: Card::new(Suit::Spade, Number::Three),
             third: None,
         };
-        assert_eq!(should_banker_hit(&None, &hand), true);
+        assert!(should_banker_hit(&None, &hand));
+    }
+
+    #[test]
+    fn rule_resolve_test() {
+        // natural, the rest stays
+        let mut values = [4, 9, 4, 0, 7].into_iter();
+        assert_eq!(resolve(&mut values), Some(Winner::BANKER));
+        assert_eq!(values.len(), 1);
+        // player draws a 9 on 5, banker 3 draws
+        let mut values = [2, 3, 3, 0, 9, 2].into_iter();
+        assert_eq!(resolve(&mut values), Some(Winner::BANKER));
+        assert_eq!(values.len(), 0);
+        // banker 3 stands on a player third 8
+        let mut values = [0, 3, 0, 0, 8, 1].into_iter();
+        assert_eq!(resolve(&mut values), Some(Winner::PLAYER));
+        assert_eq!(values.len(), 1);
+        // the player third is missing
+        assert_eq!(resolve(&mut [1, 1, 1, 1].into_iter()), None);
+    }
+
+    #[test]
+    fn rule_payout_test() {
+        assert_eq!(payout(Winner::PLAYER, Winner::PLAYER), 1.0);
+        assert_eq!(payout(Winner::BANKER, Winner::BANKER), 0.95);
+        assert_eq!(payout(Winner::BANKER, Winner::PLAYER), -1.0);
+        assert_eq!(payout(Winner::PLAYER, Winner::TIE), 0.0);
+        assert_eq!(payout(Winner::TIE, Winner::TIE), 8.0);
+        assert_eq!(payout(Winner::TIE, Winner::BANKER), -1.0);
     }
 }
@@ -0,0 +1,235 @@
+use super::rule::{value, BasicStrategy, Strategy, Variant};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+use crate::cards::traits::discard::Discard;
+
+pub const MAX_PLAYER_NUM: usize = 8;
+
+#[derive(Clone, Default)]
+pub struct Seat {
+    pub cards: Vec<Card>,
+    pub folded: bool,
+}
+
+pub struct Result {
+    pub winners: Vec<usize>,
+    pub shares: Vec<f64>, // share of the pot per seat
+    pub reshuffled: bool, // the discards went back in during the hand
+}
+
+// Seat 0 sits on the dealer's left and draws first.
+pub struct Game<S: Strategy = BasicStrategy> {
+    pub variant: Variant,
+    pub deck: Deck,
+    pub seats: Vec<Seat>,
+    pub draw: usize, // draws done so far
+    pub strategy: S,
+    pub results: Vec<Result>,
+    reshuffled: bool,
+}
+
+impl Game {
+    pub fn new(variant: Variant, player_num: usize) -> Game {
+        Game::with_strategy(variant, player_num, BasicStrategy)
+    }
+}
+
+impl<S: Strategy> Game<S> {
+    pub fn with_strategy(variant: Variant, player_num: usize, strategy: S) -> Game<S> {
+        assert!((2..=MAX_PLAYER_NUM).contains(&player_num));
+        Game {
+            variant,
+            deck: Deck::new(),
+            seats: vec![Seat::default(); player_num],
+            draw: 0,
+            strategy,
+            results: Vec::new(),
+            reshuffled: false,
+        }
+    }
+
+    fn draw_card(&mut self) -> Card {
+        if self.deck.remaining() == 0 {
+            self.deck.reshuffle_discards();
+            self.reshuffled = true;
+        }
+        self.deck
+            .draw()
+            .expect("no cards left even with the discards")
+    }
+
+    pub fn active_seats(&self) -> Vec<usize> {
+        (0..self.seats.len())
+            .filter(|idx| !self.seats[*idx].folded)
+            .collect()
+    }
+
+    pub fn fold(&mut self, seat: usize) {
+        let cards = std::mem::take(&mut self.seats[seat].cards);
+        for card in cards {
+            self.deck
+                .discard(card)
+                .expect("a seat only holds cards dealt from the deck");
+        }
+        self.seats[seat].folded = true;
+    }
+
+    // Deals a fresh hand round the table, keeping the deck order.
+    pub fn start(&mut self) {
+        self.deck.reset();
+        for seat in self.seats.iter_mut() {
+            *seat = Seat::default();
+        }
+        self.draw = 0;
+        self.reshuffled = false;
+        for _ in 0..self.variant.hand_num() {
+            for seat in 0..self.seats.len() {
+                let card = self.draw_card();
+                self.seats[seat].cards.push(card);
+            }
+        }
+    }
+
+    // Swaps the cards at `idxes` for new ones. The player's own discards only
+    // hit the pile after the replacements, so they never come straight back.
+    pub fn replace(&mut self, seat: usize, idxes: &[usize]) {
+        let mut discards = Vec::with_capacity(idxes.len());
+        for idx in idxes.iter() {
+            let card = self.draw_card();
+            discards.push(std::mem::replace(&mut self.seats[seat].cards[*idx], card));
+        }
+        for card in discards {
+            self.deck
+                .discard(card)
+                .expect("a seat only holds cards dealt from the deck");
+        }
+    }
+
+    // One draw for every seat still in, discards chosen by the strategy.
+    // Returns false once the game has no draws left.
+    pub fn draw_round(&mut self) -> bool {
+        if self.draw >= self.variant.draw_num() {
+            return false;
+        }
+        for seat in self.active_seats() {
+            let idxes = self.strategy.discard(self.variant, &self.seats[seat].cards);
+            self.replace(seat, &idxes);
+        }
+        self.draw += 1;
+        true
+    }
+
+    pub fn showdown(&mut self) {
+        let active = self.active_seats();
+        let values: Vec<u32> = active
+            .iter()
+            .map(|seat| value(self.variant, &self.seats[*seat].cards))
+            .collect();
+        let best = *values.iter().max().unwrap();
+        let winners: Vec<usize> = active
+            .iter()
+            .zip(values.iter())
+            .filter(|(_, value)| **value == best)
+            .map(|(seat, _)| *seat)
+            .collect();
+        let mut shares = vec![0.0; self.seats.len()];
+        for seat in winners.iter() {
+            shares[*seat] = 1.0 / winners.len() as f64;
+        }
+        self.results.push(Result {
+            winners,
+            shares,
+            reshuffled: self.reshuffled,
+        });
+    }
+
+    // Plays the current deck order as is, nobody folding.
+    pub fn play_dealt_round(&mut self) {
+        self.start();
+        while self.draw_round() {}
+        self.showdown();
+    }
+
+    pub fn play_one_round(&mut self) {
+        self.deck.shuffle();
+        self.play_dealt_round();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::ALL_CARDS_NUM;
+    use crate::cards::traits::order::Order;
+
+    // Throws everything every time.
+    struct DrawAll;
+
+    impl Strategy for DrawAll {
+        fn discard(&self, variant: Variant, _cards: &[Card]) -> Vec<usize> {
+            (0..variant.hand_num()).collect()
+        }
+    }
+
+    #[test]
+    fn game_test() {
+        for variant in [
+            Variant::FiveCardDraw,
+            Variant::DeuceToSevenTripleDraw,
+            Variant::AceToFiveTripleDraw,
+            Variant::Badugi,
+        ] {
+            let mut game = Game::new(variant, 6);
+            for _ in 0..20 {
+                game.play_one_round();
+            }
+            for result in game.results.iter() {
+                let shares: f64 = result.shares.iter().sum();
+                assert!((shares - 1.0).abs() < 1e-9);
+            }
+            for seat in game.seats.iter() {
+                assert_eq!(seat.cards.len(), variant.hand_num());
+            }
+        }
+    }
+
+    #[test]
+    fn game_reshuffle_test() {
+        // 4 x 5 dealt, 32 left: the second draw needs the discards
+        let mut game = Game::with_strategy(Variant::DeuceToSevenTripleDraw, 4, DrawAll);
+        game.start();
+        assert!(game.draw_round());
+        assert!(!game.reshuffled);
+        assert!(game.draw_round());
+        assert!(game.reshuffled);
+        assert!(game.draw_round());
+        assert!(!game.draw_round());
+
+        // every card is still somewhere, once
+        let mut orders: Vec<u64> = game
+            .seats
+            .iter()
+            .flat_map(|seat| seat.cards.iter())
+            .chain(game.deck.discards().iter())
+            .map(|card| card.order())
+            .collect();
+        orders.extend(
+            game.deck.cards[game.deck.cards.len() - game.deck.remaining()..]
+                .iter()
+                .map(|card| card.order()),
+        );
+        assert_eq!(orders.len(), ALL_CARDS_NUM);
+        orders.sort();
+        orders.dedup();
+        assert_eq!(orders.len(), ALL_CARDS_NUM);
+        game.showdown();
+        assert!(game.results[0].reshuffled);
+
+        // a folded hand goes to the discards
+        game.start();
+        game.fold(0);
+        assert_eq!(game.deck.discards().len(), 5);
+        assert_eq!(game.active_seats(), vec![1, 2, 3]);
+    }
+}
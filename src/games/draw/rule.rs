@@ -0,0 +1,198 @@
+use crate::cards::card::Card;
+use crate::cards::suit::ALL_SUIT_NUM;
+use crate::cards::traits::order::Order;
+use crate::games::poker::badugi::{self, best_subset};
+use crate::games::poker::high::{self, rank, Category};
+use crate::games::poker::low::{ace_to_five_rank, evaluate_ace_to_five, evaluate_deuce_to_seven};
+
+const EIGHT_RANK: u8 = 6; // poker rank
+const ACE_TO_FIVE_EIGHT_RANK: u8 = 7;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    FiveCardDraw,
+    DeuceToSevenTripleDraw,
+    AceToFiveTripleDraw,
+    Badugi,
+}
+
+impl Variant {
+    pub fn hand_num(&self) -> usize {
+        match *self {
+            Variant::Badugi => badugi::HAND_NUM,
+            _ => 5,
+        }
+    }
+
+    pub fn draw_num(&self) -> usize {
+        match *self {
+            Variant::FiveCardDraw => 1,
+            _ => 3,
+        }
+    }
+}
+
+// Showdown value of a hand, greater is better whatever the game.
+pub fn value(variant: Variant, cards: &[Card]) -> u32 {
+    match variant {
+        Variant::FiveCardDraw => high::evaluate(cards).0,
+        Variant::DeuceToSevenTripleDraw => evaluate_deuce_to_seven(cards).0,
+        Variant::AceToFiveTripleDraw => evaluate_ace_to_five(cards).0,
+        Variant::Badugi => badugi::evaluate(cards).0,
+    }
+}
+
+pub trait Strategy {
+    // Positions in `cards` to throw away.
+    fn discard(&self, variant: Variant, cards: &[Card]) -> Vec<usize>;
+}
+
+// Keeps sets in high draw, smooth unpaired cards in lowball and the best
+// badugi, throwing the rest.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BasicStrategy;
+
+impl BasicStrategy {
+    fn discard_high(cards: &[Card]) -> Vec<usize> {
+        let made = high::evaluate(cards);
+        if made.category() >= Category::Straight {
+            return Vec::new();
+        }
+        let count = |card: &Card| {
+            cards
+                .iter()
+                .filter(|other| other.number.order() == card.number.order())
+                .count()
+        };
+        if made.category() >= Category::OnePair {
+            return (0..cards.len())
+                .filter(|idx| count(&cards[*idx]) == 1)
+                .collect();
+        }
+        // four to a flush, else hold the top card
+        for suit in 0..ALL_SUIT_NUM as u64 {
+            let off: Vec<usize> = (0..cards.len())
+                .filter(|idx| cards[*idx].suit.order() != suit)
+                .collect();
+            if off.len() == 1 {
+                return off;
+            }
+        }
+        let top = (0..cards.len())
+            .max_by_key(|idx| rank(&cards[*idx].number))
+            .unwrap();
+        (0..cards.len()).filter(|idx| *idx != top).collect()
+    }
+
+    // Keeps the first card of each rank up to `max_rank`.
+    fn discard_low(cards: &[Card], low_rank: impl Fn(&Card) -> u8, max_rank: u8) -> Vec<usize> {
+        let mut seen = 0u16;
+        let mut discards = Vec::new();
+        for (idx, card) in cards.iter().enumerate() {
+            let rank = low_rank(card);
+            if rank > max_rank || seen & (1 << rank) != 0 {
+                discards.push(idx);
+            } else {
+                seen |= 1 << rank;
+            }
+        }
+        discards
+    }
+}
+
+impl Strategy for BasicStrategy {
+    fn discard(&self, variant: Variant, cards: &[Card]) -> Vec<usize> {
+        match variant {
+            Variant::FiveCardDraw => Self::discard_high(cards),
+            Variant::DeuceToSevenTripleDraw => {
+                // aces play high
+                let mut discards = Self::discard_low(cards, |card| rank(&card.number), EIGHT_RANK);
+                // break a pat straight or flush at the top
+                if discards.is_empty() && !evaluate_deuce_to_seven(cards).is_unpaired() {
+                    let top = (0..cards.len())
+                        .max_by_key(|idx| rank(&cards[*idx].number))
+                        .unwrap();
+                    discards.push(top);
+                }
+                discards
+            }
+            Variant::AceToFiveTripleDraw => {
+                Self::discard_low(cards, ace_to_five_rank, ACE_TO_FIVE_EIGHT_RANK)
+            }
+            Variant::Badugi => {
+                let (_, mask) = best_subset(cards);
+                (0..cards.len())
+                    .filter(|idx| mask & (1 << idx) == 0)
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: &[(Suit, Number)]) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|(suit, number)| Card::new(suit.clone(), number.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn basic_strategy_test() {
+        let strategy = BasicStrategy;
+
+        // J, J, 4, 7, 9: keep the jacks
+        let cards = hand(&[
+            (Suit::Spade, Number::Jack),
+            (Suit::Heart, Number::Jack),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(
+            strategy.discard(Variant::FiveCardDraw, &cards),
+            vec![2, 3, 4]
+        );
+        // A and 7 are too high for deuce to seven, the A is fine in ace to five
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Seven),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Two),
+            (Suit::Diamond, Number::King),
+        ]);
+        assert_eq!(
+            strategy.discard(Variant::DeuceToSevenTripleDraw, &cards),
+            vec![0, 2, 4]
+        );
+        assert_eq!(
+            strategy.discard(Variant::AceToFiveTripleDraw, &cards),
+            vec![2, 4]
+        );
+        // 2-3-4-5-6 is a straight in deuce to seven
+        let cards = hand(&[
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Three),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Five),
+            (Suit::Diamond, Number::Six),
+        ]);
+        assert_eq!(
+            strategy.discard(Variant::DeuceToSevenTripleDraw, &cards),
+            vec![4]
+        );
+        // two hearts: throw the higher
+        let cards = hand(&[
+            (Suit::Heart, Number::Ace),
+            (Suit::Heart, Number::Nine),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Five),
+        ]);
+        assert_eq!(strategy.discard(Variant::Badugi, &cards), vec![1]);
+    }
+}
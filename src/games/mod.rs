@@ -1 +1,8 @@
 pub mod baccarat;
+pub mod draw;
+pub mod pai_gow;
+pub mod poker;
+pub mod stud;
+pub mod three_card_poker;
+pub mod ultimate_holdem;
+pub mod video_poker;
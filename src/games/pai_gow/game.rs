@@ -0,0 +1,117 @@
+use super::hand::{Setting, HAND_NUM};
+use super::rule::{settle, FortunePayTable, HouseWay, Outcome, Setter};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+
+pub struct Bets {
+    pub wager: f64,
+    pub fortune: f64,
+}
+
+pub struct Result {
+    pub player: Setting,
+    pub banker: Setting,
+    pub outcome: Outcome,
+    pub wager: f64,   // net of the wager, after commission
+    pub fortune: f64, // net of fortune
+}
+
+// The house banks and sets its hand the house way.
+pub struct Game<S: Setter = HouseWay> {
+    pub deck: Deck, // 53 cards
+    pub house_way: HouseWay,
+    pub fortune: FortunePayTable,
+    pub player: S,
+    pub results: Vec<Result>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Game {
+        Game::with_player(HouseWay::standard())
+    }
+}
+
+impl<S: Setter> Game<S> {
+    pub fn with_player(player: S) -> Game<S> {
+        Game {
+            deck: Deck::with_jokers(1),
+            house_way: HouseWay::standard(),
+            fortune: FortunePayTable::standard(),
+            player,
+            results: Vec::new(),
+        }
+    }
+
+    // Plays the current deck order as is, player first.
+    pub fn play_dealt_round(&mut self, bets: &Bets) {
+        let cards = &self.deck.cards;
+        let player: [Card; HAND_NUM] = core::array::from_fn(|idx| cards[idx].clone());
+        let banker: [Card; HAND_NUM] = core::array::from_fn(|idx| cards[HAND_NUM + idx].clone());
+        let fortune = bets.fortune * self.fortune.payout(&player);
+        let player = self.player.set(&player);
+        let banker = self.house_way.set(&banker);
+        let outcome = settle(&player, &banker);
+        self.results.push(Result {
+            player,
+            banker,
+            outcome,
+            wager: bets.wager * outcome.payout(),
+            fortune,
+        });
+    }
+
+    pub fn play_one_round(&mut self, bets: &Bets) {
+        self.deck.shuffle();
+        self.play_dealt_round(bets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::games::pai_gow::solver::Solver;
+    use crate::games::poker::high::Category;
+
+    #[test]
+    fn game_test() {
+        let mut game = Game::new();
+        let bets = Bets {
+            wager: 1.0,
+            fortune: 1.0,
+        };
+
+        // A to 7 of spades vs 8 to K of spades and the ace of hearts
+        game.play_dealt_round(&bets);
+        let result = game.results.last().unwrap();
+        assert_eq!(result.player.high_rank.category(), Category::StraightFlush);
+        assert_eq!(result.banker.high_rank.category(), Category::StraightFlush);
+        assert_eq!(result.outcome, Outcome::Lose);
+        assert_eq!(result.wager, -1.0);
+        assert_eq!(result.fortune, 8000.0);
+
+        for _ in 0..100 {
+            game.play_one_round(&bets);
+        }
+        assert_eq!(game.results.len(), 101);
+        for result in game.results.iter() {
+            assert!(result.player.is_valid() && result.banker.is_valid());
+            assert!([-1.0, 0.0, 0.95].contains(&result.wager));
+        }
+
+        let mut game = Game::with_player(Solver {
+            sample_num: 50,
+            ..Solver::new()
+        });
+        for _ in 0..10 {
+            game.play_one_round(&bets);
+        }
+        assert!(game.results.iter().all(|result| result.player.is_valid()));
+    }
+}
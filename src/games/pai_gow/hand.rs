@@ -0,0 +1,207 @@
+use crate::cards::card::Card;
+use crate::cards::suit::ALL_SUIT_NUM;
+use crate::cards::traits::order::Order;
+use crate::games::poker::enumerate::Combinations;
+use crate::games::poker::high::{evaluate_ranks, rank, Category, HandRank, ACE_RANK, WILD_RANK};
+
+pub const HAND_NUM: usize = 7;
+pub const HIGH_NUM: usize = 5;
+pub const LOW_NUM: usize = 2;
+
+// The joker is a bug: it plays as an ace, or fills a straight, a flush or a
+// straight flush, but never pairs up with anything else.
+pub fn evaluate(cards: &[Card]) -> HandRank {
+    let card_num = cards.len();
+    let mut ranks = [0u8; HIGH_NUM];
+    let mut suits = [0u8; HIGH_NUM];
+    let mut joker = None;
+    for (idx, card) in cards.iter().enumerate() {
+        if card.is_joker() {
+            joker = Some(idx);
+            ranks[idx] = ACE_RANK;
+        } else {
+            ranks[idx] = rank(&card.number);
+            suits[idx] = card.suit.order() as u8;
+        }
+    }
+    let (ranks, suits) = (&mut ranks[..card_num], &mut suits[..card_num]);
+    let Some(joker) = joker else {
+        return evaluate_ranks(ranks, suits);
+    };
+
+    // an ace of any suit, which may itself finish a flush
+    let mut best = HandRank(0);
+    for suit in 0..ALL_SUIT_NUM as u8 {
+        suits[joker] = suit;
+        best = best.max(evaluate_ranks(ranks, suits));
+    }
+    // any card, as long as it ends up in a straight or a flush
+    if card_num == HIGH_NUM {
+        ranks[joker] = WILD_RANK;
+        let wild = evaluate_ranks(ranks, suits);
+        if matches!(
+            wild.category(),
+            Category::Straight | Category::Flush | Category::StraightFlush
+        ) {
+            best = best.max(wild);
+        }
+    }
+    best
+}
+
+// Best five out of all seven cards, as the fortune bet sees them.
+pub fn evaluate_best(cards: &[Card; HAND_NUM]) -> HandRank {
+    Combinations::new(HAND_NUM, HIGH_NUM)
+        .map(|idxes| {
+            let high: [Card; HIGH_NUM] = core::array::from_fn(|idx| cards[idxes[idx]].clone());
+            evaluate(&high)
+        })
+        .max()
+        .unwrap()
+}
+
+// Seven cards set into a five-card high hand and a two-card low hand.
+#[derive(Clone)]
+pub struct Setting {
+    pub high: [Card; HIGH_NUM],
+    pub low: [Card; LOW_NUM],
+    pub high_rank: HandRank,
+    pub low_rank: HandRank,
+}
+
+impl Setting {
+    pub fn new(high: [Card; HIGH_NUM], low: [Card; LOW_NUM]) -> Setting {
+        let high_rank = evaluate(&high);
+        let low_rank = evaluate(&low);
+        Setting {
+            high,
+            low,
+            high_rank,
+            low_rank,
+        }
+    }
+
+    // The high hand has to outrank the low hand, or the hand fouls.
+    pub fn is_valid(&self) -> bool {
+        self.high_rank >= self.low_rank
+    }
+
+    // All 21 ways to set a hand, fouls included.
+    pub fn all(cards: &[Card; HAND_NUM]) -> Vec<Setting> {
+        Combinations::new(HAND_NUM, LOW_NUM)
+            .map(|low_idxes| {
+                let mut high_idxes = (0..HAND_NUM).filter(|idx| !low_idxes.contains(idx));
+                let high = core::array::from_fn(|_| cards[high_idxes.next().unwrap()].clone());
+                let low = core::array::from_fn(|idx| cards[low_idxes[idx]].clone());
+                Setting::new(high, low)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: &[(Suit, Number)]) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|(suit, number)| Card::new(suit.clone(), number.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn evaluate_bug_test() {
+        // joker with a pair of kings is kings with an ace kicker, not trips
+        let mut cards = hand(&[
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Four),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::OnePair);
+        assert_eq!(rank.ranks()[..2], [11, ACE_RANK]);
+
+        // joker with an ace is a pair of aces
+        let mut cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Four),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        assert_eq!(evaluate(&cards).category(), Category::OnePair);
+
+        // joker fills an inside straight
+        let mut cards = hand(&[
+            (Suit::Spade, Number::Five),
+            (Suit::Heart, Number::Six),
+            (Suit::Club, Number::Eight),
+            (Suit::Spade, Number::Nine),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::Straight);
+        assert_eq!(rank.ranks()[0], 7);
+
+        // joker finishes a flush
+        let mut cards = hand(&[
+            (Suit::Club, Number::Two),
+            (Suit::Club, Number::Six),
+            (Suit::Club, Number::Eight),
+            (Suit::Club, Number::Jack),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        assert_eq!(evaluate(&cards).category(), Category::Flush);
+
+        // four aces and the joker
+        let mut cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Ace),
+            (Suit::Diamond, Number::Ace),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        assert_eq!(evaluate(&cards).category(), Category::FiveOfAKind);
+
+        // low hand: joker is an ace
+        let mut cards = hand(&[(Suit::Spade, Number::Queen)]);
+        cards.push(Card::joker(0).unwrap());
+        assert_eq!(evaluate(&cards).ranks()[..2], [ACE_RANK, 10]);
+    }
+
+    #[test]
+    fn setting_test() {
+        let cards: [Card; HAND_NUM] = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::Queen),
+            (Suit::Spade, Number::Nine),
+            (Suit::Diamond, Number::Seven),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ])
+        .try_into()
+        .unwrap_or_else(|_| panic!());
+        let settings = Setting::all(&cards);
+        assert_eq!(settings.len(), 21);
+        // ace and king in the low hand fouls
+        assert!(!settings[0].is_valid());
+        assert!(settings.iter().filter(|setting| setting.is_valid()).count() > 0);
+        for setting in settings.iter() {
+            let mut orders: Vec<u64> = setting
+                .high
+                .iter()
+                .chain(setting.low.iter())
+                .map(|card| card.order())
+                .collect();
+            orders.sort();
+            orders.dedup();
+            assert_eq!(orders.len(), HAND_NUM);
+        }
+    }
+}
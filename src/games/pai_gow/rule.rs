@@ -0,0 +1,627 @@
+use std::ops::RangeInclusive;
+
+use super::hand::{evaluate_best, Setting, HAND_NUM};
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{rank, Category, ACE_RANK};
+
+const SIX_RANK: u8 = 4;
+const SEVEN_RANK: u8 = 5;
+const TEN_RANK: u8 = 8;
+
+// Taken off every winning wager.
+pub const COMMISSION: f64 = 0.05;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Win,
+    Push,
+    Lose,
+}
+
+impl Outcome {
+    // Net result per unit wager.
+    pub fn payout(&self) -> f64 {
+        match *self {
+            Outcome::Win => 1.0 - COMMISSION,
+            Outcome::Push => 0.0,
+            Outcome::Lose => -1.0,
+        }
+    }
+}
+
+// Copies go to the banker, a foul loses outright.
+pub fn settle(player: &Setting, banker: &Setting) -> Outcome {
+    if !player.is_valid() {
+        return Outcome::Lose;
+    }
+    let high = player.high_rank > banker.high_rank;
+    let low = player.low_rank > banker.low_rank;
+    match (high, low) {
+        (true, true) => Outcome::Win,
+        (false, false) => Outcome::Lose,
+        _ => Outcome::Push,
+    }
+}
+
+// Sets of equal cards in seven, with the joker counted as an ace.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Pattern {
+    NoPair,
+    OnePair,
+    TwoPair,
+    ThreePair,
+    ThreeOfAKind,
+    TwoThreeOfAKind,
+    FullHouse, // trips with one or two pairs
+    FourOfAKind,
+    FiveAces,
+}
+
+// What a house way looks at before setting a hand.
+#[derive(Clone, Debug)]
+pub struct Shape {
+    pub pattern: Pattern,
+    pub top: u8, // rank of the biggest set, or the highest card with no pair
+    pub ace_single: bool,
+    pub straight_or_flush: bool,
+}
+
+impl Shape {
+    pub fn new(cards: &[Card; HAND_NUM], settings: &[Setting]) -> Shape {
+        let mut counts = [0u8; ACE_RANK as usize + 1];
+        for card in cards.iter() {
+            let rank = if card.is_joker() {
+                ACE_RANK
+            } else {
+                rank(&card.number)
+            };
+            counts[rank as usize] += 1;
+        }
+        let sets = |size: u8| {
+            (0..=ACE_RANK)
+                .rev()
+                .filter(move |rank| counts[*rank as usize] == size)
+        };
+        let quad = sets(4).next();
+        let trips: Vec<u8> = sets(3).collect();
+        let pairs: Vec<u8> = sets(2).collect();
+
+        let (pattern, top) = if counts[ACE_RANK as usize] == 5 {
+            (Pattern::FiveAces, ACE_RANK)
+        } else if let Some(quad) = quad {
+            (Pattern::FourOfAKind, quad)
+        } else if trips.len() == 2 {
+            (Pattern::TwoThreeOfAKind, trips[0])
+        } else if trips.len() == 1 && !pairs.is_empty() {
+            (Pattern::FullHouse, trips[0])
+        } else if trips.len() == 1 {
+            (Pattern::ThreeOfAKind, trips[0])
+        } else if pairs.len() >= 3 {
+            (Pattern::ThreePair, pairs[0])
+        } else if pairs.len() == 2 {
+            (Pattern::TwoPair, pairs[0])
+        } else if pairs.len() == 1 {
+            (Pattern::OnePair, pairs[0])
+        } else {
+            (Pattern::NoPair, sets(1).next().unwrap())
+        };
+        Shape {
+            pattern,
+            top,
+            ace_single: counts[ACE_RANK as usize] == 1,
+            straight_or_flush: settings.iter().any(|setting| {
+                matches!(
+                    setting.high_rank.category(),
+                    Category::Straight | Category::Flush | Category::StraightFlush
+                )
+            }),
+        }
+    }
+}
+
+// None matches either way.
+#[derive(Clone, Debug)]
+pub struct Condition {
+    pub pattern: Pattern,
+    pub top: RangeInclusive<u8>,
+    pub ace_single: Option<bool>,
+    pub straight_or_flush: Option<bool>,
+}
+
+impl Condition {
+    pub fn new(pattern: Pattern) -> Condition {
+        Condition {
+            pattern,
+            top: 0..=ACE_RANK,
+            ace_single: None,
+            straight_or_flush: None,
+        }
+    }
+
+    pub fn matches(&self, shape: &Shape) -> bool {
+        self.pattern == shape.pattern
+            && self.top.contains(&shape.top)
+            && self.ace_single.is_none_or(|ace| ace == shape.ace_single)
+            && self
+                .straight_or_flush
+                .is_none_or(|straight_or_flush| straight_or_flush == shape.straight_or_flush)
+    }
+}
+
+// Keep at least `high` in the high hand and `low` in the low hand, then put
+// the best low hand possible behind it.
+#[derive(Clone, Debug)]
+pub struct Rule {
+    pub when: Condition,
+    pub high: Category,
+    pub low: Category,
+}
+
+impl Rule {
+    pub fn new(when: Condition, high: Category, low: Category) -> Rule {
+        Rule { when, high, low }
+    }
+}
+
+pub trait Setter {
+    fn set(&self, cards: &[Card; HAND_NUM]) -> Setting;
+}
+
+// The first rule matching a hand sets it.
+#[derive(Clone, Debug)]
+pub struct HouseWay {
+    pub rules: Vec<Rule>,
+}
+
+impl Default for HouseWay {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+impl HouseWay {
+    pub fn standard() -> HouseWay {
+        let with_straight = |pattern| Condition {
+            straight_or_flush: Some(true),
+            ..Condition::new(pattern)
+        };
+        HouseWay {
+            rules: vec![
+                Rule::new(
+                    with_straight(Pattern::NoPair),
+                    Category::Straight,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::NoPair),
+                    Category::HighCard,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    with_straight(Pattern::OnePair),
+                    Category::Straight,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::OnePair),
+                    Category::OnePair,
+                    Category::HighCard,
+                ),
+                // small two pair stays together when an ace can go low
+                Rule::new(
+                    Condition {
+                        top: 0..=TEN_RANK,
+                        ace_single: Some(true),
+                        ..Condition::new(Pattern::TwoPair)
+                    },
+                    Category::TwoPair,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::TwoPair),
+                    Category::OnePair,
+                    Category::OnePair,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::ThreePair),
+                    Category::TwoPair,
+                    Category::OnePair,
+                ),
+                // trip aces: a pair of aces high, the third ace low
+                Rule::new(
+                    Condition {
+                        top: ACE_RANK..=ACE_RANK,
+                        ..Condition::new(Pattern::ThreeOfAKind)
+                    },
+                    Category::OnePair,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    with_straight(Pattern::ThreeOfAKind),
+                    Category::Straight,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::ThreeOfAKind),
+                    Category::ThreeOfAKind,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::TwoThreeOfAKind),
+                    Category::ThreeOfAKind,
+                    Category::OnePair,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::FullHouse),
+                    Category::ThreeOfAKind,
+                    Category::OnePair,
+                ),
+                Rule::new(
+                    Condition {
+                        top: 0..=SIX_RANK,
+                        ..Condition::new(Pattern::FourOfAKind)
+                    },
+                    Category::FourOfAKind,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition {
+                        top: SEVEN_RANK..=TEN_RANK,
+                        ace_single: Some(true),
+                        ..Condition::new(Pattern::FourOfAKind)
+                    },
+                    Category::FourOfAKind,
+                    Category::HighCard,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::FourOfAKind),
+                    Category::OnePair,
+                    Category::OnePair,
+                ),
+                Rule::new(
+                    Condition::new(Pattern::FiveAces),
+                    Category::ThreeOfAKind,
+                    Category::OnePair,
+                ),
+            ],
+        }
+    }
+}
+
+impl Setter for HouseWay {
+    fn set(&self, cards: &[Card; HAND_NUM]) -> Setting {
+        let settings: Vec<Setting> = Setting::all(cards)
+            .into_iter()
+            .filter(|setting| setting.is_valid())
+            .collect();
+        let shape = Shape::new(cards, &settings);
+        let ruled = self
+            .rules
+            .iter()
+            .find(|rule| rule.when.matches(&shape))
+            .and_then(|rule| {
+                settings
+                    .iter()
+                    .filter(|setting| {
+                        setting.high_rank.category() >= rule.high
+                            && setting.low_rank.category() >= rule.low
+                    })
+                    .max_by_key(|setting| (setting.low_rank, setting.high_rank))
+            });
+        // no rule for it: keep the best high hand
+        ruled
+            .or_else(|| {
+                settings.iter().max_by_key(|setting| {
+                    (
+                        setting.high_rank.category(),
+                        setting.low_rank,
+                        setting.high_rank,
+                    )
+                })
+            })
+            .unwrap()
+            .clone()
+    }
+}
+
+// Fortune pays on the best five of the seven cards regardless of the banker,
+// bug included. Net per unit bet, straight or better.
+#[derive(Clone, Debug)]
+pub struct FortunePayTable {
+    pub seven_card_straight_flush: f64, // natural
+    pub seven_card_straight_flush_joker: f64,
+    pub five_aces: f64,
+    pub royal_flush: f64,
+    pub straight_flush: f64,
+    pub four_of_a_kind: f64,
+    pub full_house: f64,
+    pub flush: f64,
+    pub three_of_a_kind: f64,
+    pub straight: f64,
+}
+
+impl FortunePayTable {
+    pub fn standard() -> FortunePayTable {
+        FortunePayTable {
+            seven_card_straight_flush: 8000.0,
+            seven_card_straight_flush_joker: 1000.0,
+            five_aces: 400.0,
+            royal_flush: 150.0,
+            straight_flush: 50.0,
+            four_of_a_kind: 25.0,
+            full_house: 5.0,
+            flush: 4.0,
+            three_of_a_kind: 3.0,
+            straight: 2.0,
+        }
+    }
+
+    pub fn payout(&self, cards: &[Card; HAND_NUM]) -> f64 {
+        if let Some(joker) = seven_card_straight_flush(cards) {
+            return if joker {
+                self.seven_card_straight_flush_joker
+            } else {
+                self.seven_card_straight_flush
+            };
+        }
+        let best = evaluate_best(cards);
+        if best.is_royal_flush() {
+            return self.royal_flush;
+        }
+        match best.category() {
+            Category::FiveOfAKind => self.five_aces,
+            Category::StraightFlush => self.straight_flush,
+            Category::FourOfAKind => self.four_of_a_kind,
+            Category::FullHouse => self.full_house,
+            Category::Flush => self.flush,
+            Category::ThreeOfAKind => self.three_of_a_kind,
+            Category::Straight => self.straight,
+            _ => -1.0,
+        }
+    }
+}
+
+// All seven cards in one suited run, ace low allowed. Some(true) when the
+// joker is part of it.
+fn seven_card_straight_flush(cards: &[Card; HAND_NUM]) -> Option<bool> {
+    const RUN_MASK: u16 = 0b111_1111;
+    const WHEEL_MASK: u16 = 0b1_0000_0011_1111;
+    let naturals: Vec<&Card> = cards.iter().filter(|card| !card.is_joker()).collect();
+    let suit = naturals[0].suit.order();
+    let mut rank_mask = 0u16;
+    for card in naturals.iter() {
+        if card.suit.order() != suit {
+            return None;
+        }
+        rank_mask |= 1 << rank(&card.number);
+    }
+    let run = (0..=(ACE_RANK - 6)).any(|low| rank_mask & !(RUN_MASK << low) == 0)
+        || rank_mask & !WHEEL_MASK == 0;
+    (run && rank_mask.count_ones() as usize == naturals.len()).then_some(naturals.len() < HAND_NUM)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: &[(Suit, Number)]) -> [Card; HAND_NUM] {
+        let mut hand: Vec<Card> = cards
+            .iter()
+            .map(|(suit, number)| Card::new(suit.clone(), number.clone()))
+            .collect();
+        if hand.len() < HAND_NUM {
+            hand.push(Card::joker(0).unwrap());
+        }
+        hand.try_into().unwrap_or_else(|_| panic!())
+    }
+
+    fn numbers(cards: &[Card]) -> Vec<u8> {
+        let mut ranks: Vec<u8> = cards
+            .iter()
+            .map(|card| {
+                if card.is_joker() {
+                    ACE_RANK
+                } else {
+                    rank(&card.number)
+                }
+            })
+            .collect();
+        ranks.sort();
+        ranks
+    }
+
+    #[test]
+    fn house_way_test() {
+        let house_way = HouseWay::standard();
+
+        // no pair: second and third highest go low
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::Queen),
+            (Suit::Spade, Number::Nine),
+            (Suit::Diamond, Number::Seven),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ]);
+        assert_eq!(numbers(&house_way.set(&cards).low), vec![10, 11]);
+
+        // two pair with an ace single stays together
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Eight),
+            (Suit::Club, Number::Eight),
+            (Suit::Spade, Number::Five),
+            (Suit::Diamond, Number::Five),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ]);
+        let setting = house_way.set(&cards);
+        assert_eq!(setting.high_rank.category(), Category::TwoPair);
+        assert_eq!(numbers(&setting.low), vec![2, ACE_RANK]);
+
+        // big two pair splits
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::King),
+            (Suit::Spade, Number::Five),
+            (Suit::Diamond, Number::Five),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ]);
+        let setting = house_way.set(&cards);
+        assert_eq!(numbers(&setting.low), vec![3, 3]);
+        assert_eq!(setting.high_rank.ranks()[0], 11);
+
+        // full house: the pair goes low
+        let cards = hand(&[
+            (Suit::Spade, Number::Nine),
+            (Suit::Heart, Number::Nine),
+            (Suit::Club, Number::Nine),
+            (Suit::Spade, Number::Jack),
+            (Suit::Diamond, Number::Jack),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ]);
+        let setting = house_way.set(&cards);
+        assert_eq!(numbers(&setting.low), vec![9, 9]);
+        assert_eq!(setting.high_rank.category(), Category::ThreeOfAKind);
+
+        // joker fills a straight: play it, best two leftovers low
+        let cards = hand(&[
+            (Suit::Spade, Number::Four),
+            (Suit::Heart, Number::Five),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Eight),
+            (Suit::Diamond, Number::King),
+            (Suit::Heart, Number::Queen),
+        ]);
+        let setting = house_way.set(&cards);
+        assert_eq!(setting.high_rank.category(), Category::Straight);
+        assert_eq!(numbers(&setting.low), vec![10, 11]);
+
+        // five aces: aces low
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Ace),
+            (Suit::Diamond, Number::Ace),
+            (Suit::Diamond, Number::Queen),
+            (Suit::Heart, Number::Three),
+        ]);
+        let setting = house_way.set(&cards);
+        assert_eq!(numbers(&setting.low), vec![ACE_RANK, ACE_RANK]);
+        assert_eq!(setting.high_rank.category(), Category::ThreeOfAKind);
+
+        // no rules: best high hand
+        let house_way = HouseWay { rules: Vec::new() };
+        let setting = house_way.set(&cards);
+        assert_eq!(setting.high_rank.category(), Category::FiveOfAKind);
+    }
+
+    fn setting(high: [(Suit, Number); 5], low: [(Suit, Number); 2]) -> Setting {
+        Setting::new(
+            high.map(|(suit, number)| Card::new(suit, number)),
+            low.map(|(suit, number)| Card::new(suit, number)),
+        )
+    }
+
+    #[test]
+    fn settle_test() {
+        let player = setting(
+            [
+                (Suit::Spade, Number::Ace),
+                (Suit::Heart, Number::Ace),
+                (Suit::Club, Number::Nine),
+                (Suit::Spade, Number::Seven),
+                (Suit::Diamond, Number::Four),
+            ],
+            [(Suit::Heart, Number::King), (Suit::Club, Number::Queen)],
+        );
+        let copy = setting(
+            [
+                (Suit::Club, Number::Ace),
+                (Suit::Diamond, Number::Ace),
+                (Suit::Heart, Number::Nine),
+                (Suit::Club, Number::Seven),
+                (Suit::Club, Number::Four),
+            ],
+            [(Suit::Spade, Number::King), (Suit::Spade, Number::Queen)],
+        );
+        // copies go to the banker either way round
+        assert_eq!(settle(&player, &copy), Outcome::Lose);
+        assert_eq!(settle(&copy, &player), Outcome::Lose);
+
+        let weaker = Setting::new(
+            copy.high.clone(),
+            [
+                Card::new(Suit::Spade, Number::King),
+                Card::new(Suit::Spade, Number::Jack),
+            ],
+        );
+        assert_eq!(settle(&player, &weaker), Outcome::Push);
+        assert_eq!(settle(&weaker, &player), Outcome::Lose);
+        assert_eq!(Outcome::Win.payout(), 1.0 - COMMISSION);
+
+        // a foul loses no matter what
+        let foul = setting(
+            [
+                (Suit::Club, Number::Two),
+                (Suit::Diamond, Number::Five),
+                (Suit::Heart, Number::Nine),
+                (Suit::Club, Number::Seven),
+                (Suit::Club, Number::Four),
+            ],
+            [(Suit::Spade, Number::Ace), (Suit::Heart, Number::Ace)],
+        );
+        assert!(!foul.is_valid());
+        assert_eq!(settle(&foul, &weaker), Outcome::Lose);
+    }
+
+    #[test]
+    fn fortune_test() {
+        let table = FortunePayTable::standard();
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Spade, Number::Two),
+            (Suit::Spade, Number::Three),
+            (Suit::Spade, Number::Four),
+            (Suit::Spade, Number::Five),
+            (Suit::Spade, Number::Six),
+            (Suit::Spade, Number::Seven),
+        ]);
+        assert_eq!(table.payout(&cards), 8000.0);
+        let cards = hand(&[
+            (Suit::Heart, Number::Eight),
+            (Suit::Heart, Number::Nine),
+            (Suit::Heart, Number::Ten),
+            (Suit::Heart, Number::Queen),
+            (Suit::Heart, Number::King),
+            (Suit::Heart, Number::Ace),
+        ]);
+        assert_eq!(table.payout(&cards), 1000.0);
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Ace),
+            (Suit::Diamond, Number::Ace),
+            (Suit::Diamond, Number::Queen),
+            (Suit::Heart, Number::Three),
+        ]);
+        assert_eq!(table.payout(&cards), 400.0);
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::King),
+            (Suit::Spade, Number::Five),
+            (Suit::Diamond, Number::Five),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Two),
+        ]);
+        assert_eq!(table.payout(&cards), -1.0);
+    }
+}
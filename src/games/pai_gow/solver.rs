@@ -0,0 +1,126 @@
+use rand::Rng;
+
+use super::hand::{Setting, HAND_NUM};
+use super::rule::{settle, HouseWay, Setter};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::order::Order;
+
+// Expected result of a setting against banker hands already set.
+pub fn expected(setting: &Setting, bankers: &[Setting]) -> f64 {
+    let total: f64 = bankers
+        .iter()
+        .map(|banker| settle(setting, banker).payout())
+        .sum();
+    total / bankers.len() as f64
+}
+
+// Sets a hand for the best expected result against a banker playing the
+// house way, over banker hands sampled from the unseen cards.
+#[derive(Clone, Debug)]
+pub struct Solver {
+    pub house_way: HouseWay,
+    pub sample_num: usize,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Solver {
+    pub fn new() -> Solver {
+        Solver {
+            house_way: HouseWay::standard(),
+            sample_num: 1000,
+        }
+    }
+
+    pub fn sample_bankers(&self, cards: &[Card; HAND_NUM]) -> Vec<Setting> {
+        let mut unseen: Vec<Card> = Deck::with_jokers(1)
+            .cards
+            .into_iter()
+            .filter(|card| cards.iter().all(|held| held.order() != card.order()))
+            .collect();
+        let mut rng = rand::thread_rng();
+        (0..self.sample_num)
+            .map(|_| {
+                // partial shuffle of the first seven
+                for idx in 0..HAND_NUM {
+                    let target_idx = rng.gen_range(idx..unseen.len());
+                    unseen.swap(idx, target_idx);
+                }
+                let banker: [Card; HAND_NUM] = core::array::from_fn(|idx| unseen[idx].clone());
+                self.house_way.set(&banker)
+            })
+            .collect()
+    }
+
+    // Every valid setting with its expected result.
+    pub fn values(&self, cards: &[Card; HAND_NUM], bankers: &[Setting]) -> Vec<(Setting, f64)> {
+        Setting::all(cards)
+            .into_iter()
+            .filter(|setting| setting.is_valid())
+            .map(|setting| {
+                let value = expected(&setting, bankers);
+                (setting, value)
+            })
+            .collect()
+    }
+}
+
+impl Setter for Solver {
+    fn set(&self, cards: &[Card; HAND_NUM]) -> Setting {
+        let bankers = self.sample_bankers(cards);
+        self.values(cards, &bankers)
+            .into_iter()
+            .max_by(|(_, value1), (_, value2)| value1.total_cmp(value2))
+            .unwrap()
+            .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+    use crate::games::poker::high::Category;
+
+    #[test]
+    fn solver_test() {
+        let solver = Solver {
+            sample_num: 300,
+            ..Solver::new()
+        };
+        // K, K, Q, Q, 7, 5, 3: splitting is far ahead of keeping the pairs
+        let cards = [
+            Card::new(Suit::Spade, Number::King),
+            Card::new(Suit::Heart, Number::King),
+            Card::new(Suit::Club, Number::Queen),
+            Card::new(Suit::Diamond, Number::Queen),
+            Card::new(Suit::Club, Number::Seven),
+            Card::new(Suit::Heart, Number::Five),
+            Card::new(Suit::Diamond, Number::Three),
+        ];
+        let bankers = solver.sample_bankers(&cards);
+        assert_eq!(bankers.len(), 300);
+        assert!(bankers.iter().all(|banker| banker.is_valid()));
+
+        let setting = solver.set(&cards);
+        assert!(setting.is_valid());
+        assert_eq!(setting.high_rank.category(), Category::OnePair);
+        assert_eq!(setting.low_rank.category(), Category::OnePair);
+        assert_eq!(setting.high_rank.ranks()[0], 11);
+
+        // the solver never does worse than the house way on its own sample
+        let values = solver.values(&cards, &bankers);
+        let best = values
+            .iter()
+            .map(|(_, value)| *value)
+            .fold(f64::MIN, f64::max);
+        let house = expected(&solver.house_way.set(&cards), &bankers);
+        assert!(best >= house);
+    }
+}
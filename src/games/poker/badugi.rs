@@ -0,0 +1,128 @@
+use super::low::ace_to_five_rank;
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+
+pub const HAND_NUM: usize = 4;
+const RANK_BITS: u32 = 4;
+const RANKS_WORST: u32 = (1 << (RANK_BITS * HAND_NUM as u32)) - 1;
+
+// The more cards of different suits and ranks the better, then the lower the
+// highest of them, aces low. Greater is better.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct BadugiRank(pub u32);
+
+impl BadugiRank {
+    fn new(ranks: &[u8]) -> BadugiRank {
+        let mut sorted = ranks.to_vec();
+        sorted.sort_by(|rank1, rank2| rank2.cmp(rank1));
+        let mut bad = 0;
+        for (idx, rank) in sorted.iter().enumerate() {
+            bad |= (*rank as u32) << (RANK_BITS * (HAND_NUM - 1 - idx) as u32);
+        }
+        BadugiRank(((ranks.len() as u32) << (RANK_BITS * HAND_NUM as u32)) | (RANKS_WORST - bad))
+    }
+
+    // How many cards play, four for a badugi.
+    pub fn card_num(&self) -> usize {
+        (self.0 >> (RANK_BITS * HAND_NUM as u32)) as usize
+    }
+
+    pub fn is_badugi(&self) -> bool {
+        self.card_num() == HAND_NUM
+    }
+
+    // The ranks that play, highest first, 0 padded.
+    pub fn ranks(&self) -> [u8; HAND_NUM] {
+        let bad = RANKS_WORST - (self.0 & RANKS_WORST);
+        core::array::from_fn(|idx| ((bad >> (RANK_BITS * (HAND_NUM - 1 - idx) as u32)) & 0xf) as u8)
+    }
+}
+
+// Which cards play in the best badugi, as a mask over `cards`.
+pub fn best_subset(cards: &[Card]) -> (BadugiRank, u32) {
+    let mut best = (BadugiRank(0), 0);
+    for mask in 1u32..(1 << cards.len()) {
+        let mut suit_mask = 0u8;
+        let mut rank_mask = 0u16;
+        let mut ranks = Vec::with_capacity(HAND_NUM);
+        let mut valid = true;
+        for (idx, card) in cards.iter().enumerate() {
+            if mask & (1 << idx) == 0 {
+                continue;
+            }
+            let suit = 1 << card.suit.order();
+            let rank = ace_to_five_rank(card);
+            if suit_mask & suit != 0 || rank_mask & (1 << rank) != 0 {
+                valid = false;
+                break;
+            }
+            suit_mask |= suit;
+            rank_mask |= 1 << rank;
+            ranks.push(rank);
+        }
+        if valid {
+            let rank = BadugiRank::new(&ranks);
+            if rank > best.0 {
+                best = (rank, mask);
+            }
+        }
+    }
+    best
+}
+
+pub fn evaluate(cards: &[Card]) -> BadugiRank {
+    best_subset(cards).0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: [(Suit, Number); 4]) -> [Card; 4] {
+        cards.map(|(suit, number)| Card::new(suit, number))
+    }
+
+    #[test]
+    fn evaluate_badugi_test() {
+        let nuts = evaluate(&hand([
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Diamond, Number::Three),
+            (Suit::Club, Number::Four),
+        ]));
+        assert!(nuts.is_badugi());
+        assert_eq!(nuts.ranks(), [3, 2, 1, 0]);
+
+        let king_badugi = evaluate(&hand([
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::Queen),
+            (Suit::Diamond, Number::Jack),
+            (Suit::Club, Number::Ten),
+        ]));
+        assert!(nuts > king_badugi);
+
+        // two spades: a three card hand, worse than any badugi
+        let (three_card, mask) = best_subset(&hand([
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Diamond, Number::Three),
+            (Suit::Spade, Number::Four),
+        ]));
+        assert_eq!(three_card.card_num(), 3);
+        assert_eq!(mask, 0b0111);
+        assert!(king_badugi > three_card);
+
+        // a paired rank plays once
+        let paired = evaluate(&hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Two),
+            (Suit::Diamond, Number::Three),
+            (Suit::Club, Number::Four),
+        ]));
+        assert_eq!(paired.card_num(), 3);
+        assert_eq!(paired.ranks()[..3], [3, 2, 1]);
+        assert!(three_card > paired);
+    }
+}
@@ -0,0 +1,158 @@
+use std::collections::HashMap;
+
+use crate::cards::card::Card;
+use crate::cards::number::ALL_NUMBER_NUM;
+use crate::cards::suit::ALL_SUIT_NUM;
+use crate::cards::traits::order::Order;
+
+#[derive(Clone, Debug)]
+pub struct HouseEdge {
+    pub combinations: u64,
+    pub expected_return: f64, // per unit of the initial bet
+}
+
+impl HouseEdge {
+    pub fn house_edge(&self) -> f64 {
+        -self.expected_return
+    }
+}
+
+// Iterates k-subsets of 0..n as ascending index vectors.
+pub struct Combinations {
+    n: usize,
+    idxes: Vec<usize>,
+    done: bool,
+}
+
+impl Combinations {
+    pub fn new(n: usize, k: usize) -> Combinations {
+        Combinations {
+            n,
+            idxes: (0..k).collect(),
+            done: k > n,
+        }
+    }
+}
+
+impl Iterator for Combinations {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.done {
+            return None;
+        }
+        let current = self.idxes.clone();
+        let k = self.idxes.len();
+        // advance the rightmost index that still has room
+        let mut pos = k;
+        while pos > 0 && self.idxes[pos - 1] == self.n - k + pos - 1 {
+            pos -= 1;
+        }
+        if pos == 0 {
+            self.done = true;
+        } else {
+            self.idxes[pos - 1] += 1;
+            for idx in pos..k {
+                self.idxes[idx] = self.idxes[idx - 1] + 1;
+            }
+        }
+        Some(current)
+    }
+}
+
+fn permutations(items: &[u64]) -> Vec<Vec<u64>> {
+    if items.len() <= 1 {
+        return vec![items.to_vec()];
+    }
+    let mut all = Vec::new();
+    for idx in 0..items.len() {
+        let mut rest = items.to_vec();
+        let first = rest.remove(idx);
+        for mut perm in permutations(&rest) {
+            perm.insert(0, first);
+            all.push(perm);
+        }
+    }
+    all
+}
+
+// Groups every `size`-card subset of `cards` by suit isomorphism and returns
+// one representative per class with the number of subsets it stands for.
+// `cards` has to be closed under suit permutation (e.g. whole ranks of a deck)
+// for the weights to be exact.
+pub fn suit_classes(cards: &[Card], size: usize) -> Vec<(Vec<Card>, u64)> {
+    let suits: Vec<u64> = (0..ALL_SUIT_NUM as u64).collect();
+    let suit_perms = permutations(&suits);
+    let mut classes: HashMap<Vec<u64>, (Vec<Card>, u64)> = HashMap::new();
+    for idxes in Combinations::new(cards.len(), size) {
+        let key = suit_perms
+            .iter()
+            .map(|perm| {
+                let mut key: Vec<u64> = idxes
+                    .iter()
+                    .map(|idx| {
+                        let card = &cards[*idx];
+                        if card.is_joker() {
+                            // jokers aren't suited, keep them apart
+                            return card.order();
+                        }
+                        perm[card.suit.order() as usize] * (ALL_NUMBER_NUM as u64)
+                            + card.number.order()
+                    })
+                    .collect();
+                key.sort();
+                key
+            })
+            .min()
+            .unwrap();
+        let entry = classes
+            .entry(key)
+            .or_insert_with(|| (idxes.iter().map(|idx| cards[*idx].clone()).collect(), 0));
+        entry.1 += 1;
+    }
+    classes.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::{all_cards, ALL_CARDS_NUM};
+    use crate::cards::deck::Deck;
+
+    #[test]
+    fn combinations_test() {
+        let all: Vec<Vec<usize>> = Combinations::new(4, 2).collect();
+        assert_eq!(
+            all,
+            vec![
+                vec![0, 1],
+                vec![0, 2],
+                vec![0, 3],
+                vec![1, 2],
+                vec![1, 3],
+                vec![2, 3]
+            ]
+        );
+        assert_eq!(Combinations::new(ALL_CARDS_NUM, 3).count(), 22100);
+        assert_eq!(Combinations::new(2, 3).count(), 0);
+        assert_eq!(Combinations::new(3, 0).count(), 1);
+    }
+
+    #[test]
+    fn suit_classes_test() {
+        let classes = suit_classes(all_cards(), 2);
+        // 13 pairs, 78 suited and 78 offsuit starting hands
+        assert_eq!(classes.len(), 169);
+        assert_eq!(classes.iter().map(|(_, weight)| weight).sum::<u64>(), 1326);
+
+        let classes = suit_classes(all_cards(), 3);
+        assert_eq!(classes.len(), 1755);
+        assert_eq!(classes.iter().map(|(_, weight)| weight).sum::<u64>(), 22100);
+
+        // plus a joker with each of the 169 and with each of the 52
+        let deck = Deck::with_jokers(1);
+        let classes = suit_classes(&deck.cards, 2);
+        assert_eq!(classes.len(), 169 + 13);
+        assert_eq!(classes.iter().map(|(_, weight)| weight).sum::<u64>(), 1378);
+    }
+}
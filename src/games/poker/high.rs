@@ -0,0 +1,627 @@
+use crate::cards::card::Card;
+use crate::cards::id::HandMask;
+use crate::cards::number::Number;
+use crate::cards::suit::ALL_SUIT_NUM;
+use crate::cards::traits::order::Order;
+use crate::cards::valuation::{RankValuation, ValueTable};
+use crate::cards::wild::Wild;
+
+// Poker ranks run from Two (0) to Ace (12).
+pub const ALL_RANK_NUM: usize = 13;
+pub const ACE_RANK: u8 = 12;
+// Stands for any card; jokers always get it, other wild cards when declared.
+pub const WILD_RANK: u8 = 13;
+// The most cards any supported game hands to the evaluator at once.
+pub const MAX_CARD_NUM: usize = 8;
+
+const RANK_BITS: u32 = 4;
+const CATEGORY_SHIFT: u32 = RANK_BITS * 5;
+const WHEEL_MASK: u16 = 0b1_0000_0000_1111;
+
+// Number::order() puts Ace first, poker puts it on top.
+pub static ACE_HIGH: ValueTable = ValueTable([12, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+
+pub fn rank(number: &Number) -> u8 {
+    if let Number::Joker = number {
+        return WILD_RANK;
+    }
+    ACE_HIGH.value(number) as u8
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Category {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    FiveOfAKind, // only with wild cards
+}
+
+pub const ALL_CATEGORY_NUM: usize = 10;
+
+pub static ALL_CATEGORIES: [Category; ALL_CATEGORY_NUM] = [
+    Category::HighCard,
+    Category::OnePair,
+    Category::TwoPair,
+    Category::ThreeOfAKind,
+    Category::Straight,
+    Category::Flush,
+    Category::FullHouse,
+    Category::FourOfAKind,
+    Category::StraightFlush,
+    Category::FiveOfAKind,
+];
+
+impl Order for Category {
+    fn order(&self) -> u64 {
+        match *self {
+            Category::HighCard => 0,
+            Category::OnePair => 1,
+            Category::TwoPair => 2,
+            Category::ThreeOfAKind => 3,
+            Category::Straight => 4,
+            Category::Flush => 5,
+            Category::FullHouse => 6,
+            Category::FourOfAKind => 7,
+            Category::StraightFlush => 8,
+            Category::FiveOfAKind => 9,
+        }
+    }
+}
+
+// Packed as category | five ranks (most significant first), so plain integer
+// comparison orders hands.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct HandRank(pub u32);
+
+impl HandRank {
+    pub fn new(category: Category, ranks: &[u8]) -> HandRank {
+        let mut packed = (category.order() as u32) << CATEGORY_SHIFT;
+        for (idx, rank) in ranks.iter().take(5).enumerate() {
+            packed |= (*rank as u32) << (RANK_BITS * (4 - idx as u32));
+        }
+        HandRank(packed)
+    }
+
+    // The weakest hand of a category.
+    pub const fn lowest(category: Category) -> HandRank {
+        HandRank((category as u32) << CATEGORY_SHIFT)
+    }
+
+    pub fn category(&self) -> Category {
+        ALL_CATEGORIES[(self.0 >> CATEGORY_SHIFT) as usize]
+    }
+
+    // The ranks that decide ties, most significant first.
+    pub fn ranks(&self) -> [u8; 5] {
+        core::array::from_fn(|idx| ((self.0 >> (RANK_BITS * (4 - idx as u32))) & 0xf) as u8)
+    }
+
+    pub fn is_royal_flush(&self) -> bool {
+        self.category() == Category::StraightFlush && self.ranks()[0] == ACE_RANK
+    }
+}
+
+fn highest_straight(mask: u16) -> Option<u8> {
+    for top in (4..ALL_RANK_NUM as u8).rev() {
+        let run = 0b1_1111 << (top - 4);
+        if mask & run == run {
+            return Some(top);
+        }
+    }
+    if mask & WHEEL_MASK == WHEEL_MASK {
+        return Some(3);
+    }
+    None
+}
+
+fn highest(mask: u16) -> Option<u8> {
+    if mask == 0 {
+        None
+    } else {
+        Some(15 - mask.leading_zeros() as u8)
+    }
+}
+
+// Ranks that make up a hand, kept on the stack since the evaluator runs in
+// enumeration loops.
+struct Ranks {
+    ranks: [u8; 5],
+    len: usize,
+}
+
+impl Ranks {
+    fn new() -> Ranks {
+        Ranks {
+            ranks: [0; 5],
+            len: 0,
+        }
+    }
+
+    fn push(mut self, rank: u8) -> Ranks {
+        self.ranks[self.len] = rank;
+        self.len += 1;
+        self
+    }
+
+    fn push_top(mut self, mut mask: u16, num: usize) -> Ranks {
+        for _ in 0..num {
+            let Some(rank) = highest(mask) else {
+                break;
+            };
+            self = self.push(rank);
+            mask &= !(1 << rank);
+        }
+        self
+    }
+
+    fn into_rank(self, category: Category) -> HandRank {
+        HandRank::new(category, &self.ranks[..self.len])
+    }
+}
+
+fn evaluate_natural(ranks: &[u8], suits: &[u8]) -> HandRank {
+    let mut counts = [0u8; ALL_RANK_NUM];
+    let mut suit_masks = [0u16; ALL_SUIT_NUM];
+    let mut suit_counts = [0u8; ALL_SUIT_NUM];
+    let mut mask = 0u16;
+    for (rank, suit) in ranks.iter().zip(suits.iter()) {
+        counts[*rank as usize] += 1;
+        suit_masks[*suit as usize] |= 1 << rank;
+        suit_counts[*suit as usize] += 1;
+        mask |= 1 << rank;
+    }
+    evaluate_counts(&counts, &suit_masks, &suit_counts, mask)
+}
+
+// Moves the ace from the bottom of a `HandMask` lane to the top.
+fn poker_lane(lane: u16) -> u16 {
+    (lane >> 1) | ((lane & 1) << ACE_RANK)
+}
+
+// Same as `evaluate` for distinct cards without wild ones, straight off the
+// bits.
+pub fn evaluate_mask(hand: HandMask) -> HandRank {
+    let suit_masks: [u16; ALL_SUIT_NUM] = core::array::from_fn(|suit| poker_lane(hand.suit(suit)));
+    let suit_counts = suit_masks.map(|suit_mask| suit_mask.count_ones() as u8);
+    let mut counts = [0u8; ALL_RANK_NUM];
+    for suit_mask in suit_masks.iter() {
+        for (rank, count) in counts.iter_mut().enumerate() {
+            *count += ((suit_mask >> rank) & 1) as u8;
+        }
+    }
+    let mask = suit_masks
+        .iter()
+        .fold(0, |mask, suit_mask| mask | suit_mask);
+    evaluate_counts(&counts, &suit_masks, &suit_counts, mask)
+}
+
+fn evaluate_counts(
+    counts: &[u8; ALL_RANK_NUM],
+    suit_masks: &[u16; ALL_SUIT_NUM],
+    suit_counts: &[u8; ALL_SUIT_NUM],
+    mask: u16,
+) -> HandRank {
+    // only reachable through wild cards
+    let five_mask = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count >= 5)
+        .fold(0u16, |five_mask, (rank, _)| five_mask | (1 << rank));
+    if let Some(five) = highest(five_mask) {
+        return HandRank::new(Category::FiveOfAKind, &[five]);
+    }
+
+    // flush and straight flush
+    let mut flush = None;
+    for (suit_mask, suit_count) in suit_masks.iter().zip(suit_counts.iter()) {
+        if *suit_count >= 5 {
+            if let Some(top) = highest_straight(*suit_mask) {
+                return HandRank::new(Category::StraightFlush, &[top]);
+            }
+            flush = Some(
+                Ranks::new()
+                    .push_top(*suit_mask, 5)
+                    .into_rank(Category::Flush),
+            );
+        }
+    }
+
+    let mut quad_mask = 0u16;
+    let mut trip_mask = 0u16;
+    let mut pair_mask = 0u16;
+    for (rank, count) in counts.iter().enumerate() {
+        match *count {
+            4 => quad_mask |= 1 << rank,
+            3 => trip_mask |= 1 << rank,
+            2 => pair_mask |= 1 << rank,
+            _ => {}
+        }
+    }
+
+    if let Some(quad) = highest(quad_mask) {
+        return Ranks::new()
+            .push(quad)
+            .push_top(mask & !(1 << quad), 1)
+            .into_rank(Category::FourOfAKind);
+    }
+    if let Some(trip) = highest(trip_mask) {
+        // a second set of trips plays as the pair
+        if let Some(pair) = highest((trip_mask & !(1 << trip)) | pair_mask) {
+            return HandRank::new(Category::FullHouse, &[trip, pair]);
+        }
+    }
+    if let Some(flush) = flush {
+        return flush;
+    }
+    if let Some(top) = highest_straight(mask) {
+        return HandRank::new(Category::Straight, &[top]);
+    }
+    if let Some(trip) = highest(trip_mask) {
+        return Ranks::new()
+            .push(trip)
+            .push_top(mask & !(1 << trip), 2)
+            .into_rank(Category::ThreeOfAKind);
+    }
+    if pair_mask.count_ones() >= 2 {
+        let top_pairs = Ranks::new().push_top(pair_mask, 2);
+        let kicker_mask = mask & !(1 << top_pairs.ranks[0]) & !(1 << top_pairs.ranks[1]);
+        return top_pairs
+            .push_top(kicker_mask, 1)
+            .into_rank(Category::TwoPair);
+    }
+    if let Some(pair) = highest(pair_mask) {
+        return Ranks::new()
+            .push(pair)
+            .push_top(mask & !(1 << pair), 3)
+            .into_rank(Category::OnePair);
+    }
+    Ranks::new().push_top(mask, 5).into_rank(Category::HighCard)
+}
+
+// Tries every rank for the wild cards, all in one suit at a time since
+// splitting them over suits never helps a flush.
+fn evaluate_substituted(
+    ranks: &mut [u8; MAX_CARD_NUM],
+    suits: &mut [u8; MAX_CARD_NUM],
+    card_num: usize,
+    next: usize,
+    min_rank: u8,
+) -> HandRank {
+    if next == card_num {
+        return evaluate_natural(&ranks[..card_num], &suits[..card_num]);
+    }
+    let mut best = HandRank(0);
+    for rank in min_rank..(ALL_RANK_NUM as u8) {
+        ranks[next] = rank;
+        // wild cards are interchangeable, so ranks never need to go down
+        best = best.max(evaluate_substituted(ranks, suits, card_num, next + 1, rank));
+    }
+    best
+}
+
+// Evaluate the best five-card hand out of up to MAX_CARD_NUM (rank, suit)
+// pairs, resolving WILD_RANK to whatever plays best. Fewer than five cards
+// are ranked on what is there, more than MAX_CARD_NUM panic.
+pub fn evaluate_ranks(ranks: &[u8], suits: &[u8]) -> HandRank {
+    let card_num = ranks.len();
+    assert!(
+        card_num <= MAX_CARD_NUM,
+        "{} cards, at most {} can be evaluated",
+        card_num,
+        MAX_CARD_NUM
+    );
+    let wild_num = ranks.iter().filter(|rank| **rank == WILD_RANK).count();
+    if wild_num == 0 {
+        return evaluate_natural(ranks, suits);
+    }
+    // naturals first, wild cards after
+    let natural_num = card_num - wild_num;
+    let mut sub_ranks = [0u8; MAX_CARD_NUM];
+    let mut sub_suits = [0u8; MAX_CARD_NUM];
+    let mut pos = 0;
+    for (rank, suit) in ranks.iter().zip(suits.iter()) {
+        if *rank != WILD_RANK {
+            sub_ranks[pos] = *rank;
+            sub_suits[pos] = *suit;
+            pos += 1;
+        }
+    }
+    let mut best = HandRank(0);
+    for suit in 0..ALL_SUIT_NUM as u8 {
+        for sub_suit in sub_suits[natural_num..card_num].iter_mut() {
+            *sub_suit = suit;
+        }
+        best = best.max(evaluate_substituted(
+            &mut sub_ranks,
+            &mut sub_suits,
+            card_num,
+            natural_num,
+            0,
+        ));
+    }
+    best
+}
+
+// Jokers play wild.
+pub fn evaluate(cards: &[Card]) -> HandRank {
+    evaluate_wild(cards, &Wild::jokers())
+}
+
+// Like `evaluate_ranks`, more than MAX_CARD_NUM cards panic, dead jokers
+// left out.
+pub fn evaluate_wild(cards: &[Card], wild: &Wild) -> HandRank {
+    let mut ranks = [0u8; MAX_CARD_NUM];
+    let mut suits = [0u8; MAX_CARD_NUM];
+    let mut card_num = 0;
+    for card in cards.iter() {
+        let card_rank = if wild.is_wild(card) {
+            WILD_RANK
+        } else if card.is_joker() {
+            // a joker that isn't wild is a dead card
+            continue;
+        } else {
+            rank(&card.number)
+        };
+        assert!(
+            card_num < MAX_CARD_NUM,
+            "more than {} cards can't be evaluated",
+            MAX_CARD_NUM
+        );
+        ranks[card_num] = card_rank;
+        suits[card_num] = card.suit.order() as u8;
+        card_num += 1;
+    }
+    evaluate_ranks(&ranks[..card_num], &suits[..card_num])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: &[(Suit, Number)]) -> Vec<Card> {
+        cards
+            .iter()
+            .map(|(suit, number)| Card::new(suit.clone(), number.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn rank_test() {
+        assert_eq!(rank(&Number::Two), 0);
+        assert_eq!(rank(&Number::King), 11);
+        assert_eq!(rank(&Number::Ace), ACE_RANK);
+        assert_eq!(rank(&Number::Joker), WILD_RANK);
+    }
+
+    #[test]
+    fn evaluate_category_test() {
+        // A, K, Q, J, T suited
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Spade, Number::King),
+            (Suit::Spade, Number::Queen),
+            (Suit::Spade, Number::Jack),
+            (Suit::Spade, Number::Ten),
+        ]);
+        assert!(evaluate(&cards).is_royal_flush());
+
+        // A, 2, 3, 4, 5 offsuit
+        let cards = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Spade, Number::Three),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Five),
+        ]);
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::Straight);
+        assert_eq!(rank.ranks()[0], 3);
+
+        // 9, 9, 9, 4, 4, 4, K
+        let cards = hand(&[
+            (Suit::Spade, Number::Nine),
+            (Suit::Heart, Number::Nine),
+            (Suit::Club, Number::Nine),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Four),
+            (Suit::Heart, Number::Four),
+            (Suit::Heart, Number::King),
+        ]);
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::FullHouse);
+        assert_eq!(rank.ranks()[..2], [7, 2]);
+
+        // 2, 5, 7, 9, J of hearts, 8, T offsuit
+        let cards = hand(&[
+            (Suit::Heart, Number::Two),
+            (Suit::Heart, Number::Five),
+            (Suit::Heart, Number::Seven),
+            (Suit::Heart, Number::Nine),
+            (Suit::Heart, Number::Jack),
+            (Suit::Club, Number::Eight),
+            (Suit::Spade, Number::Ten),
+        ]);
+        assert_eq!(evaluate(&cards).category(), Category::Flush);
+
+        // Q, Q, 3, 3, 7, 7, A
+        let cards = hand(&[
+            (Suit::Heart, Number::Queen),
+            (Suit::Club, Number::Queen),
+            (Suit::Heart, Number::Three),
+            (Suit::Spade, Number::Three),
+            (Suit::Heart, Number::Seven),
+            (Suit::Club, Number::Seven),
+            (Suit::Diamond, Number::Ace),
+        ]);
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::TwoPair);
+        assert_eq!(rank.ranks()[..3], [10, 5, 12]);
+    }
+
+    #[test]
+    fn evaluate_compare_test() {
+        // K, K, 5, 4, 3 vs K, K, 6, 3, 2
+        let hand1 = hand(&[
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::King),
+            (Suit::Spade, Number::Five),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Three),
+        ]);
+        let hand2 = hand(&[
+            (Suit::Diamond, Number::King),
+            (Suit::Club, Number::King),
+            (Suit::Heart, Number::Six),
+            (Suit::Heart, Number::Three),
+            (Suit::Heart, Number::Two),
+        ]);
+        assert!(evaluate(&hand1) < evaluate(&hand2));
+
+        // 6-high straight beats the wheel
+        let hand1 = hand(&[
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Three),
+            (Suit::Spade, Number::Four),
+            (Suit::Club, Number::Five),
+            (Suit::Spade, Number::Six),
+        ]);
+        let hand2 = hand(&[
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Spade, Number::Three),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Five),
+        ]);
+        assert!(evaluate(&hand1) > evaluate(&hand2));
+
+        // same ranks, different suits
+        let hand1 = hand(&[(Suit::Spade, Number::Ace), (Suit::Heart, Number::Jack)]);
+        let hand2 = hand(&[(Suit::Club, Number::Ace), (Suit::Diamond, Number::Jack)]);
+        assert_eq!(evaluate(&hand1), evaluate(&hand2));
+    }
+
+    #[test]
+    fn evaluate_wild_test() {
+        // joker, K, Q, J, T of spades
+        let mut cards = hand(&[
+            (Suit::Spade, Number::King),
+            (Suit::Spade, Number::Queen),
+            (Suit::Spade, Number::Jack),
+            (Suit::Spade, Number::Ten),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        assert!(evaluate(&cards).is_royal_flush());
+        assert_eq!(
+            evaluate_wild(&cards, &Wild::none()).category(),
+            Category::HighCard
+        );
+
+        // joker, 9, 9, 9, 9
+        let mut cards = hand(&[
+            (Suit::Spade, Number::Nine),
+            (Suit::Heart, Number::Nine),
+            (Suit::Club, Number::Nine),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        cards.push(Card::joker(1).unwrap());
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::FiveOfAKind);
+        assert_eq!(rank.ranks()[0], 7);
+
+        // deuces wild: 2, 2, 7, 7, K
+        let cards = hand(&[
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Two),
+            (Suit::Club, Number::Seven),
+            (Suit::Diamond, Number::Seven),
+            (Suit::Diamond, Number::King),
+        ]);
+        let deuces = Wild::numbers(&[Number::Two]);
+        let rank = evaluate_wild(&cards, &deuces);
+        assert_eq!(rank.category(), Category::FourOfAKind);
+        assert_eq!(rank.ranks()[..2], [5, 11]);
+
+        // joker completes the best of a pair or a straight: 5, 6, 7, 8 and 8
+        let mut cards = hand(&[
+            (Suit::Spade, Number::Five),
+            (Suit::Heart, Number::Six),
+            (Suit::Club, Number::Seven),
+            (Suit::Diamond, Number::Eight),
+            (Suit::Spade, Number::Eight),
+        ]);
+        cards.push(Card::joker(0).unwrap());
+        let rank = evaluate(&cards);
+        assert_eq!(rank.category(), Category::Straight);
+        assert_eq!(rank.ranks()[0], 7);
+
+        // a dead joker doesn't count towards the eight
+        let mut cards = eight_cards();
+        cards.insert(0, Card::joker(0).unwrap());
+        assert_eq!(
+            evaluate_wild(&cards, &Wild::none()).category(),
+            Category::HighCard
+        );
+    }
+
+    // 2, 4, 6, 8, T, Q, A, K
+    fn eight_cards() -> Vec<Card> {
+        hand(&[
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Six),
+            (Suit::Diamond, Number::Eight),
+            (Suit::Spade, Number::Ten),
+            (Suit::Heart, Number::Queen),
+            (Suit::Club, Number::Ace),
+            (Suit::Diamond, Number::King),
+        ])
+    }
+
+    #[test]
+    #[should_panic(expected = "more than 8 cards")]
+    fn evaluate_wild_too_many_test() {
+        let mut cards = eight_cards();
+        cards.push(Card::joker(0).unwrap());
+        evaluate(&cards);
+    }
+
+    #[test]
+    #[should_panic(expected = "9 cards, at most 8")]
+    fn evaluate_ranks_too_many_test() {
+        let cards = eight_cards();
+        let mut ranks: Vec<u8> = cards.iter().map(|card| rank(&card.number)).collect();
+        let mut suits: Vec<u8> = cards.iter().map(|card| card.suit.order() as u8).collect();
+        ranks.push(WILD_RANK);
+        suits.push(0);
+        evaluate_ranks(&ranks, &suits);
+    }
+
+    #[test]
+    fn evaluate_mask_test() {
+        use crate::cards::card::all_cards;
+        use crate::cards::id::CardId;
+        use crate::games::poker::enumerate::Combinations;
+
+        // every seven of a wheel-to-royal spread over all suits
+        let cards: Vec<Card> = all_cards()
+            .iter()
+            .filter(|card| [0, 1, 2, 3, 4, 9, 11, 12].contains(&card.number.order()))
+            .cloned()
+            .collect();
+        for idxes in Combinations::new(cards.len(), 7).step_by(7) {
+            let hand: Vec<Card> = idxes.iter().map(|idx| cards[*idx].clone()).collect();
+            let mask: HandMask = hand
+                .iter()
+                .map(|card| CardId::try_from(card).unwrap())
+                .collect();
+            assert_eq!(evaluate_mask(mask), evaluate(&hand), "{:?}", hand);
+        }
+    }
+}
@@ -0,0 +1,249 @@
+// A Texas hold'em hand as imported from a real-world hand history.
+
+use std::fmt;
+
+use crate::cards::card::Card;
+use crate::cards::notation::{parse_cards, ParseError};
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{evaluate, HandRank};
+
+pub const HOLE_NUM: usize = 2;
+pub const BOARD_NUM: usize = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Betting {
+    NoLimit,
+    PotLimit,
+    Limit,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Street {
+    Preflop,
+    Flop,
+    Turn,
+    River,
+}
+
+impl Street {
+    // The board once the street is dealt.
+    pub fn board_num(&self) -> usize {
+        match *self {
+            Street::Preflop => 0,
+            Street::Flop => 3,
+            Street::Turn => 4,
+            Street::River => 5,
+        }
+    }
+
+    pub(super) fn after(board_num: usize) -> Option<Street> {
+        match board_num {
+            3 => Some(Street::Flop),
+            4 => Some(Street::Turn),
+            5 => Some(Street::River),
+            _ => None,
+        }
+    }
+}
+
+// Amounts are in the file's own units, chips or currency.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Action {
+    Post(f64), // blind, straddle or ante
+    Fold,
+    Check,
+    Call(f64),
+    Bet(f64),
+    RaiseTo(f64), // the street total
+    Show,
+    Muck,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Play {
+    pub street: Street,
+    pub seat: usize, // index into `seats`
+    pub action: Action,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Seat {
+    pub number: usize, // as printed at the table
+    pub name: String,
+    pub stack: f64,
+}
+
+pub struct HandHistory {
+    pub id: Option<String>,
+    pub betting: Betting,
+    pub small_blind: f64,
+    pub big_blind: f64,
+    pub seats: Vec<Seat>,
+    pub button: Option<usize>,                     // index into `seats`
+    pub hole_cards: Vec<Option<[Card; HOLE_NUM]>>, // by seat, where known
+    pub board: Vec<Card>,
+    pub plays: Vec<Play>,
+    pub collected: Vec<f64>, // won from the pots, by seat
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ImportError {
+    Syntax { line: usize, message: String },
+    Card { line: usize, error: ParseError },
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ImportError::Syntax { line, message } => write!(f, "line {}: {}", line, message),
+            ImportError::Card { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+pub(super) fn syntax(line: usize, message: String) -> ImportError {
+    ImportError::Syntax { line, message }
+}
+
+// Standard cards only, no jokers in hold'em.
+pub(super) fn import_cards(s: &str, line: usize) -> Result<Vec<Card>, ImportError> {
+    let cards = parse_cards::<Card>(s).map_err(|error| ImportError::Card { line, error })?;
+    if let Some(joker) = cards.iter().find(|card| card.is_joker()) {
+        return Err(ImportError::Card {
+            line,
+            error: ParseError::Number(joker.to_string()),
+        });
+    }
+    Ok(cards)
+}
+
+impl HandHistory {
+    pub(super) fn new(betting: Betting) -> HandHistory {
+        HandHistory {
+            id: None,
+            betting,
+            small_blind: 0.0,
+            big_blind: 0.0,
+            seats: Vec::new(),
+            button: None,
+            hole_cards: Vec::new(),
+            board: Vec::new(),
+            plays: Vec::new(),
+            collected: Vec::new(),
+        }
+    }
+
+    pub(super) fn add_seat(&mut self, seat: Seat) {
+        self.seats.push(seat);
+        self.hole_cards.push(None);
+        self.collected.push(0.0);
+    }
+
+    pub fn seat_idx(&self, name: &str) -> Option<usize> {
+        self.seats.iter().position(|seat| seat.name == name)
+    }
+
+    fn known_cards(&self) -> impl Iterator<Item = &Card> {
+        self.hole_cards
+            .iter()
+            .flatten()
+            .flatten()
+            .chain(self.board.iter())
+    }
+
+    fn check_unseen(&self, cards: &[Card], line: usize) -> Result<(), ImportError> {
+        for (idx, card) in cards.iter().enumerate() {
+            if self
+                .known_cards()
+                .chain(cards[..idx].iter())
+                .any(|known| known.order() == card.order())
+            {
+                return Err(ImportError::Card {
+                    line,
+                    error: ParseError::Duplicate(card.to_string()),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    // Dealt or shown. Showing the cards already dealt is fine.
+    pub(super) fn set_hole_cards(
+        &mut self,
+        seat: usize,
+        cards: Vec<Card>,
+        line: usize,
+    ) -> Result<(), ImportError> {
+        let cards: [Card; HOLE_NUM] =
+            cards
+                .try_into()
+                .map_err(|cards: Vec<Card>| ImportError::Card {
+                    line,
+                    error: ParseError::Count {
+                        expected: HOLE_NUM,
+                        found: cards.len(),
+                    },
+                })?;
+        if let Some(known) = &self.hole_cards[seat] {
+            let mut orders: Vec<u64> = known.iter().map(|card| card.order()).collect();
+            let mut shown: Vec<u64> = cards.iter().map(|card| card.order()).collect();
+            orders.sort();
+            shown.sort();
+            if orders != shown {
+                return Err(syntax(
+                    line,
+                    format!(
+                        "{} shows {} {} but holds {} {}",
+                        self.seats[seat].name, cards[0], cards[1], known[0], known[1]
+                    ),
+                ));
+            }
+            return Ok(());
+        }
+        self.check_unseen(&cards, line)?;
+        self.hole_cards[seat] = Some(cards);
+        Ok(())
+    }
+
+    // Returns the street the new cards open.
+    pub(super) fn deal_board(
+        &mut self,
+        cards: Vec<Card>,
+        line: usize,
+    ) -> Result<Street, ImportError> {
+        self.check_unseen(&cards, line)?;
+        let board_num = self.board.len() + cards.len();
+        let Some(street) = Street::after(board_num) else {
+            return Err(syntax(
+                line,
+                format!("a board of {} cards is not a street", board_num),
+            ));
+        };
+        self.board.extend(cards);
+        Ok(street)
+    }
+
+    pub fn folded(&self, seat: usize) -> bool {
+        self.plays
+            .iter()
+            .any(|play| play.seat == seat && play.action == Action::Fold)
+    }
+
+    // Seats that saw the whole board with their cards known, and what they
+    // made. Empty before the river.
+    pub fn showdown(&self) -> Vec<(usize, HandRank)> {
+        if self.board.len() < BOARD_NUM {
+            return Vec::new();
+        }
+        (0..self.seats.len())
+            .filter(|seat| !self.folded(*seat))
+            .filter_map(|seat| {
+                let hole = self.hole_cards[seat].as_ref()?;
+                let cards: Vec<Card> = hole.iter().chain(self.board.iter()).cloned().collect();
+                Some((seat, evaluate(&cards)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod hand;
+pub mod phh;
+pub mod pokerstars;
@@ -0,0 +1,580 @@
+use super::hand::{
+    import_cards, syntax, Action, Betting, HandHistory, ImportError, Play, Seat, Street,
+};
+
+// The Poker Hand History format is TOML. Only the part of TOML the format
+// uses is read: `key = value` lines with strings, numbers, booleans and
+// arrays of those, arrays spanning lines.
+
+enum Value {
+    Str(String),
+    Num(f64),
+    Bool,                       // no field takes one yet
+    Array(Vec<(usize, Value)>), // with the line of each item
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Value::Str(_) => "a string",
+            Value::Num(_) => "a number",
+            Value::Bool => "a boolean",
+            Value::Array(_) => "an array",
+        }
+    }
+}
+
+struct Reader {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+}
+
+impl Reader {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_comment(&mut self) {
+        if self.peek() == Some('#') {
+            while self.peek().is_some_and(|c| c != '\n') {
+                self.bump();
+            }
+        }
+    }
+
+    // Spaces and a trailing comment, not the end of the line.
+    fn skip_space(&mut self) {
+        while self
+            .peek()
+            .is_some_and(|c| c == ' ' || c == '\t' || c == '\r')
+        {
+            self.bump();
+        }
+        self.skip_comment();
+    }
+
+    fn skip_blank(&mut self) {
+        loop {
+            self.skip_space();
+            if self.peek() != Some('\n') {
+                return;
+            }
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: String) -> ImportError {
+        syntax(self.line, message)
+    }
+
+    fn key(&mut self) -> Result<String, ImportError> {
+        let mut key = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        {
+            key.push(c);
+            self.bump();
+        }
+        if key.is_empty() {
+            return Err(match self.peek() {
+                Some('[') => self.error("tables are not supported, one hand a file".to_string()),
+                c => self.error(format!("expected a key, found {:?}", c)),
+            });
+        }
+        Ok(key)
+    }
+
+    fn value(&mut self) -> Result<Value, ImportError> {
+        match self.peek() {
+            Some('"') => self.string(),
+            Some('[') => self.array(),
+            _ => self.scalar(),
+        }
+    }
+
+    fn string(&mut self) -> Result<Value, ImportError> {
+        let line = self.line;
+        self.bump();
+        let mut s = String::new();
+        loop {
+            match self.bump() {
+                Some('"') => return Ok(Value::Str(s)),
+                Some('\\') => match self.bump() {
+                    Some('"') => s.push('"'),
+                    Some('\\') => s.push('\\'),
+                    Some('n') => s.push('\n'),
+                    Some('t') => s.push('\t'),
+                    c => return Err(self.error(format!("unknown escape {:?}", c))),
+                },
+                Some('\n') | None => return Err(syntax(line, "unterminated string".to_string())),
+                Some(c) => s.push(c),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ImportError> {
+        self.bump();
+        let mut items = Vec::new();
+        loop {
+            self.skip_blank();
+            if self.peek() == Some(']') {
+                self.bump();
+                return Ok(Value::Array(items));
+            }
+            let line = self.line;
+            items.push((line, self.value()?));
+            self.skip_blank();
+            match self.bump() {
+                Some(',') => {}
+                Some(']') => return Ok(Value::Array(items)),
+                c => return Err(self.error(format!("expected ',' or ']', found {:?}", c))),
+            }
+        }
+    }
+
+    fn scalar(&mut self) -> Result<Value, ImportError> {
+        let mut word = String::new();
+        while let Some(c) = self
+            .peek()
+            .filter(|c| c.is_ascii_alphanumeric() || "+-._".contains(*c))
+        {
+            word.push(c);
+            self.bump();
+        }
+        match word.as_str() {
+            "true" | "false" => Ok(Value::Bool),
+            _ => word
+                .replace('_', "")
+                .parse()
+                .map(Value::Num)
+                .map_err(|_| self.error(format!("\"{}\" is not a value", word))),
+        }
+    }
+}
+
+// key -> (line, value)
+fn read_toml(text: &str) -> Result<Vec<(String, usize, Value)>, ImportError> {
+    let mut reader = Reader {
+        chars: text.chars().collect(),
+        pos: 0,
+        line: 1,
+    };
+    let mut fields: Vec<(String, usize, Value)> = Vec::new();
+    loop {
+        reader.skip_blank();
+        if reader.peek().is_none() {
+            return Ok(fields);
+        }
+        let line = reader.line;
+        let key = reader.key()?;
+        if fields.iter().any(|(other, _, _)| *other == key) {
+            return Err(syntax(line, format!("`{}` is given twice", key)));
+        }
+        reader.skip_space();
+        if reader.bump() != Some('=') {
+            return Err(syntax(line, format!("expected `=` after `{}`", key)));
+        }
+        reader.skip_space();
+        let value = reader.value()?;
+        reader.skip_space();
+        if reader.peek().is_some_and(|c| c != '\n') {
+            return Err(reader.error("expected the end of the line".to_string()));
+        }
+        fields.push((key, line, value));
+    }
+}
+
+struct Fields {
+    fields: Vec<(String, usize, Value)>,
+    end: usize, // line past the end, for missing keys
+}
+
+impl Fields {
+    fn get(&self, key: &str) -> Option<(usize, &Value)> {
+        self.fields
+            .iter()
+            .find(|(other, _, _)| other == key)
+            .map(|(_, line, value)| (*line, value))
+    }
+
+    fn require(&self, key: &str) -> Result<(usize, &Value), ImportError> {
+        self.get(key)
+            .ok_or_else(|| syntax(self.end, format!("`{}` is missing", key)))
+    }
+
+    // Items of array `key`, one per player when `len` is given.
+    fn array(
+        &self,
+        key: &str,
+        len: Option<usize>,
+    ) -> Result<Option<&[(usize, Value)]>, ImportError> {
+        let Some((line, value)) = self.get(key) else {
+            return Ok(None);
+        };
+        let Value::Array(items) = value else {
+            return Err(syntax(
+                line,
+                format!("`{}` is {}, not an array", key, value.kind()),
+            ));
+        };
+        if let Some(len) = len.filter(|len| *len != items.len()) {
+            return Err(syntax(
+                line,
+                format!("`{}` has {} items for {} players", key, items.len(), len),
+            ));
+        }
+        Ok(Some(items))
+    }
+
+    fn numbers(&self, key: &str, len: Option<usize>) -> Result<Option<Vec<f64>>, ImportError> {
+        let Some(items) = self.array(key, len)? else {
+            return Ok(None);
+        };
+        items
+            .iter()
+            .map(|(line, item)| match item {
+                Value::Num(num) => Ok(*num),
+                _ => Err(syntax(*line, format!("`{}` takes numbers", key))),
+            })
+            .collect::<Result<Vec<f64>, ImportError>>()
+            .map(Some)
+    }
+}
+
+// "p2" -> 1
+fn parse_player(token: &str, player_num: usize, line: usize) -> Result<usize, ImportError> {
+    token
+        .strip_prefix('p')
+        .and_then(|number| number.parse::<usize>().ok())
+        .filter(|number| (1..=player_num).contains(number))
+        .map(|number| number - 1)
+        .ok_or_else(|| syntax(line, format!("\"{}\" is not a player", token)))
+}
+
+fn parse_amount(token: &str, line: usize) -> Result<f64, ImportError> {
+    token
+        .parse()
+        .map_err(|_| syntax(line, format!("\"{}\" is not an amount", token)))
+}
+
+// Hole cards may be hidden as "????".
+fn is_hidden(cards: &str) -> bool {
+    cards.contains('?')
+}
+
+pub fn parse(text: &str) -> Result<HandHistory, ImportError> {
+    let fields = Fields {
+        fields: read_toml(text)?,
+        end: text.lines().count() + 1,
+    };
+
+    let (line, variant) = fields.require("variant")?;
+    let betting = match variant {
+        Value::Str(variant) if variant == "NT" => Betting::NoLimit,
+        Value::Str(variant) if variant == "FT" => Betting::Limit,
+        _ => {
+            return Err(syntax(
+                line,
+                "only Texas hold'em, NT or FT, is supported".to_string(),
+            ))
+        }
+    };
+    let mut hand = HandHistory::new(betting);
+
+    let Some(stacks) = fields.numbers("starting_stacks", None)? else {
+        return Err(syntax(
+            fields.end,
+            "`starting_stacks` is missing".to_string(),
+        ));
+    };
+    let player_num = stacks.len();
+    let (line, _) = fields.require("starting_stacks")?;
+    if player_num < 2 {
+        return Err(syntax(line, "a hand takes two players or more".to_string()));
+    }
+    let blinds = fields
+        .numbers("blinds_or_straddles", Some(player_num))?
+        .ok_or_else(|| syntax(fields.end, "`blinds_or_straddles` is missing".to_string()))?;
+    let antes = fields
+        .numbers("antes", Some(player_num))?
+        .unwrap_or(vec![0.0; player_num]);
+    let numbers = fields.numbers("seats", Some(player_num))?;
+    let names = match fields.array("players", Some(player_num))? {
+        Some(items) => items
+            .iter()
+            .map(|(line, item)| match item {
+                Value::Str(name) => Ok(name.clone()),
+                _ => Err(syntax(*line, "`players` takes strings".to_string())),
+            })
+            .collect::<Result<Vec<String>, ImportError>>()?,
+        None => (1..=player_num)
+            .map(|number| format!("p{}", number))
+            .collect(),
+    };
+    for (idx, (name, stack)) in names.into_iter().zip(stacks.iter()).enumerate() {
+        hand.add_seat(Seat {
+            number: numbers
+                .as_ref()
+                .map_or(idx + 1, |numbers| numbers[idx] as usize),
+            name,
+            stack: *stack,
+        });
+    }
+    hand.id = match fields.get("hand") {
+        Some((_, Value::Num(id))) => Some(id.to_string()),
+        Some((_, Value::Str(id))) => Some(id.clone()),
+        Some((line, value)) => return Err(syntax(line, format!("`hand` is {}", value.kind()))),
+        None => None,
+    };
+    if let Some(winnings) = fields.numbers("winnings", Some(player_num))? {
+        hand.collected = winnings;
+    }
+
+    // Players run from the small blind round to the button. Heads up the
+    // button posts the small blind.
+    let mut posted: Vec<f64> = blinds
+        .iter()
+        .copied()
+        .filter(|blind| *blind > 0.0)
+        .collect();
+    posted.sort_by(|a, b| a.total_cmp(b));
+    hand.small_blind = posted.first().copied().unwrap_or(0.0);
+    hand.big_blind = posted.get(1).copied().unwrap_or(hand.small_blind);
+    hand.button = Some(if player_num == 2 {
+        (0..2)
+            .min_by(|a, b| blinds[*a].total_cmp(&blinds[*b]))
+            .unwrap()
+    } else {
+        player_num - 1
+    });
+    for (seat, amount) in antes.iter().chain(blinds.iter()).enumerate() {
+        if *amount > 0.0 {
+            hand.plays.push(Play {
+                street: Street::Preflop,
+                seat: seat % player_num,
+                action: Action::Post(*amount),
+            });
+        }
+    }
+
+    // what each player has in on this street, to tell a check from a call
+    let mut street = Street::Preflop;
+    let mut street_bets = blinds.clone();
+    let actions = fields
+        .array("actions", None)?
+        .ok_or_else(|| syntax(fields.end, "`actions` is missing".to_string()))?;
+    for (line, item) in actions.iter() {
+        let line = *line;
+        let Value::Str(action) = item else {
+            return Err(syntax(line, "`actions` takes strings".to_string()));
+        };
+        let action = action.split('#').next().unwrap_or("");
+        let words: Vec<&str> = action.split_whitespace().collect();
+        match words[..] {
+            ["d", "dh", player, cards] => {
+                let seat = parse_player(player, player_num, line)?;
+                if !is_hidden(cards) {
+                    hand.set_hole_cards(seat, import_cards(cards, line)?, line)?;
+                }
+            }
+            ["d", "db", cards] => {
+                street = hand.deal_board(import_cards(cards, line)?, line)?;
+                street_bets = vec![0.0; player_num];
+            }
+            [player, ref rest @ ..] if player != "d" => {
+                let seat = parse_player(player, player_num, line)?;
+                let max = street_bets.iter().copied().fold(0.0, f64::max);
+                let action = match rest {
+                    ["f"] => Action::Fold,
+                    ["cc"] if street_bets[seat] < max => {
+                        let call = max - street_bets[seat];
+                        street_bets[seat] = max;
+                        Action::Call(call)
+                    }
+                    ["cc"] => Action::Check,
+                    ["cbr", amount] => {
+                        let amount = parse_amount(amount, line)?;
+                        street_bets[seat] = amount;
+                        if max > 0.0 {
+                            Action::RaiseTo(amount)
+                        } else {
+                            Action::Bet(amount)
+                        }
+                    }
+                    ["sm"] => Action::Muck,
+                    ["sm", cards] if is_hidden(cards) => Action::Muck,
+                    ["sm", cards] => {
+                        hand.set_hole_cards(seat, import_cards(cards, line)?, line)?;
+                        Action::Show
+                    }
+                    _ => {
+                        return Err(syntax(
+                            line,
+                            format!("unknown action \"{}\"", action.trim()),
+                        ))
+                    }
+                };
+                hand.plays.push(Play {
+                    street,
+                    seat,
+                    action,
+                });
+            }
+            _ => {
+                return Err(syntax(
+                    line,
+                    format!("unknown action \"{}\"", action.trim()),
+                ))
+            }
+        }
+    }
+    Ok(hand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::notation::ParseError;
+
+    const HAND: &str = r#"variant = "NT"
+antes = [0, 0, 0]
+blinds_or_straddles = [5, 10, 0]
+min_bet = 10
+starting_stacks = [1000, 1_000, 1000]
+players = ["alice", "bob", "carol"]
+hand = 42
+actions = [
+  # preflop
+  "d dh p1 7h7d",
+  "d dh p2 ????",
+  "d dh p3 AhKd",
+  "p3 cbr 30",
+  "p1 cc",
+  "p2 f",
+  "d db Ad7c2h",
+  "p1 cc",
+  "p3 cbr 40",
+  "p1 cc",
+  "d db 9s",
+  "p1 cc", "p3 cc",
+  "d db Tc",
+  "p1 cbr 100 # value",
+  "p3 cc",
+  "p1 sm 7h7d",
+  "p3 sm",
+]
+winnings = [340, 0, 0]
+"#;
+
+    #[test]
+    fn phh_parse_test() {
+        let hand = parse(HAND).unwrap();
+        assert_eq!(hand.id.as_deref(), Some("42"));
+        assert_eq!(hand.betting, Betting::NoLimit);
+        assert_eq!((hand.small_blind, hand.big_blind), (5.0, 10.0));
+        assert_eq!(hand.seats[2].name, "carol");
+        assert_eq!(hand.seats[1].stack, 1000.0);
+        assert_eq!(hand.button, Some(2));
+        assert!(hand.hole_cards[1].is_none());
+        assert_eq!(hand.board.len(), 5);
+        assert_eq!(hand.collected, vec![340.0, 0.0, 0.0]);
+
+        let actions: Vec<Action> = hand.plays.iter().map(|play| play.action).collect();
+        assert_eq!(
+            actions,
+            vec![
+                Action::Post(5.0),
+                Action::Post(10.0),
+                Action::RaiseTo(30.0),
+                Action::Call(25.0),
+                Action::Fold,
+                Action::Check,
+                Action::Bet(40.0),
+                Action::Call(40.0),
+                Action::Check,
+                Action::Check,
+                Action::Bet(100.0),
+                Action::Call(100.0),
+                Action::Show,
+                Action::Muck,
+            ]
+        );
+        assert_eq!(hand.plays[8].street, Street::Turn);
+
+        let showdown = hand.showdown();
+        let (winner, _) = showdown.iter().max_by_key(|(_, rank)| *rank).unwrap();
+        assert_eq!(*winner, 0);
+
+        // heads up the button posts the small blind
+        let text = HAND
+            .replace("[0, 0, 0]", "[0, 0]")
+            .replace("[5, 10, 0]", "[10, 5]")
+            .replace("[1000, 1_000, 1000]", "[1000, 1000]")
+            .replace("[340, 0, 0]", "[340, 0]")
+            .replace(", \"carol\"", "")
+            .replace("\"d dh p3 AhKd\",", "");
+        let text: String = text
+            .lines()
+            .filter(|line| !line.contains("p3"))
+            .map(|line| format!("{}\n", line))
+            .collect();
+        let hand = parse(&text).unwrap();
+        assert_eq!(hand.button, Some(1));
+    }
+
+    #[test]
+    fn phh_error_test() {
+        let text = HAND.replace("\"d db 9s\"", "\"d db 9x\"");
+        assert_eq!(
+            parse(&text).err(),
+            Some(ImportError::Card {
+                line: 20,
+                error: ParseError::Suit("9x".to_string())
+            })
+        );
+        let text = HAND.replace("\"p3 cc\",\n  \"p1 sm", "\"p3 dances\",\n  \"p1 sm");
+        assert_eq!(
+            parse(&text).err().unwrap().to_string(),
+            "line 24: unknown action \"p3 dances\""
+        );
+        let text = HAND.replace("\"d db Tc\"", "\"d db Ah\"");
+        assert_eq!(
+            parse(&text).err(),
+            Some(ImportError::Card {
+                line: 22,
+                error: ParseError::Duplicate("Ah".to_string())
+            })
+        );
+        let text = HAND.replace("[5, 10, 0]", "[5, 10]");
+        assert_eq!(
+            parse(&text).err().unwrap().to_string(),
+            "line 3: `blinds_or_straddles` has 2 items for 3 players"
+        );
+        let text = HAND.replace("\"p1 sm 7h7d\"", "\"p1 sm 7h7s\"");
+        assert!(matches!(
+            parse(&text),
+            Err(ImportError::Syntax { line: 25, .. })
+        ));
+        let text = HAND.replace("\"NT\"", "\"PO\"");
+        assert!(matches!(
+            parse(&text),
+            Err(ImportError::Syntax { line: 1, .. })
+        ));
+        let text = HAND.replace("hand = 42", "hand = \"42");
+        assert_eq!(
+            parse(&text).err().unwrap().to_string(),
+            "line 7: unterminated string"
+        );
+    }
+}
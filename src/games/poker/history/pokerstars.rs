@@ -0,0 +1,415 @@
+use super::hand::{
+    import_cards, syntax, Action, Betting, HandHistory, ImportError, Play, Seat, Street,
+};
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+
+// Table talk and comings and goings, nothing to do with the cards.
+static NOISE: [&str; 11] = [
+    " said, \"",
+    " joins the table",
+    " leaves the table",
+    " is disconnected",
+    " is connected",
+    " has timed out",
+    " sits out",
+    " is sitting out",
+    " has returned",
+    " will be allowed to play",
+    " was removed from the table",
+];
+
+fn parse_amount(token: &str, line: usize) -> Result<f64, ImportError> {
+    token
+        .trim_start_matches(['$', '€', '£'])
+        .replace(',', "")
+        .parse()
+        .map_err(|_| syntax(line, format!("\"{}\" is not an amount", token)))
+}
+
+// The cards between the last brackets, as in "[Ad 7c 2h] [9s]".
+fn last_bracket(s: &str) -> Option<&str> {
+    let start = s.rfind('[')?;
+    let end = start + s[start..].find(']')?;
+    Some(&s[start + 1..end])
+}
+
+// "Hold'em No Limit ($0.05/$0.10 USD)" or "Level I (10/20)".
+fn parse_header(hand: &mut HandHistory, header: &str, line: usize) -> Result<(), ImportError> {
+    let id = header
+        .split_once('#')
+        .and_then(|(_, rest)| rest.split(':').next())
+        .filter(|id| !id.is_empty() && id.chars().all(|c| c.is_ascii_digit()))
+        .ok_or_else(|| syntax(line, "no hand number".to_string()))?;
+    hand.id = Some(id.to_string());
+    for group in header.split('(').skip(1) {
+        let Some((small, big)) = group
+            .split(')')
+            .next()
+            .and_then(|group| group.split_once('/'))
+        else {
+            continue;
+        };
+        let big = big.split_whitespace().next().unwrap_or("");
+        if let (Ok(small), Ok(big)) = (parse_amount(small, line), parse_amount(big, line)) {
+            hand.small_blind = small;
+            hand.big_blind = big;
+            return Ok(());
+        }
+    }
+    Err(syntax(line, "no blinds in the header".to_string()))
+}
+
+fn parse_betting(header: &str, line: usize) -> Result<Betting, ImportError> {
+    if !header.contains("Hold'em") {
+        return Err(syntax(line, "only Texas hold'em is supported".to_string()));
+    }
+    if header.contains("No Limit") {
+        Ok(Betting::NoLimit)
+    } else if header.contains("Pot Limit") {
+        Ok(Betting::PotLimit)
+    } else if header.contains("Limit") {
+        Ok(Betting::Limit)
+    } else {
+        Err(syntax(
+            line,
+            "no betting structure in the header".to_string(),
+        ))
+    }
+}
+
+// "Seat 2: bob smith ($12.50 in chips)"
+fn parse_seat(s: &str, line: usize) -> Result<Seat, ImportError> {
+    let bad_seat = || syntax(line, format!("bad seat \"{}\"", s));
+    let (number, rest) = s["Seat ".len()..].split_once(": ").ok_or_else(bad_seat)?;
+    let number = number.parse().map_err(|_| bad_seat())?;
+    let (name, chips) = rest.rsplit_once(" (").ok_or_else(bad_seat)?;
+    let stack = chips.split_whitespace().next().ok_or_else(bad_seat)?;
+    Ok(Seat {
+        number,
+        name: name.to_string(),
+        stack: parse_amount(stack, line)?,
+    })
+}
+
+// The seat whose name opens `s`, the longest name if several do.
+fn find_seat<'a>(hand: &HandHistory, s: &'a str, separator: &str) -> Option<(usize, &'a str)> {
+    (0..hand.seats.len())
+        .filter(|idx| {
+            s.strip_prefix(hand.seats[*idx].name.as_str())
+                .is_some_and(|rest| rest.starts_with(separator))
+        })
+        .max_by_key(|idx| hand.seats[*idx].name.len())
+        .map(|idx| (idx, &s[hand.seats[idx].name.len() + separator.len()..]))
+}
+
+// What follows "name: ". None for anything that is not a play.
+fn parse_action(s: &str, line: usize) -> Result<Option<Action>, ImportError> {
+    let s = s.trim_end_matches(" and is all-in");
+    let words: Vec<&str> = s.split_whitespace().collect();
+    let last = || parse_amount(words[words.len() - 1], line);
+    let action = match words[..] {
+        ["folds", ..] => Action::Fold,
+        ["checks"] => Action::Check,
+        ["calls", amount] => Action::Call(parse_amount(amount, line)?),
+        ["bets", amount] => Action::Bet(parse_amount(amount, line)?),
+        ["raises", _, "to", amount] => Action::RaiseTo(parse_amount(amount, line)?),
+        ["posts", ..] if words.len() > 2 => Action::Post(last()?),
+        ["shows", ..] => Action::Show,
+        ["mucks", "hand"] | ["doesn't", "show", "hand"] => Action::Muck,
+        ["sits", "out"] | ["is", "sitting", "out"] | ["has", "timed", "out"] => return Ok(None),
+        _ => return Err(syntax(line, format!("unknown action \"{}\"", s))),
+    };
+    Ok(Some(action))
+}
+
+fn parse_hand(lines: &[(usize, &str)]) -> Result<HandHistory, ImportError> {
+    let (line, header) = lines[0];
+    let mut hand = HandHistory::new(parse_betting(header, line)?);
+    parse_header(&mut hand, header, line)?;
+
+    let Some((_, table)) = lines
+        .get(1)
+        .copied()
+        .filter(|(_, s)| s.starts_with("Table "))
+    else {
+        return Err(syntax(line + 1, "expected the table line".to_string()));
+    };
+    let button = table
+        .rsplit_once("Seat #")
+        .and_then(|(_, rest)| rest.split_whitespace().next())
+        .and_then(|number| number.parse::<usize>().ok());
+
+    let mut street = Street::Preflop;
+    let mut dealt = false;
+    let mut summary = false;
+    for (line, s) in lines[2..].iter().copied() {
+        if summary {
+            if let Some(board) = s.strip_prefix("Board ") {
+                let cards = import_cards(last_bracket(board).unwrap_or(""), line)?;
+                if !same_cards(&cards, &hand.board) {
+                    return Err(syntax(
+                        line,
+                        "the board differs from the one dealt".to_string(),
+                    ));
+                }
+            }
+            continue;
+        }
+        if let Some(marker) = s.strip_prefix("*** ") {
+            let next = if marker.starts_with("HOLE CARDS ***") {
+                dealt = true;
+                None
+            } else if marker.starts_with("FLOP ***") {
+                Some(Street::Flop)
+            } else if marker.starts_with("TURN ***") {
+                Some(Street::Turn)
+            } else if marker.starts_with("RIVER ***") {
+                Some(Street::River)
+            } else if marker.starts_with("SHOW DOWN ***") {
+                None
+            } else if marker.starts_with("SUMMARY ***") {
+                summary = true;
+                None
+            } else {
+                return Err(syntax(line, format!("unsupported section \"{}\"", s)));
+            };
+            if let Some(next) = next {
+                // "[Ad 7c 2h] [9s]": the first brackets repeat the board so far
+                let Some(new) = last_bracket(s) else {
+                    return Err(syntax(line, "no cards on the street".to_string()));
+                };
+                if s.matches('[').count() > 1 {
+                    let old = s.split_once('[').and_then(|(_, rest)| rest.split_once(']'));
+                    let old = import_cards(old.map_or("", |(old, _)| old), line)?;
+                    if !same_cards(&old, &hand.board) {
+                        return Err(syntax(
+                            line,
+                            "the board differs from the one dealt".to_string(),
+                        ));
+                    }
+                }
+                let cards = import_cards(new, line)?;
+                street = hand.deal_board(cards, line)?;
+                if street != next {
+                    return Err(syntax(line, format!("{} board cards", hand.board.len())));
+                }
+            }
+            continue;
+        }
+        if !dealt && s.starts_with("Seat ") {
+            hand.add_seat(parse_seat(s, line)?);
+            continue;
+        }
+        if let Some(rest) = s.strip_prefix("Dealt to ") {
+            let Some((seat, cards)) = find_seat(&hand, rest, " [") else {
+                return Err(syntax(line, format!("no seat for \"{}\"", rest)));
+            };
+            let cards = import_cards(cards.trim_end_matches(']'), line)?;
+            hand.set_hole_cards(seat, cards, line)?;
+            continue;
+        }
+        if s.starts_with("Uncalled bet ") {
+            continue;
+        }
+        if let Some((seat, rest)) = find_seat(&hand, s, " collected ") {
+            let amount = rest.split_whitespace().next().unwrap_or("");
+            hand.collected[seat] += parse_amount(amount, line)?;
+            continue;
+        }
+        if let Some((seat, rest)) = find_seat(&hand, s, ": ") {
+            let Some(action) = parse_action(rest, line)? else {
+                continue;
+            };
+            if action == Action::Show {
+                let cards = import_cards(last_bracket(rest).unwrap_or(""), line)?;
+                hand.set_hole_cards(seat, cards, line)?;
+            }
+            hand.plays.push(Play {
+                street,
+                seat,
+                action,
+            });
+            continue;
+        }
+        if NOISE.iter().any(|noise| s.contains(noise)) {
+            continue;
+        }
+        return Err(syntax(line, format!("unrecognised line \"{}\"", s)));
+    }
+
+    let (line, _) = lines[lines.len() - 1];
+    if hand.seats.is_empty() || !dealt {
+        return Err(syntax(line, "the hand is cut short".to_string()));
+    }
+    hand.button =
+        button.and_then(|number| hand.seats.iter().position(|seat| seat.number == number));
+    Ok(hand)
+}
+
+fn same_cards(cards: &[Card], other: &[Card]) -> bool {
+    cards.len() == other.len()
+        && cards
+            .iter()
+            .zip(other.iter())
+            .all(|(card, other)| card.order() == other.order())
+}
+
+// Every hand in a PokerStars export, hands being separated by blank lines.
+pub fn parse(text: &str) -> Result<Vec<HandHistory>, ImportError> {
+    let lines: Vec<(usize, &str)> = text
+        .lines()
+        .enumerate()
+        .map(|(idx, s)| (idx + 1, s.trim_start_matches('\u{feff}').trim()))
+        .collect();
+    let mut hands = Vec::new();
+    for block in lines
+        .split(|(_, s)| s.is_empty())
+        .filter(|block| !block.is_empty())
+    {
+        let (line, header) = block[0];
+        if !header.starts_with("PokerStars ") {
+            return Err(syntax(
+                line,
+                format!("expected a PokerStars hand, found \"{}\"", header),
+            ));
+        }
+        hands.push(parse_hand(block)?);
+    }
+    if hands.is_empty() {
+        return Err(syntax(1, "no hands".to_string()));
+    }
+    Ok(hands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::notation::ParseError;
+    use crate::games::poker::high::Category;
+
+    const HAND: &str = "\
+PokerStars Hand #230000000001:  Hold'em No Limit ($0.05/$0.10 USD) - 2021/06/01 20:00:00 ET
+Table 'Aludra III' 6-max Seat #2 is the button
+Seat 1: alice ($10 in chips)
+Seat 2: bob smith ($12.50 in chips)
+Seat 3: carol ($9.80 in chips)
+carol: posts small blind $0.05
+alice: posts big blind $0.10
+*** HOLE CARDS ***
+Dealt to bob smith [Ah Kd]
+bob smith: raises $0.20 to $0.30
+carol: folds
+alice: calls $0.20
+*** FLOP *** [Ad 7c 2h]
+alice: checks
+bob smith: bets $0.40
+alice: calls $0.40
+*** TURN *** [Ad 7c 2h] [9s]
+alice: checks
+carol said, \"nh\"
+bob smith: checks
+*** RIVER *** [Ad 7c 2h 9s] [Tc]
+alice: bets $1
+bob smith: calls $1
+*** SHOW DOWN ***
+alice: shows [7h 7d] (three of a kind, Sevens)
+bob smith: mucks hand
+alice collected $3.29 from pot
+*** SUMMARY ***
+Total pot $3.45 | Rake $0.16
+Board [Ad 7c 2h 9s Tc]
+Seat 1: alice (big blind) showed [7h 7d] and won ($3.29) with three of a kind, Sevens
+Seat 2: bob smith (button) mucked [Ah Kd]
+Seat 3: carol (small blind) folded before Flop
+";
+
+    #[test]
+    fn pokerstars_parse_test() {
+        let hands = parse(HAND).unwrap();
+        assert_eq!(hands.len(), 1);
+        let hand = &hands[0];
+        assert_eq!(hand.id.as_deref(), Some("230000000001"));
+        assert_eq!(hand.betting, Betting::NoLimit);
+        assert_eq!((hand.small_blind, hand.big_blind), (0.05, 0.10));
+        assert_eq!(hand.seats.len(), 3);
+        assert_eq!(hand.seats[1].name, "bob smith");
+        assert_eq!(hand.seats[1].stack, 12.5);
+        assert_eq!(hand.button, Some(1));
+        assert_eq!(hand.board.len(), 5);
+        assert_eq!(hand.plays.len(), 14);
+        assert_eq!(
+            hand.plays[2],
+            Play {
+                street: Street::Preflop,
+                seat: 1,
+                action: Action::RaiseTo(0.30)
+            }
+        );
+        assert_eq!(hand.plays[9].street, Street::Turn);
+        assert_eq!(hand.plays[10].street, Street::River);
+        assert!(hand.folded(2));
+        assert_eq!(hand.collected, vec![3.29, 0.0, 0.0]);
+
+        // the trips beat the mucked aces
+        let showdown = hand.showdown();
+        assert_eq!(showdown.len(), 2);
+        let (winner, rank) = showdown.iter().max_by_key(|(_, rank)| *rank).unwrap();
+        assert_eq!(*winner, 0);
+        assert_eq!(rank.category(), Category::ThreeOfAKind);
+
+        // several hands to a file
+        let text = format!(
+            "{}\n\n\n{}",
+            HAND,
+            HAND.replace("#230000000001", "#230000000002")
+        );
+        let hands = parse(&text).unwrap();
+        assert_eq!(hands[1].id.as_deref(), Some("230000000002"));
+    }
+
+    #[test]
+    fn pokerstars_error_test() {
+        let text = HAND.replace("Dealt to bob smith [Ah Kd]", "Dealt to bob smith [Ah Kx]");
+        assert_eq!(
+            parse(&text).err(),
+            Some(ImportError::Card {
+                line: 9,
+                error: ParseError::Suit("Kx".to_string())
+            })
+        );
+        let text = HAND.replace("alice: checks\nbob", "alice: dances\nbob");
+        assert_eq!(
+            parse(&text).err().unwrap().to_string(),
+            "line 14: unknown action \"dances\""
+        );
+        let text = HAND.replace("[Ad 7c 2h] [9s]", "[Ad 7c 2h] [Ah]");
+        assert_eq!(
+            parse(&text).err(),
+            Some(ImportError::Card {
+                line: 17,
+                error: ParseError::Duplicate("Ah".to_string())
+            })
+        );
+        let text = HAND.replace("[Ad 7c 2h 9s] [Tc]", "[Ad 7c 2h 8s] [Tc]");
+        assert!(matches!(
+            parse(&text),
+            Err(ImportError::Syntax { line: 21, .. })
+        ));
+        let text = HAND.replace("alice: shows [7h 7d]", "alice: shows [7h]");
+        assert!(matches!(
+            parse(&text),
+            Err(ImportError::Card { line: 25, .. })
+        ));
+        let text = HAND.replace("Hold'em No Limit", "Omaha Pot Limit");
+        assert_eq!(
+            parse(&text).err().unwrap().to_string(),
+            "line 1: only Texas hold'em is supported"
+        );
+        let text = HAND.replace("*** SHOW DOWN ***", "Dealer: shuffle");
+        assert!(matches!(
+            parse(&text),
+            Err(ImportError::Syntax { line: 24, .. })
+        ));
+    }
+}
@@ -0,0 +1,323 @@
+use super::enumerate::Combinations;
+use super::high::{evaluate_ranks, rank, Category, HandRank, ACE_RANK};
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+
+const HAND_NUM: usize = 5;
+const RANK_BITS: u32 = 4;
+const CATEGORY_SHIFT: u32 = RANK_BITS * 5;
+const WORST: u32 = (1 << (CATEGORY_SHIFT + RANK_BITS)) - 1;
+// ace to five ranks run from Ace (0) to King (12)
+const EIGHT_RANK: u8 = 7;
+
+// Low hands, packed so that, as with HandRank, the greater value is the
+// better hand: the smaller the cards the greater the rank.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct LowRank(pub u32);
+
+impl LowRank {
+    // Built from how bad a hand is: its pairing, then its ranks from the top.
+    fn new(pairing: u8, ranks: &[u8]) -> LowRank {
+        let mut bad = (pairing as u32) << CATEGORY_SHIFT;
+        for (idx, rank) in ranks.iter().take(HAND_NUM).enumerate() {
+            bad |= (*rank as u32) << (RANK_BITS * (4 - idx as u32));
+        }
+        LowRank(WORST - bad)
+    }
+
+    // Deuce to seven lows are high hands turned upside down.
+    fn from_high(high: HandRank) -> LowRank {
+        LowRank(WORST - high.0)
+    }
+
+    // The ranks that decide ties, highest first. Ace to five ranks put the
+    // ace at 0, deuce to seven ranks are poker ranks.
+    pub fn ranks(&self) -> [u8; 5] {
+        let bad = WORST - self.0;
+        core::array::from_fn(|idx| ((bad >> (RANK_BITS * (4 - idx as u32))) & 0xf) as u8)
+    }
+
+    // No pair at all, a "number" low.
+    pub fn is_unpaired(&self) -> bool {
+        (WORST - self.0) >> CATEGORY_SHIFT == 0
+    }
+}
+
+// Ace to five rank of a card, the ace lowest.
+pub fn ace_to_five_rank(card: &Card) -> u8 {
+    card.number.order() as u8
+}
+
+// Up to five ace to five ranks: no pair, one pair, two pair, trips, full
+// house, quads, each tie broken by the sets first.
+fn ace_to_five_ranks(ranks: &[u8]) -> LowRank {
+    let mut counts = [0u8; 13];
+    for rank in ranks.iter() {
+        counts[*rank as usize] += 1;
+    }
+    let mut sets: Vec<(u8, u8)> = (0..13u8)
+        .filter(|rank| counts[*rank as usize] > 0)
+        .map(|rank| (counts[rank as usize], rank))
+        .collect();
+    sets.sort_by(|set1, set2| set2.cmp(set1));
+    let pairing = match (sets[0].0, sets.get(1).map_or(0, |set| set.0)) {
+        (1, _) => 0,
+        (2, 2) => 2,
+        (2, _) => 1,
+        (3, 2) => 4,
+        (3, _) => 3,
+        _ => 5,
+    };
+    let ranks: Vec<u8> = sets
+        .iter()
+        .flat_map(|(count, rank)| std::iter::repeat_n(*rank, *count as usize))
+        .collect();
+    LowRank::new(pairing, &ranks)
+}
+
+// Best ace to five low out of any number of cards, as in Razz and ace to five
+// lowball. Straights and flushes don't count against it.
+pub fn evaluate_ace_to_five(cards: &[Card]) -> LowRank {
+    if cards.len() <= HAND_NUM {
+        let ranks: Vec<u8> = cards.iter().map(ace_to_five_rank).collect();
+        return ace_to_five_ranks(&ranks);
+    }
+    Combinations::new(cards.len(), HAND_NUM)
+        .map(|idxes| {
+            let ranks: Vec<u8> = idxes
+                .iter()
+                .map(|idx| ace_to_five_rank(&cards[*idx]))
+                .collect();
+            ace_to_five_ranks(&ranks)
+        })
+        .max()
+        .unwrap()
+}
+
+// Deuce to seven: aces are always high, so A-2-3-4-5 is no straight, and
+// straights and flushes count against the hand.
+fn deuce_to_seven_ranks(ranks: &[u8], suits: &[u8]) -> LowRank {
+    let high = evaluate_ranks(ranks, suits);
+    let wheel = matches!(
+        high.category(),
+        Category::Straight | Category::StraightFlush
+    ) && high.ranks()[0] == 3;
+    if !wheel {
+        return LowRank::from_high(high);
+    }
+    let category = if high.category() == Category::Straight {
+        Category::HighCard
+    } else {
+        Category::Flush
+    };
+    LowRank::from_high(HandRank::new(category, &[ACE_RANK, 3, 2, 1, 0]))
+}
+
+// Best deuce to seven low, out of five cards in a draw game.
+pub fn evaluate_deuce_to_seven(cards: &[Card]) -> LowRank {
+    let card_ranks = |idxes: &[usize]| -> (Vec<u8>, Vec<u8>) {
+        idxes
+            .iter()
+            .map(|idx| (rank(&cards[*idx].number), cards[*idx].suit.order() as u8))
+            .unzip()
+    };
+    if cards.len() <= HAND_NUM {
+        let (ranks, suits) = card_ranks(&(0..cards.len()).collect::<Vec<usize>>());
+        return deuce_to_seven_ranks(&ranks, &suits);
+    }
+    Combinations::new(cards.len(), HAND_NUM)
+        .map(|idxes| {
+            let (ranks, suits) = card_ranks(&idxes);
+            deuce_to_seven_ranks(&ranks, &suits)
+        })
+        .max()
+        .unwrap()
+}
+
+// Five unpaired cards eight or lower, or no low at all.
+pub fn evaluate_eight_or_better(cards: &[Card]) -> Option<LowRank> {
+    let mut mask = 0u16;
+    for card in cards.iter() {
+        let rank = ace_to_five_rank(card);
+        if rank <= EIGHT_RANK {
+            mask |= 1 << rank;
+        }
+    }
+    if (mask.count_ones() as usize) < HAND_NUM {
+        return None;
+    }
+    let mut ranks: Vec<u8> = (0..=EIGHT_RANK)
+        .filter(|rank| mask & (1 << rank) != 0)
+        .take(HAND_NUM)
+        .collect();
+    ranks.reverse();
+    Some(LowRank::new(0, &ranks))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::{ALL_SUITS, ALL_SUIT_NUM};
+
+    // suits cycle so that nothing is ever a flush
+    fn hand(numbers: &[Number]) -> Vec<Card> {
+        numbers
+            .iter()
+            .enumerate()
+            .map(|(idx, number)| Card::new(ALL_SUITS[idx % ALL_SUIT_NUM].clone(), number.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn evaluate_ace_to_five_test() {
+        // the wheel is the nuts
+        let wheel = evaluate_ace_to_five(&hand(&[
+            Number::Ace,
+            Number::Two,
+            Number::Three,
+            Number::Four,
+            Number::Five,
+            Number::King,
+            Number::King,
+        ]));
+        assert!(wheel.is_unpaired());
+        assert_eq!(wheel.ranks(), [4, 3, 2, 1, 0]);
+
+        let six_four = evaluate_ace_to_five(&hand(&[
+            Number::Six,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+            Number::Ace,
+        ]));
+        let six_five = evaluate_ace_to_five(&hand(&[
+            Number::Six,
+            Number::Five,
+            Number::Two,
+            Number::Three,
+            Number::Ace,
+        ]));
+        assert!(wheel > six_four);
+        assert!(six_four > six_five);
+
+        // any number low beats a pair
+        let king_low = evaluate_ace_to_five(&hand(&[
+            Number::King,
+            Number::Queen,
+            Number::Jack,
+            Number::Ten,
+            Number::Nine,
+        ]));
+        let pair = evaluate_ace_to_five(&hand(&[
+            Number::Ace,
+            Number::Ace,
+            Number::Two,
+            Number::Three,
+            Number::Four,
+        ]));
+        assert!(!pair.is_unpaired());
+        assert!(king_low > pair);
+
+        // fewer cards, as when comparing up cards
+        let up1 = evaluate_ace_to_five(&hand(&[Number::Seven, Number::Two]));
+        let up2 = evaluate_ace_to_five(&hand(&[Number::Eight, Number::Ace]));
+        assert!(up1 > up2);
+    }
+
+    #[test]
+    fn evaluate_deuce_to_seven_test() {
+        let number_seven = evaluate_deuce_to_seven(&hand(&[
+            Number::Seven,
+            Number::Five,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+        ]));
+        assert!(number_seven.is_unpaired());
+        assert_eq!(number_seven.ranks(), [5, 3, 2, 1, 0]);
+
+        // a straight, a flush and the ace all count against
+        let straight = evaluate_deuce_to_seven(&hand(&[
+            Number::Six,
+            Number::Five,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+        ]));
+        let flush: Vec<Card> = [
+            Number::Seven,
+            Number::Five,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+        ]
+        .iter()
+        .map(|number| Card::new(ALL_SUITS[0].clone(), number.clone()))
+        .collect();
+        let flush = evaluate_deuce_to_seven(&flush);
+        let ace_high = evaluate_deuce_to_seven(&hand(&[
+            Number::Ace,
+            Number::Five,
+            Number::Four,
+            Number::Three,
+            Number::Two,
+        ]));
+        let king_high = evaluate_deuce_to_seven(&hand(&[
+            Number::King,
+            Number::Queen,
+            Number::Jack,
+            Number::Ten,
+            Number::Eight,
+        ]));
+        let pair = evaluate_deuce_to_seven(&hand(&[
+            Number::Two,
+            Number::Two,
+            Number::Four,
+            Number::Three,
+            Number::Five,
+        ]));
+        assert!(number_seven > king_high);
+        assert!(king_high > ace_high);
+        assert!(ace_high.is_unpaired());
+        assert!(ace_high > pair);
+        assert!(pair > straight);
+        assert!(straight > flush);
+    }
+
+    #[test]
+    fn evaluate_eight_or_better_test() {
+        let low = evaluate_eight_or_better(&hand(&[
+            Number::Eight,
+            Number::Seven,
+            Number::Seven,
+            Number::Three,
+            Number::Two,
+            Number::Ace,
+            Number::King,
+        ]));
+        assert_eq!(low.unwrap().ranks(), [7, 6, 2, 1, 0]);
+        let none = evaluate_eight_or_better(&hand(&[
+            Number::Nine,
+            Number::Seven,
+            Number::Seven,
+            Number::Three,
+            Number::Two,
+            Number::Ace,
+            Number::King,
+        ]));
+        assert!(none.is_none());
+        // the same five as an ace to five low
+        let cards = hand(&[
+            Number::Eight,
+            Number::Six,
+            Number::Four,
+            Number::Two,
+            Number::Ace,
+        ]);
+        assert_eq!(
+            evaluate_eight_or_better(&cards),
+            Some(evaluate_ace_to_five(&cards))
+        );
+    }
+}
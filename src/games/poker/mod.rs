@@ -0,0 +1,5 @@
+pub mod badugi;
+pub mod enumerate;
+pub mod high;
+pub mod history;
+pub mod low;
@@ -0,0 +1,397 @@
+use super::rule::{bring_in_key, showing_key, Variant};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+use crate::games::poker::high::evaluate;
+use crate::games::poker::low::{evaluate_ace_to_five, evaluate_eight_or_better};
+
+pub const MAX_PLAYER_NUM: usize = 8;
+pub const FIRST_STREET: usize = 3;
+pub const LAST_STREET: usize = 7;
+
+#[derive(Clone, Default)]
+pub struct Seat {
+    pub down: Vec<Card>,
+    pub up: Vec<Card>,
+    pub folded: bool,
+}
+
+pub struct Result {
+    pub high: Vec<usize>, // winning seats, empty in Razz
+    pub low: Vec<usize>,  // winning seats, empty in Stud or with no qualifying low
+    pub shares: Vec<f64>, // share of the pot per seat
+}
+
+// Seat 0 sits on the dealer's left and gets the first card of every street.
+pub struct Game {
+    pub variant: Variant,
+    pub deck: Deck,
+    pub seats: Vec<Seat>,
+    pub community: Option<Card>, // dealt when the deck can't go round
+    pub street: usize,           // last street dealt, 0 before third street
+    pub results: Vec<Result>,
+    card_idx: usize,
+}
+
+impl Game {
+    pub fn new(variant: Variant, player_num: usize) -> Game {
+        assert!((2..=MAX_PLAYER_NUM).contains(&player_num));
+        Game {
+            variant,
+            deck: Deck::new(),
+            seats: vec![Seat::default(); player_num],
+            community: None,
+            street: 0,
+            results: Vec::new(),
+            card_idx: 0,
+        }
+    }
+
+    // Clears the table, keeping the deck order.
+    pub fn start(&mut self) {
+        for seat in self.seats.iter_mut() {
+            *seat = Seat::default();
+        }
+        self.community = None;
+        self.street = 0;
+        self.card_idx = 0;
+    }
+
+    fn draw(&mut self) -> Card {
+        let card = self.deck.cards[self.card_idx].clone();
+        self.card_idx += 1;
+        card
+    }
+
+    pub fn active_seats(&self) -> Vec<usize> {
+        (0..self.seats.len())
+            .filter(|idx| !self.seats[*idx].folded)
+            .collect()
+    }
+
+    pub fn fold(&mut self, seat: usize) {
+        self.seats[seat].folded = true;
+    }
+
+    // Deals the next street, two down and one up on third street, one up on
+    // fourth to sixth and one down on seventh. Returns false past seventh.
+    pub fn deal_street(&mut self) -> bool {
+        if self.street >= LAST_STREET {
+            return false;
+        }
+        self.street = if self.street == 0 {
+            FIRST_STREET
+        } else {
+            self.street + 1
+        };
+        let active = self.active_seats();
+        if self.street == FIRST_STREET {
+            for _ in 0..2 {
+                for seat in active.iter() {
+                    let card = self.draw();
+                    self.seats[*seat].down.push(card);
+                }
+            }
+        }
+        // not enough cards to go round: one card face up in the middle
+        if self.deck.cards.len() - self.card_idx < active.len() {
+            self.community = Some(self.draw());
+            return true;
+        }
+        for seat in active.iter() {
+            let card = self.draw();
+            if self.street == LAST_STREET {
+                self.seats[*seat].down.push(card);
+            } else {
+                self.seats[*seat].up.push(card);
+            }
+        }
+        true
+    }
+
+    // The forced bet on third street, by door card.
+    pub fn bring_in(&self) -> usize {
+        self.active_seats()
+            .into_iter()
+            .rev()
+            .max_by_key(|seat| bring_in_key(self.variant, &self.seats[*seat].up[0]))
+            .unwrap()
+    }
+
+    // The bring-in on third street, then the best board showing. Ties go to
+    // the seat closest to the dealer's left.
+    pub fn first_to_act(&self) -> usize {
+        if self.street <= FIRST_STREET {
+            return self.bring_in();
+        }
+        self.active_seats()
+            .into_iter()
+            .rev()
+            .max_by_key(|seat| showing_key(self.variant, &self.seats[*seat].up))
+            .unwrap()
+    }
+
+    pub fn cards(&self, seat: usize) -> Vec<Card> {
+        let seat = &self.seats[seat];
+        seat.down
+            .iter()
+            .chain(seat.up.iter())
+            .chain(self.community.iter())
+            .cloned()
+            .collect()
+    }
+
+    fn best_seats<K: Ord>(seats: &[usize], key: impl Fn(usize) -> K) -> Vec<usize> {
+        let best = seats.iter().map(|seat| key(*seat)).max().unwrap();
+        seats
+            .iter()
+            .filter(|seat| key(**seat) == best)
+            .cloned()
+            .collect()
+    }
+
+    fn split(shares: &mut [f64], winners: &[usize], pot: f64) {
+        for seat in winners.iter() {
+            shares[*seat] += pot / winners.len() as f64;
+        }
+    }
+
+    // Settles the pot among the seats still in. High and low split it in
+    // Hi/Lo, and the high hand scoops when nobody has an eight or better.
+    pub fn showdown(&mut self) {
+        let active = self.active_seats();
+        let mut shares = vec![0.0; self.seats.len()];
+        let (high, low) = if active.len() == 1 {
+            (active.clone(), Vec::new())
+        } else {
+            match self.variant {
+                Variant::SevenCardStud => (
+                    Self::best_seats(&active, |seat| evaluate(&self.cards(seat))),
+                    Vec::new(),
+                ),
+                Variant::Razz => (
+                    Vec::new(),
+                    Self::best_seats(&active, |seat| evaluate_ace_to_five(&self.cards(seat))),
+                ),
+                Variant::StudHiLo => {
+                    let high = Self::best_seats(&active, |seat| evaluate(&self.cards(seat)));
+                    let qualified: Vec<usize> = active
+                        .iter()
+                        .filter(|seat| evaluate_eight_or_better(&self.cards(**seat)).is_some())
+                        .cloned()
+                        .collect();
+                    let low = if qualified.is_empty() {
+                        Vec::new()
+                    } else {
+                        Self::best_seats(&qualified, |seat| {
+                            evaluate_eight_or_better(&self.cards(seat))
+                        })
+                    };
+                    (high, low)
+                }
+            }
+        };
+        if high.is_empty() {
+            Self::split(&mut shares, &low, 1.0);
+        } else if low.is_empty() {
+            Self::split(&mut shares, &high, 1.0);
+        } else {
+            Self::split(&mut shares, &high, 0.5);
+            Self::split(&mut shares, &low, 0.5);
+        }
+        self.results.push(Result { high, low, shares });
+    }
+
+    // Plays the current deck order as is, nobody folding.
+    pub fn play_dealt_round(&mut self) {
+        self.start();
+        while self.deal_street() {}
+        self.showdown();
+    }
+
+    pub fn play_one_round(&mut self) {
+        self.deck.shuffle();
+        self.play_dealt_round();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    // Stacks the deck so that seat cards come out as given, third street
+    // first.
+    fn stack(game: &mut Game, seats: &[[(Suit, Number); 7]]) {
+        let mut cards = Vec::new();
+        for street in 0..7 {
+            for seat in seats.iter() {
+                let (suit, number) = seat[street].clone();
+                cards.push(Card::new(suit, number));
+            }
+        }
+        game.deck.cards = cards;
+    }
+
+    fn fillers(num: usize) -> Vec<Card> {
+        (0..num)
+            .map(|idx| Card::new(Suit::Heart, [Number::Three, Number::Four][idx % 2].clone()))
+            .collect()
+    }
+
+    #[test]
+    fn game_bring_in_test() {
+        // door cards 2c, 2s, Ks, Kc
+        let mut game = Game::new(Variant::SevenCardStud, 4);
+        let doors = [
+            (Suit::Club, Number::Two),
+            (Suit::Spade, Number::Two),
+            (Suit::Spade, Number::King),
+            (Suit::Club, Number::King),
+        ];
+        let mut cards = fillers(8);
+        cards.extend(
+            doors
+                .iter()
+                .map(|(suit, number)| Card::new(suit.clone(), number.clone())),
+        );
+        game.deck.cards = cards;
+        game.start();
+        game.deal_street();
+        assert_eq!(game.bring_in(), 0);
+        assert_eq!(game.first_to_act(), 0);
+
+        game.variant = Variant::Razz;
+        assert_eq!(game.bring_in(), 2);
+
+        // the bring-in skips folded seats
+        game.variant = Variant::SevenCardStud;
+        game.fold(0);
+        assert_eq!(game.bring_in(), 1);
+    }
+
+    #[test]
+    fn game_first_to_act_test() {
+        let mut game = Game::new(Variant::SevenCardStud, 3);
+        stack(
+            &mut game,
+            &[
+                [
+                    (Suit::Club, Number::Two),
+                    (Suit::Club, Number::Three),
+                    (Suit::Club, Number::Four),
+                    (Suit::Heart, Number::Four),
+                    (Suit::Club, Number::Five),
+                    (Suit::Club, Number::Six),
+                    (Suit::Club, Number::Seven),
+                ],
+                [
+                    (Suit::Spade, Number::Two),
+                    (Suit::Spade, Number::Three),
+                    (Suit::Spade, Number::Ace),
+                    (Suit::Spade, Number::King),
+                    (Suit::Spade, Number::Four),
+                    (Suit::Spade, Number::Five),
+                    (Suit::Spade, Number::Six),
+                ],
+                [
+                    (Suit::Diamond, Number::Two),
+                    (Suit::Diamond, Number::Three),
+                    (Suit::Diamond, Number::Ace),
+                    (Suit::Heart, Number::King),
+                    (Suit::Diamond, Number::Four),
+                    (Suit::Diamond, Number::Five),
+                    (Suit::Diamond, Number::Six),
+                ],
+            ],
+        );
+        game.start();
+        game.deal_street();
+        assert_eq!(game.street, 3);
+        assert_eq!(game.first_to_act(), 0);
+        // fourth street: pair of fours beats ace-king twice, then seat order
+        game.deal_street();
+        assert_eq!(game.first_to_act(), 0);
+        game.fold(0);
+        assert_eq!(game.first_to_act(), 1);
+    }
+
+    #[test]
+    fn game_showdown_test() {
+        // seat 0: a straight flush, seat 1: a wheel and a flush, seat 2: 6-4 low
+        let seats = [
+            [
+                (Suit::Club, Number::Nine),
+                (Suit::Club, Number::Ten),
+                (Suit::Club, Number::Jack),
+                (Suit::Club, Number::Queen),
+                (Suit::Club, Number::King),
+                (Suit::Heart, Number::King),
+                (Suit::Diamond, Number::King),
+            ],
+            [
+                (Suit::Spade, Number::Ace),
+                (Suit::Spade, Number::Two),
+                (Suit::Spade, Number::Three),
+                (Suit::Spade, Number::Four),
+                (Suit::Heart, Number::Five),
+                (Suit::Spade, Number::Queen),
+                (Suit::Spade, Number::Jack),
+            ],
+            [
+                (Suit::Diamond, Number::Ace),
+                (Suit::Diamond, Number::Two),
+                (Suit::Diamond, Number::Three),
+                (Suit::Diamond, Number::Four),
+                (Suit::Diamond, Number::Six),
+                (Suit::Heart, Number::Queen),
+                (Suit::Heart, Number::Jack),
+            ],
+        ];
+
+        let mut game = Game::new(Variant::SevenCardStud, 3);
+        stack(&mut game, &seats);
+        game.play_dealt_round();
+        let result = game.results.last().unwrap();
+        assert_eq!(result.high, vec![0]);
+        assert_eq!(result.shares, vec![1.0, 0.0, 0.0]);
+
+        let mut game = Game::new(Variant::Razz, 3);
+        stack(&mut game, &seats);
+        game.play_dealt_round();
+        assert_eq!(game.results.last().unwrap().low, vec![1]);
+
+        let mut game = Game::new(Variant::StudHiLo, 3);
+        stack(&mut game, &seats);
+        game.play_dealt_round();
+        let result = game.results.last().unwrap();
+        assert_eq!(result.shares, vec![0.5, 0.5, 0.0]);
+
+        // with seat 0 out, seat 1 has the best flush and the best low
+        game.fold(0);
+        game.showdown();
+        assert_eq!(game.results.last().unwrap().shares, vec![0.0, 1.0, 0.0]);
+    }
+
+    #[test]
+    fn game_community_card_test() {
+        // eight players need 56 cards
+        let mut game = Game::new(Variant::SevenCardStud, MAX_PLAYER_NUM);
+        game.play_dealt_round();
+        assert!(game.community.is_some());
+        for seat in game.seats.iter() {
+            assert_eq!(seat.down.len(), 2);
+            assert_eq!(seat.up.len(), 4);
+        }
+        assert_eq!(game.cards(0).len(), 7);
+
+        // seven players still go round
+        let mut game = Game::new(Variant::Razz, 7);
+        game.play_one_round();
+        assert!(game.community.is_none());
+        assert_eq!(game.cards(6).len(), 7);
+        let shares: f64 = game.results[0].shares.iter().sum();
+        assert_eq!(shares, 1.0);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::cards::card::Card;
+use crate::cards::suit::ALL_SUIT_NUM;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{evaluate_ranks, rank, ACE_RANK};
+use crate::games::poker::low::{ace_to_five_rank, evaluate_ace_to_five};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    SevenCardStud,
+    Razz,
+    StudHiLo, // eight or better
+}
+
+// Suit::order() runs spades first, so clubs are the lowest suit.
+fn suit_strength(card: &Card) -> u8 {
+    (ALL_SUIT_NUM as u64 - 1 - card.suit.order()) as u8
+}
+
+// The door card that brings it in has the greatest key: the lowest card in
+// high games, the highest in Razz, with suits breaking ties either way.
+pub fn bring_in_key(variant: Variant, card: &Card) -> (u8, u8) {
+    match variant {
+        Variant::SevenCardStud | Variant::StudHiLo => {
+            (ACE_RANK - rank(&card.number), 3 - suit_strength(card))
+        }
+        Variant::Razz => (ace_to_five_rank(card), suit_strength(card)),
+    }
+}
+
+// From fourth street on the best showing hand acts first. Only pairs and
+// high cards count on the board, so suits are kept apart.
+pub fn showing_key(variant: Variant, up: &[Card]) -> u32 {
+    match variant {
+        Variant::SevenCardStud | Variant::StudHiLo => {
+            let ranks: Vec<u8> = up.iter().map(|card| rank(&card.number)).collect();
+            let suits: Vec<u8> = (0..up.len() as u8).collect();
+            evaluate_ranks(&ranks, &suits).0
+        }
+        Variant::Razz => evaluate_ace_to_five(up).0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    #[test]
+    fn bring_in_key_test() {
+        let two_clubs = Card::new(Suit::Club, Number::Two);
+        let two_spades = Card::new(Suit::Spade, Number::Two);
+        let ace = Card::new(Suit::Club, Number::Ace);
+        let king_clubs = Card::new(Suit::Club, Number::King);
+        let king_spades = Card::new(Suit::Spade, Number::King);
+
+        let stud = Variant::SevenCardStud;
+        assert!(bring_in_key(stud, &two_clubs) > bring_in_key(stud, &two_spades));
+        assert!(bring_in_key(stud, &two_spades) > bring_in_key(stud, &ace));
+
+        let razz = Variant::Razz;
+        assert!(bring_in_key(razz, &king_spades) > bring_in_key(razz, &king_clubs));
+        assert!(bring_in_key(razz, &two_clubs) > bring_in_key(razz, &ace));
+    }
+
+    #[test]
+    fn showing_key_test() {
+        let pair = [
+            Card::new(Suit::Club, Number::Four),
+            Card::new(Suit::Heart, Number::Four),
+        ];
+        let ace_king = [
+            Card::new(Suit::Club, Number::Ace),
+            Card::new(Suit::Club, Number::King),
+        ];
+        let stud = Variant::SevenCardStud;
+        assert!(showing_key(stud, &pair) > showing_key(stud, &ace_king));
+        let razz = Variant::Razz;
+        assert!(showing_key(razz, &ace_king) > showing_key(razz, &pair));
+    }
+}
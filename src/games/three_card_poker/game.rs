@@ -0,0 +1,107 @@
+use super::hand::Hand;
+use super::rule::{
+    dealer_qualifies, settle_ante_play, should_play, AnteBonusPayTable, PairPlusPayTable,
+};
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+
+pub struct Bets {
+    pub ante: f64,
+    pub pair_plus: f64,
+}
+
+pub struct Result {
+    pub player: Hand,
+    pub dealer: Hand,
+    pub played: bool,
+    pub dealer_qualified: bool,
+    pub ante_play: f64, // net of ante and play
+    pub pair_plus: f64, // net of pair plus
+}
+
+pub struct Game {
+    pub deck: Deck,
+    pub ante_bonus: AnteBonusPayTable,
+    pub pair_plus: PairPlusPayTable,
+    pub results: Vec<Result>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Game {
+        Game {
+            deck: Deck::new(),
+            ante_bonus: AnteBonusPayTable::standard(),
+            pair_plus: PairPlusPayTable::standard(),
+            results: Vec::new(),
+        }
+    }
+
+    // Both hands come off the top of the deck, player first.
+    fn deal(&self) -> (Hand, Hand) {
+        let cards = &self.deck.cards;
+        let player = Hand::new(core::array::from_fn(|idx| cards[idx].clone()));
+        let dealer = Hand::new(core::array::from_fn(|idx| cards[3 + idx].clone()));
+        (player, dealer)
+    }
+
+    // Plays the current deck order as is.
+    pub fn play_dealt_round(&mut self, bets: &Bets) {
+        let (player, dealer) = self.deal();
+        let player_rank = player.rank();
+        let dealer_rank = dealer.rank();
+        let played = should_play(&player_rank);
+        let ante_play =
+            bets.ante * settle_ante_play(&self.ante_bonus, played, &player_rank, &dealer_rank);
+        let pair_plus = bets.pair_plus * self.pair_plus.payout(player_rank.category);
+        self.results.push(Result {
+            player,
+            dealer,
+            played,
+            dealer_qualified: dealer_qualifies(&dealer_rank),
+            ante_play,
+            pair_plus,
+        });
+    }
+
+    pub fn play_one_round(&mut self, bets: &Bets) {
+        self.deck.shuffle();
+        self.play_dealt_round(bets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_test() {
+        let mut game = Game::new();
+        let bets = Bets {
+            ante: 1.0,
+            pair_plus: 1.0,
+        };
+
+        // A, 2, 3 of spades vs 4, 5, 6 of spades
+        game.play_dealt_round(&bets);
+        let result = game.results.last().unwrap();
+        assert!(result.played);
+        assert!(result.dealer_qualified);
+        assert_eq!(result.ante_play, -2.0 + 5.0);
+        assert_eq!(result.pair_plus, 40.0);
+
+        for _ in 0..100 {
+            game.play_one_round(&bets);
+        }
+        assert_eq!(game.results.len(), 101);
+        for result in game.results.iter() {
+            assert!(result.played || result.ante_play == -1.0);
+            assert!(result.pair_plus >= -1.0);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{rank, ACE_RANK};
+
+// With three cards a straight is rarer than a flush, so it ranks higher.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Category {
+    HighCard,
+    Pair,
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    StraightFlush,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct HandRank {
+    pub category: Category,
+    pub ranks: [u8; 3], // most significant first
+}
+
+#[derive(Clone)]
+pub struct Hand {
+    pub cards: [Card; 3],
+}
+
+impl Hand {
+    pub fn new(cards: [Card; 3]) -> Hand {
+        Hand { cards }
+    }
+
+    pub fn rank(&self) -> HandRank {
+        evaluate(&self.cards)
+    }
+}
+
+pub fn evaluate_ranks(ranks: [u8; 3], suits: [u8; 3]) -> HandRank {
+    let mut ranks = ranks;
+    ranks.sort_by(|a, b| b.cmp(a));
+    let flush = suits[0] == suits[1] && suits[1] == suits[2];
+    let straight_top = if ranks[0] == ranks[1] + 1 && ranks[1] == ranks[2] + 1 {
+        Some(ranks[0])
+    } else if ranks == [ACE_RANK, 1, 0] {
+        // A, 2, 3 plays as the lowest straight
+        Some(1)
+    } else {
+        None
+    };
+
+    if let Some(top) = straight_top {
+        let category = if flush {
+            Category::StraightFlush
+        } else {
+            Category::Straight
+        };
+        return HandRank {
+            category,
+            ranks: [top, 0, 0],
+        };
+    }
+    if ranks[0] == ranks[2] {
+        return HandRank {
+            category: Category::ThreeOfAKind,
+            ranks: [ranks[0], 0, 0],
+        };
+    }
+    if flush {
+        return HandRank {
+            category: Category::Flush,
+            ranks,
+        };
+    }
+    if ranks[0] == ranks[1] {
+        return HandRank {
+            category: Category::Pair,
+            ranks: [ranks[0], ranks[2], 0],
+        };
+    }
+    if ranks[1] == ranks[2] {
+        return HandRank {
+            category: Category::Pair,
+            ranks: [ranks[1], ranks[0], 0],
+        };
+    }
+    HandRank {
+        category: Category::HighCard,
+        ranks,
+    }
+}
+
+pub fn evaluate(cards: &[Card; 3]) -> HandRank {
+    evaluate_ranks(
+        core::array::from_fn(|idx| rank(&cards[idx].number)),
+        core::array::from_fn(|idx| cards[idx].suit.order() as u8),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: [(Suit, Number); 3]) -> Hand {
+        Hand::new(cards.map(|(suit, number)| Card::new(suit, number)))
+    }
+
+    #[test]
+    fn hand_category_test() {
+        // A, 2, 3 offsuit
+        let rank = hand([
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Club, Number::Three),
+        ])
+        .rank();
+        assert_eq!(rank.category, Category::Straight);
+
+        // K, A, 2 is not a straight
+        let rank = hand([
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Two),
+        ])
+        .rank();
+        assert_eq!(rank.category, Category::HighCard);
+
+        // 9, T, J suited
+        let rank = hand([
+            (Suit::Heart, Number::Nine),
+            (Suit::Heart, Number::Ten),
+            (Suit::Heart, Number::Jack),
+        ])
+        .rank();
+        assert_eq!(rank.category, Category::StraightFlush);
+
+        // 5, 5, K
+        let rank = hand([
+            (Suit::Heart, Number::Five),
+            (Suit::Club, Number::King),
+            (Suit::Spade, Number::Five),
+        ])
+        .rank();
+        assert_eq!(rank.category, Category::Pair);
+        assert_eq!(rank.ranks, [3, 11, 0]);
+    }
+
+    #[test]
+    fn hand_compare_test() {
+        // straight beats flush
+        let straight = hand([
+            (Suit::Spade, Number::Four),
+            (Suit::Heart, Number::Five),
+            (Suit::Club, Number::Six),
+        ]);
+        let flush = hand([
+            (Suit::Diamond, Number::Ace),
+            (Suit::Diamond, Number::King),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert!(straight.rank() > flush.rank());
+
+        // A, 2, 3 is below 2, 3, 4
+        let wheel = hand([
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Two),
+            (Suit::Club, Number::Three),
+        ]);
+        let low = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Three),
+            (Suit::Club, Number::Four),
+        ]);
+        assert!(wheel.rank() < low.rank());
+
+        // pair kicker decides
+        let hand1 = hand([
+            (Suit::Spade, Number::Eight),
+            (Suit::Heart, Number::Eight),
+            (Suit::Club, Number::Queen),
+        ]);
+        let hand2 = hand([
+            (Suit::Diamond, Number::Eight),
+            (Suit::Club, Number::Eight),
+            (Suit::Heart, Number::Jack),
+        ]);
+        assert!(hand1.rank() > hand2.rank());
+    }
+}
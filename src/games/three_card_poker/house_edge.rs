@@ -0,0 +1,94 @@
+use super::hand::{evaluate, HandRank};
+use super::rule::{settle_ante_play, should_play, AnteBonusPayTable, PairPlusPayTable};
+use crate::cards::card::{all_cards, Card, ALL_CARDS_NUM};
+use crate::cards::traits::order::Order;
+use crate::games::poker::enumerate::{suit_classes, HouseEdge};
+
+fn card_id(card: &Card) -> usize {
+    card.order() as usize
+}
+
+// Ranks of every three-card hand, indexed by ascending card ids.
+fn rank_table() -> Vec<Option<HandRank>> {
+    let cards = all_cards();
+    let mut table = vec![None; ALL_CARDS_NUM * ALL_CARDS_NUM * ALL_CARDS_NUM];
+    for a in 0..ALL_CARDS_NUM {
+        for b in (a + 1)..ALL_CARDS_NUM {
+            for c in (b + 1)..ALL_CARDS_NUM {
+                let hand = [cards[a].clone(), cards[b].clone(), cards[c].clone()];
+                table[(a * ALL_CARDS_NUM + b) * ALL_CARDS_NUM + c] = Some(evaluate(&hand));
+            }
+        }
+    }
+    table
+}
+
+// Enumerates every player hand against every dealer hand from a single deck,
+// with the player raising on Q, 6, 4 or better.
+pub fn ante_play(ante_bonus: &AnteBonusPayTable) -> HouseEdge {
+    let table = rank_table();
+    let mut combinations = 0;
+    let mut total = 0.0;
+    for (player, weight) in suit_classes(all_cards(), 3) {
+        let player: [Card; 3] = core::array::from_fn(|idx| player[idx].clone());
+        let player_rank = evaluate(&player);
+        let player_ids: Vec<usize> = player.iter().map(card_id).collect();
+        let rest: Vec<usize> = (0..ALL_CARDS_NUM)
+            .filter(|id| !player_ids.contains(id))
+            .collect();
+        let played = should_play(&player_rank);
+        let mut dealer_num = 0;
+        let mut sum = 0.0;
+        for (i, a) in rest.iter().enumerate() {
+            for (j, b) in rest.iter().enumerate().skip(i + 1) {
+                for c in rest.iter().skip(j + 1) {
+                    let dealer_rank = table[(a * ALL_CARDS_NUM + b) * ALL_CARDS_NUM + c].unwrap();
+                    sum += settle_ante_play(ante_bonus, played, &player_rank, &dealer_rank);
+                    dealer_num += 1;
+                }
+            }
+        }
+        combinations += weight * dealer_num;
+        total += (weight as f64) * sum;
+    }
+    HouseEdge {
+        combinations,
+        expected_return: total / (combinations as f64),
+    }
+}
+
+pub fn pair_plus(pay_table: &PairPlusPayTable) -> HouseEdge {
+    let mut combinations = 0;
+    let mut total = 0.0;
+    for (player, weight) in suit_classes(all_cards(), 3) {
+        let player: [Card; 3] = core::array::from_fn(|idx| player[idx].clone());
+        combinations += weight;
+        total += (weight as f64) * pay_table.payout(evaluate(&player).category);
+    }
+    HouseEdge {
+        combinations,
+        expected_return: total / (combinations as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pair_plus_test() {
+        let edge = pair_plus(&PairPlusPayTable::standard());
+        assert_eq!(edge.combinations, 22100);
+        assert!((edge.house_edge() - 0.0232).abs() < 0.0001);
+
+        let edge = pair_plus(&PairPlusPayTable::reduced_flush());
+        assert!((edge.house_edge() - 0.0728).abs() < 0.0001);
+    }
+
+    #[test]
+    fn ante_play_test() {
+        let edge = ante_play(&AnteBonusPayTable::standard());
+        assert_eq!(edge.combinations, 22100 * 18424);
+        assert!((edge.house_edge() - 0.0337).abs() < 0.0001);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod game;
+pub mod hand;
+pub mod house_edge;
+pub mod rule;
@@ -0,0 +1,163 @@
+use super::hand::{Category, HandRank};
+
+// Dealer needs queen high or better to open.
+pub const DEALER_QUALIFY: HandRank = HandRank {
+    category: Category::HighCard,
+    ranks: [10, 0, 0],
+};
+
+// Optimal strategy: raise with Q, 6, 4 or better, fold the rest.
+pub const PLAY_THRESHOLD: HandRank = HandRank {
+    category: Category::HighCard,
+    ranks: [10, 4, 2],
+};
+
+pub fn dealer_qualifies(dealer: &HandRank) -> bool {
+    *dealer >= DEALER_QUALIFY
+}
+
+pub fn should_play(player: &HandRank) -> bool {
+    *player >= PLAY_THRESHOLD
+}
+
+// Paid on the ante whenever the player raises, win or lose.
+#[derive(Clone, Debug)]
+pub struct AnteBonusPayTable {
+    pub straight: f64,
+    pub three_of_a_kind: f64,
+    pub straight_flush: f64,
+}
+
+impl AnteBonusPayTable {
+    pub fn standard() -> AnteBonusPayTable {
+        AnteBonusPayTable {
+            straight: 1.0,
+            three_of_a_kind: 4.0,
+            straight_flush: 5.0,
+        }
+    }
+
+    pub fn payout(&self, category: Category) -> f64 {
+        match category {
+            Category::Straight => self.straight,
+            Category::ThreeOfAKind => self.three_of_a_kind,
+            Category::StraightFlush => self.straight_flush,
+            _ => 0.0,
+        }
+    }
+}
+
+// Pair Plus pays on the player's own hand, to one.
+#[derive(Clone, Debug)]
+pub struct PairPlusPayTable {
+    pub pair: f64,
+    pub flush: f64,
+    pub straight: f64,
+    pub three_of_a_kind: f64,
+    pub straight_flush: f64,
+}
+
+impl PairPlusPayTable {
+    // 1-4-6-30-40
+    pub fn standard() -> PairPlusPayTable {
+        PairPlusPayTable {
+            pair: 1.0,
+            flush: 4.0,
+            straight: 6.0,
+            three_of_a_kind: 30.0,
+            straight_flush: 40.0,
+        }
+    }
+
+    // 1-3-6-30-40
+    pub fn reduced_flush() -> PairPlusPayTable {
+        PairPlusPayTable {
+            flush: 3.0,
+            ..PairPlusPayTable::standard()
+        }
+    }
+
+    // Net result per unit bet.
+    pub fn payout(&self, category: Category) -> f64 {
+        match category {
+            Category::HighCard => -1.0,
+            Category::Pair => self.pair,
+            Category::Flush => self.flush,
+            Category::Straight => self.straight,
+            Category::ThreeOfAKind => self.three_of_a_kind,
+            Category::StraightFlush => self.straight_flush,
+        }
+    }
+}
+
+// Net result of the ante and play bets per unit ante. The play bet equals the
+// ante.
+pub fn settle_ante_play(
+    ante_bonus: &AnteBonusPayTable,
+    played: bool,
+    player: &HandRank,
+    dealer: &HandRank,
+) -> f64 {
+    if !played {
+        return -1.0;
+    }
+    let bonus = ante_bonus.payout(player.category);
+    if !dealer_qualifies(dealer) {
+        // ante wins, play pushes
+        return 1.0 + bonus;
+    }
+    if player > dealer {
+        2.0 + bonus
+    } else if player < dealer {
+        -2.0 + bonus
+    } else {
+        bonus
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_threshold_test() {
+        let q64 = HandRank {
+            category: Category::HighCard,
+            ranks: [10, 4, 2],
+        };
+        let q63 = HandRank {
+            category: Category::HighCard,
+            ranks: [10, 4, 1],
+        };
+        let j98 = HandRank {
+            category: Category::HighCard,
+            ranks: [9, 7, 6],
+        };
+        assert!(should_play(&q64));
+        assert!(!should_play(&q63));
+        assert!(dealer_qualifies(&q63));
+        assert!(!dealer_qualifies(&j98));
+    }
+
+    #[test]
+    fn rule_settle_test() {
+        let table = AnteBonusPayTable::standard();
+        let pair = HandRank {
+            category: Category::Pair,
+            ranks: [5, 3, 0],
+        };
+        let straight = HandRank {
+            category: Category::Straight,
+            ranks: [8, 0, 0],
+        };
+        let j98 = HandRank {
+            category: Category::HighCard,
+            ranks: [9, 7, 6],
+        };
+        assert_eq!(settle_ante_play(&table, false, &straight, &pair), -1.0);
+        assert_eq!(settle_ante_play(&table, true, &pair, &j98), 1.0);
+        assert_eq!(settle_ante_play(&table, true, &straight, &pair), 3.0);
+        assert_eq!(settle_ante_play(&table, true, &pair, &straight), -2.0);
+        assert_eq!(settle_ante_play(&table, true, &pair, &pair), 0.0);
+    }
+}
@@ -0,0 +1,222 @@
+use super::rule::{
+    dealer_qualifies, settle, BasicStrategy, BlindPayTable, Showdown, Strategy, TripsPayTable, FOLD,
+};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+use crate::games::poker::high::{evaluate, HandRank};
+
+const HOLE_NUM: usize = 2;
+const BOARD_NUM: usize = 5;
+// player hole, dealer hole, then the board
+const BOARD_POS: usize = HOLE_NUM * 2;
+const DEALT_NUM: usize = BOARD_POS + BOARD_NUM;
+
+pub struct Bets {
+    pub ante: f64, // the blind is always equal to the ante
+    pub trips: f64,
+}
+
+pub struct Result {
+    pub player: [Card; 2],
+    pub dealer: [Card; 2],
+    pub board: [Card; 5],
+    pub play: f64, // play bet in antes, zero on a fold
+    pub dealer_qualified: bool,
+    pub ante_blind_play: f64, // net of ante, blind and play
+    pub trips: f64,           // net of trips
+}
+
+pub struct Game<S: Strategy = BasicStrategy> {
+    pub deck: Deck,
+    pub blind: BlindPayTable,
+    pub trips: TripsPayTable,
+    pub strategy: S,
+    pub results: Vec<Result>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    pub fn new() -> Game {
+        Game::with_strategy(BasicStrategy)
+    }
+}
+
+impl<S: Strategy> Game<S> {
+    pub fn with_strategy(strategy: S) -> Game<S> {
+        Game {
+            deck: Deck::new(),
+            blind: BlindPayTable::standard(),
+            trips: TripsPayTable::standard(),
+            strategy,
+            results: Vec::new(),
+        }
+    }
+
+    // Every dealer hand the player cannot rule out at the river.
+    fn river_showdown(&self, player: &HandRank, board: &[Card; 5]) -> Showdown {
+        let cards = &self.deck.cards;
+        let unseen: Vec<&Card> = cards[HOLE_NUM..BOARD_POS]
+            .iter()
+            .chain(cards[DEALT_NUM..].iter())
+            .collect();
+        let mut showdown = Showdown::default();
+        for (idx, first) in unseen.iter().enumerate() {
+            for second in unseen.iter().skip(idx + 1) {
+                let dealer = [
+                    (*first).clone(),
+                    (*second).clone(),
+                    board[0].clone(),
+                    board[1].clone(),
+                    board[2].clone(),
+                    board[3].clone(),
+                    board[4].clone(),
+                ];
+                showdown.add(player, &evaluate(&dealer));
+            }
+        }
+        showdown
+    }
+
+    // Plays the current deck order as is.
+    pub fn play_dealt_round(&mut self, bets: &Bets) {
+        let cards = &self.deck.cards;
+        let player: [Card; 2] = core::array::from_fn(|idx| cards[idx].clone());
+        let dealer: [Card; 2] = core::array::from_fn(|idx| cards[HOLE_NUM + idx].clone());
+        let board: [Card; 5] = core::array::from_fn(|idx| cards[BOARD_POS + idx].clone());
+        let player_rank = evaluate(&[player.as_slice(), board.as_slice()].concat());
+        let dealer_rank = evaluate(&[dealer.as_slice(), board.as_slice()].concat());
+
+        let play = if self.strategy.preflop(&player) {
+            4.0
+        } else if self.strategy.flop(&player, &board[..3]) {
+            2.0
+        } else {
+            let showdown = self.river_showdown(&player_rank, &board);
+            if self.strategy.river(&self.blind, &player_rank, &showdown) {
+                1.0
+            } else {
+                0.0
+            }
+        };
+
+        let ante_blind_play = if play == 0.0 {
+            bets.ante * FOLD
+        } else {
+            bets.ante * settle(&self.blind, play, &player_rank, &dealer_rank)
+        };
+        // trips stands on the player's hand, folded or not
+        let trips = bets.trips * self.trips.payout(&player_rank);
+        self.results.push(Result {
+            player,
+            dealer,
+            board,
+            play,
+            dealer_qualified: dealer_qualifies(&dealer_rank),
+            ante_blind_play,
+            trips,
+        });
+    }
+
+    pub fn play_one_round(&mut self, bets: &Bets) {
+        self.deck.shuffle();
+        self.play_dealt_round(bets);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::all_cards;
+    use crate::games::poker::enumerate::Combinations;
+    use crate::games::poker::high::rank;
+    use crate::games::ultimate_holdem::house_edge::trips;
+
+    #[test]
+    fn game_test() {
+        let mut game = Game::new();
+        let bets = Bets {
+            ante: 1.0,
+            trips: 1.0,
+        };
+
+        // A, 2 vs 3, 4 on 5, 6, 7, 8, 9 of spades: both play the board
+        game.play_dealt_round(&bets);
+        let result = game.results.last().unwrap();
+        assert_eq!(result.play, 4.0);
+        assert!(result.dealer_qualified);
+        assert_eq!(result.ante_blind_play, 0.0);
+        assert_eq!(result.trips, 40.0);
+
+        for _ in 0..100 {
+            game.play_one_round(&bets);
+        }
+        assert_eq!(game.results.len(), 101);
+        for result in game.results.iter() {
+            if result.play == 0.0 {
+                assert_eq!(result.ante_blind_play, -2.0);
+            }
+        }
+    }
+
+    struct Fold;
+
+    impl Strategy for Fold {
+        fn preflop(&self, _hole: &[Card; 2]) -> bool {
+            false
+        }
+
+        fn flop(&self, _hole: &[Card; 2], _flop: &[Card]) -> bool {
+            false
+        }
+
+        fn river(&self, _blind: &BlindPayTable, _player: &HandRank, _showdown: &Showdown) -> bool {
+            false
+        }
+    }
+
+    // Average trips over every player seven of `deck`, each dealt once.
+    fn trips_return<S: Strategy>(game: &mut Game<S>, deck: &[Card]) -> f64 {
+        let bets = Bets {
+            ante: 1.0,
+            trips: 1.0,
+        };
+        let mut total = 0.0;
+        let mut combinations = 0;
+        for idxes in Combinations::new(deck.len(), HOLE_NUM + BOARD_NUM) {
+            let mut rest = (0..deck.len()).filter(|idx| !idxes.contains(idx));
+            // player hole, two others for the dealer, the board, the rest
+            let mut order = idxes[..HOLE_NUM].to_vec();
+            order.extend(rest.by_ref().take(HOLE_NUM));
+            order.extend(idxes[HOLE_NUM..].iter().copied());
+            order.extend(rest);
+            game.deck = Deck::from_cards(order.iter().map(|idx| deck[*idx].clone()).collect());
+            game.play_dealt_round(&bets);
+            total += game.results.last().unwrap().trips;
+            combinations += 1;
+        }
+        total / (combinations as f64)
+    }
+
+    #[test]
+    fn game_trips_edge_test() {
+        // queens, kings and aces of every suit
+        let deck: Vec<Card> = all_cards()
+            .iter()
+            .filter(|card| rank(&card.number) >= 10)
+            .cloned()
+            .collect();
+        let edge = trips(&deck, &TripsPayTable::standard());
+        let folded = trips_return(&mut Game::with_strategy(Fold), &deck);
+        assert!((folded - edge.expected_return).abs() < 1e-9);
+        let mut game = Game::new();
+        let played = trips_return(&mut game, &deck);
+        assert!((played - edge.expected_return).abs() < 1e-9);
+        assert!(game.results.iter().any(|result| result.play > 0.0));
+    }
+}
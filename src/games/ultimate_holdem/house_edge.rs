@@ -0,0 +1,297 @@
+use super::rule::{expected, BlindPayTable, Showdown, Strategy, TripsPayTable, FOLD};
+use crate::cards::card::Card;
+use crate::cards::id::{CardId, HandMask};
+use crate::games::poker::enumerate::{suit_classes, Combinations, HouseEdge};
+use crate::games::poker::high::{evaluate_mask, HandRank};
+
+const BOARD_NUM: usize = 5;
+const FLOP_NUM: usize = 3;
+
+// Every seven-card hand made of one board and the two-card holes drawn from the
+// cards left over, with the dealer distribution shared by all holes.
+struct BoardHands {
+    board: Vec<Card>,
+    rest: Vec<Card>,
+    ranks: Vec<HandRank>, // ranks[i * rest.len() + j] for hole (i, j), i < j
+    sorted: Vec<HandRank>,
+}
+
+impl BoardHands {
+    fn new(deck: &[Card], board: Vec<Card>) -> BoardHands {
+        let id = |card: &Card| CardId::try_from(card).expect("hold'em has no jokers");
+        let board_mask: HandMask = board.iter().map(id).collect();
+        let rest: Vec<Card> = deck
+            .iter()
+            .filter(|card| !board_mask.contains(id(card)))
+            .cloned()
+            .collect();
+        let rest_masks: Vec<HandMask> = rest.iter().map(|card| id(card).into()).collect();
+        let rest_num = rest.len();
+        let mut ranks = vec![HandRank(0); rest_num * rest_num];
+        let mut sorted = Vec::with_capacity(rest_num * (rest_num - 1) / 2);
+        for i in 0..rest_num {
+            let with_i = board_mask | rest_masks[i];
+            for j in (i + 1)..rest_num {
+                let hand_rank = evaluate_mask(with_i | rest_masks[j]);
+                ranks[i * rest_num + j] = hand_rank;
+                sorted.push(hand_rank);
+            }
+        }
+        sorted.sort();
+        BoardHands {
+            board,
+            rest,
+            ranks,
+            sorted,
+        }
+    }
+
+    fn rank(&self, i: usize, j: usize) -> HandRank {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        self.ranks[i * self.rest.len() + j]
+    }
+
+    fn below(&self, hand_rank: &HandRank) -> u64 {
+        self.sorted.partition_point(|other| other < hand_rank) as u64
+    }
+
+    // Dealer holes that share no card with the player's hole (i, j).
+    fn showdown(&self, i: usize, j: usize) -> Showdown {
+        let player = self.rank(i, j);
+        let qualify = super::rule::DEALER_QUALIFY;
+        let above_player = HandRank(player.0 + 1);
+        let all = self.sorted.len() as u64;
+        let below_player = self.below(&player);
+        let not_above_player = self.below(&above_player);
+        let below_qualify = self.below(&qualify);
+        let mut showdown = Showdown {
+            win_qualified: below_player.saturating_sub(below_qualify),
+            win_unqualified: below_player.min(below_qualify),
+            tie: not_above_player - below_player,
+            lose_qualified: all - not_above_player.max(below_qualify),
+            lose_unqualified: below_qualify.saturating_sub(not_above_player),
+        };
+
+        // take back the holes that use one of the player's cards
+        let mut overlap = Showdown::default();
+        for other in 0..self.rest.len() {
+            if other != i {
+                overlap.add(&player, &self.rank(i, other));
+            }
+            if other != i && other != j {
+                overlap.add(&player, &self.rank(j, other));
+            }
+        }
+        showdown.win_qualified -= overlap.win_qualified;
+        showdown.win_unqualified -= overlap.win_unqualified;
+        showdown.tie -= overlap.tie;
+        showdown.lose_qualified -= overlap.lose_qualified;
+        showdown.lose_unqualified -= overlap.lose_unqualified;
+        showdown
+    }
+}
+
+// Expected net of ante, blind and play for one hole on one board, averaged
+// over which three board cards came on the flop.
+fn hole_value<S: Strategy>(
+    hands: &BoardHands,
+    i: usize,
+    j: usize,
+    blind: &BlindPayTable,
+    strategy: &S,
+) -> f64 {
+    let hole = [hands.rest[i].clone(), hands.rest[j].clone()];
+    let player = hands.rank(i, j);
+    let showdown = hands.showdown(i, j);
+    if strategy.preflop(&hole) {
+        return expected(blind, 4.0, &player, &showdown);
+    }
+    let river = if strategy.river(blind, &player, &showdown) {
+        expected(blind, 1.0, &player, &showdown)
+    } else {
+        FOLD
+    };
+    let mut flop_raise = None;
+    let mut total = 0.0;
+    let mut splits = 0;
+    for idxes in Combinations::new(BOARD_NUM, FLOP_NUM) {
+        let flop: Vec<Card> = idxes.iter().map(|idx| hands.board[*idx].clone()).collect();
+        total += if strategy.flop(&hole, &flop) {
+            *flop_raise.get_or_insert_with(|| expected(blind, 2.0, &player, &showdown))
+        } else {
+            river
+        };
+        splits += 1;
+    }
+    total / (splits as f64)
+}
+
+// Enumerates every player hole, board and dealer hole from `deck`, which has
+// to be closed under suit permutation (a full deck, or whole ranks of one).
+// Boards are reduced by suit isomorphism and the dealer holes are counted off
+// one sorted rank list per board, so a full deck costs one seven-card
+// evaluation per board and hole (a few minutes in release). Result is per
+// unit ante.
+pub fn ante_blind_play<S: Strategy>(
+    deck: &[Card],
+    blind: &BlindPayTable,
+    strategy: &S,
+) -> HouseEdge {
+    let mut combinations = 0;
+    let mut total = 0.0;
+    for (board, weight) in suit_classes(deck, BOARD_NUM) {
+        let hands = BoardHands::new(deck, board);
+        let rest_num = hands.rest.len();
+        let dealer_num = ((rest_num - 2) * (rest_num - 3) / 2) as u64;
+        for i in 0..rest_num {
+            for j in (i + 1)..rest_num {
+                combinations += weight * dealer_num;
+                total += (weight * dealer_num) as f64 * hole_value(&hands, i, j, blind, strategy);
+            }
+        }
+    }
+    HouseEdge {
+        combinations,
+        expected_return: total / (combinations as f64),
+    }
+}
+
+// Trips resolved on every final player hand, which it is whether the player
+// folds or not.
+pub fn trips(deck: &[Card], pay_table: &TripsPayTable) -> HouseEdge {
+    let mut combinations = 0;
+    let mut total = 0.0;
+    for (board, weight) in suit_classes(deck, BOARD_NUM) {
+        let hands = BoardHands::new(deck, board);
+        let rest_num = hands.rest.len();
+        for i in 0..rest_num {
+            for j in (i + 1)..rest_num {
+                combinations += weight;
+                total += (weight as f64) * pay_table.payout(&hands.rank(i, j));
+            }
+        }
+    }
+    HouseEdge {
+        combinations,
+        expected_return: total / (combinations as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::all_cards;
+    use crate::cards::number::Number;
+    use crate::cards::traits::value::Value;
+    use crate::games::poker::high::evaluate;
+    use crate::games::ultimate_holdem::rule::{settle, BasicStrategy};
+
+    // queens, kings and aces of every suit
+    fn small_deck() -> Vec<Card> {
+        all_cards()
+            .iter()
+            .filter(|card| card.number.value() == Number::Ace.value() || card.number.value() >= 11)
+            .cloned()
+            .collect()
+    }
+
+    fn pick(cards: &[Card], idxes: &[usize]) -> Vec<Card> {
+        idxes.iter().map(|idx| cards[*idx].clone()).collect()
+    }
+
+    fn without(cards: &[Card], idxes: &[usize]) -> Vec<Card> {
+        cards
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !idxes.contains(idx))
+            .map(|(_, card)| card.clone())
+            .collect()
+    }
+
+    #[test]
+    fn ante_blind_play_test() {
+        let deck = small_deck();
+        let blind = BlindPayTable::standard();
+        let strategy = BasicStrategy;
+
+        // deal by deal, without any of the shortcuts
+        let mut combinations = 0u64;
+        let mut total = 0.0;
+        for hole_idxes in Combinations::new(deck.len(), 2) {
+            let hole = pick(&deck, &hole_idxes);
+            let hole: [Card; 2] = [hole[0].clone(), hole[1].clone()];
+            let after_hole = without(&deck, &hole_idxes);
+            for board_idxes in Combinations::new(after_hole.len(), BOARD_NUM) {
+                let board = pick(&after_hole, &board_idxes);
+                let rest = without(&after_hole, &board_idxes);
+                let player = evaluate(&[hole.as_slice(), &board].concat());
+                let dealers: Vec<HandRank> = Combinations::new(rest.len(), 2)
+                    .map(|idxes| evaluate(&[pick(&rest, &idxes), board.clone()].concat()))
+                    .collect();
+                let mut showdown = Showdown::default();
+                for dealer in dealers.iter() {
+                    showdown.add(&player, dealer);
+                }
+                for dealer in dealers.iter() {
+                    let mut value = 0.0;
+                    for flop_idxes in Combinations::new(BOARD_NUM, FLOP_NUM) {
+                        let play = if strategy.preflop(&hole) {
+                            4.0
+                        } else if strategy.flop(&hole, &pick(&board, &flop_idxes)) {
+                            2.0
+                        } else if strategy.river(&blind, &player, &showdown) {
+                            1.0
+                        } else {
+                            0.0
+                        };
+                        value += if play == 0.0 {
+                            FOLD
+                        } else {
+                            settle(&blind, play, &player, dealer)
+                        };
+                    }
+                    combinations += 1;
+                    total += value / 10.0;
+                }
+            }
+        }
+
+        let edge = ante_blind_play(&deck, &blind, &strategy);
+        assert_eq!(edge.combinations, combinations);
+        assert!((edge.expected_return - total / (combinations as f64)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn trips_test() {
+        let deck = small_deck();
+        let pay_table = TripsPayTable::standard();
+        let mut combinations = 0u64;
+        let mut total = 0.0;
+        for idxes in Combinations::new(deck.len(), 7) {
+            combinations += 1;
+            total += pay_table.payout(&evaluate(&pick(&deck, &idxes)));
+        }
+        let edge = trips(&deck, &pay_table);
+        // each seven cards shows up once per choice of hole
+        assert_eq!(edge.combinations, combinations * 21);
+        assert!((edge.expected_return - total / (combinations as f64)).abs() < 1e-9);
+    }
+    // A few minutes even optimized: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn ante_blind_play_full_deck_test() {
+        let edge = ante_blind_play(all_cards(), &BlindPayTable::standard(), &BasicStrategy);
+        // every hole, board and dealer hole: C(52, 2) * C(50, 5) * C(45, 2)
+        assert_eq!(edge.combinations, 1326 * 2_118_760 * 990);
+        // Optimal play gives the published 2.185% of the ante. The simple flop
+        // rules give a little of it back, staying under the 2.43% published
+        // for the simple strategy they follow.
+        let house_edge = edge.house_edge();
+        assert!(
+            house_edge > 0.02185 && house_edge < 0.0243,
+            "{}",
+            house_edge
+        );
+        assert!((house_edge - 0.023239).abs() < 1e-6, "{}", house_edge);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod game;
+pub mod house_edge;
+pub mod rule;
@@ -0,0 +1,298 @@
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{evaluate, rank, Category, HandRank, ACE_RANK};
+
+// Dealer needs a pair or better for the ante to play.
+pub const DEALER_QUALIFY: HandRank = HandRank::lowest(Category::OnePair);
+
+pub fn dealer_qualifies(dealer: &HandRank) -> bool {
+    *dealer >= DEALER_QUALIFY
+}
+
+// Paid to one on the blind when the player wins with a straight or better.
+#[derive(Clone, Debug)]
+pub struct BlindPayTable {
+    pub royal_flush: f64,
+    pub straight_flush: f64,
+    pub four_of_a_kind: f64,
+    pub full_house: f64,
+    pub flush: f64,
+    pub straight: f64,
+}
+
+impl BlindPayTable {
+    pub fn standard() -> BlindPayTable {
+        BlindPayTable {
+            royal_flush: 500.0,
+            straight_flush: 50.0,
+            four_of_a_kind: 10.0,
+            full_house: 3.0,
+            flush: 1.5,
+            straight: 1.0,
+        }
+    }
+
+    // Blind result on a winning hand, zero is a push.
+    pub fn payout(&self, player: &HandRank) -> f64 {
+        if player.is_royal_flush() {
+            return self.royal_flush;
+        }
+        match player.category() {
+            Category::StraightFlush => self.straight_flush,
+            Category::FourOfAKind => self.four_of_a_kind,
+            Category::FullHouse => self.full_house,
+            Category::Flush => self.flush,
+            Category::Straight => self.straight,
+            _ => 0.0,
+        }
+    }
+}
+
+// Trips pays on the player's final hand regardless of the dealer.
+#[derive(Clone, Debug)]
+pub struct TripsPayTable {
+    pub royal_flush: f64,
+    pub straight_flush: f64,
+    pub four_of_a_kind: f64,
+    pub full_house: f64,
+    pub flush: f64,
+    pub straight: f64,
+    pub three_of_a_kind: f64,
+}
+
+impl TripsPayTable {
+    pub fn standard() -> TripsPayTable {
+        TripsPayTable {
+            royal_flush: 50.0,
+            straight_flush: 40.0,
+            four_of_a_kind: 30.0,
+            full_house: 8.0,
+            flush: 6.0,
+            straight: 5.0,
+            three_of_a_kind: 3.0,
+        }
+    }
+
+    // Net result per unit bet.
+    pub fn payout(&self, player: &HandRank) -> f64 {
+        if player.is_royal_flush() {
+            return self.royal_flush;
+        }
+        match player.category() {
+            Category::StraightFlush => self.straight_flush,
+            Category::FourOfAKind => self.four_of_a_kind,
+            Category::FullHouse => self.full_house,
+            Category::Flush => self.flush,
+            Category::Straight => self.straight,
+            Category::ThreeOfAKind => self.three_of_a_kind,
+            _ => -1.0,
+        }
+    }
+}
+
+// How the unseen dealer hands stack up against a finished player hand.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Showdown {
+    pub win_qualified: u64,
+    pub win_unqualified: u64,
+    pub tie: u64,
+    pub lose_qualified: u64,
+    pub lose_unqualified: u64,
+}
+
+impl Showdown {
+    pub fn add(&mut self, player: &HandRank, dealer: &HandRank) {
+        let qualified = dealer_qualifies(dealer);
+        if player > dealer {
+            if qualified {
+                self.win_qualified += 1;
+            } else {
+                self.win_unqualified += 1;
+            }
+        } else if player < dealer {
+            if qualified {
+                self.lose_qualified += 1;
+            } else {
+                self.lose_unqualified += 1;
+            }
+        } else {
+            self.tie += 1;
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.win_qualified
+            + self.win_unqualified
+            + self.tie
+            + self.lose_qualified
+            + self.lose_unqualified
+    }
+}
+
+// Net result of ante, blind and a play bet of `play` antes, per unit ante.
+pub fn settle(blind: &BlindPayTable, play: f64, player: &HandRank, dealer: &HandRank) -> f64 {
+    let mut showdown = Showdown::default();
+    showdown.add(player, dealer);
+    expected(blind, play, player, &showdown)
+}
+
+// Expected net of ante, blind and play over a showdown distribution.
+pub fn expected(blind: &BlindPayTable, play: f64, player: &HandRank, showdown: &Showdown) -> f64 {
+    let blind_win = blind.payout(player);
+    let win = play + blind_win;
+    let lose = -play - 1.0;
+    let total = (showdown.win_qualified as f64) * (win + 1.0)
+        + (showdown.win_unqualified as f64) * win
+        + (showdown.lose_qualified as f64) * (lose - 1.0)
+        + (showdown.lose_unqualified as f64) * lose;
+    total / (showdown.total() as f64)
+}
+
+// Folding gives up both ante and blind.
+pub const FOLD: f64 = -2.0;
+
+pub trait Strategy {
+    // Raise 4x before the flop.
+    fn preflop(&self, hole: &[Card; 2]) -> bool;
+    // Raise 2x on the flop.
+    fn flop(&self, hole: &[Card; 2], flop: &[Card]) -> bool;
+    // Raise 1x on the river, otherwise fold.
+    fn river(&self, blind: &BlindPayTable, player: &HandRank, showdown: &Showdown) -> bool;
+}
+
+// Simple preflop and flop rules, with the river played off the exact dealer
+// distribution.
+pub struct BasicStrategy;
+
+impl Strategy for BasicStrategy {
+    fn preflop(&self, hole: &[Card; 2]) -> bool {
+        let high = rank(&hole[0].number).max(rank(&hole[1].number));
+        let low = rank(&hole[0].number).min(rank(&hole[1].number));
+        let suited = hole[0].suit.order() == hole[1].suit.order();
+        if high == low {
+            // threes or better
+            return high >= 1;
+        }
+        match high {
+            ACE_RANK => true,
+            11 => suited || low >= 3, // king: suited, or five and up
+            10 => low >= if suited { 4 } else { 6 }, // queen: 6s / 8o
+            9 => low >= if suited { 6 } else { 8 }, // jack: 8s / To
+            _ => false,
+        }
+    }
+
+    fn flop(&self, hole: &[Card; 2], flop: &[Card]) -> bool {
+        let cards = [hole.as_slice(), flop].concat();
+        let hand = evaluate(&cards);
+        if hand.category() >= Category::TwoPair {
+            return true;
+        }
+        let hole_ranks = [rank(&hole[0].number), rank(&hole[1].number)];
+        let board_ranks: Vec<u8> = flop.iter().map(|card| rank(&card.number)).collect();
+        // hidden pair, except pocket deuces
+        if hole_ranks[0] == hole_ranks[1] {
+            return hole_ranks[0] > 0;
+        }
+        if hole_ranks.iter().any(|rank| board_ranks.contains(rank)) {
+            return true;
+        }
+        // four to a flush with a hidden ten or better
+        hole.iter().any(|card| {
+            let suited = cards
+                .iter()
+                .filter(|other| other.suit.order() == card.suit.order())
+                .count();
+            suited >= 4
+                && hole.iter().any(|hidden| {
+                    hidden.suit.order() == card.suit.order() && rank(&hidden.number) >= 8
+                })
+        })
+    }
+
+    fn river(&self, blind: &BlindPayTable, player: &HandRank, showdown: &Showdown) -> bool {
+        expected(blind, 1.0, player, showdown) > FOLD
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    #[test]
+    fn rule_payout_test() {
+        let royal = HandRank::new(Category::StraightFlush, &[ACE_RANK]);
+        let flush = HandRank::new(Category::Flush, &[10, 8, 5, 3, 1]);
+        let pair = HandRank::new(Category::OnePair, &[2, 10, 8, 5]);
+        let ace_high = HandRank::new(Category::HighCard, &[ACE_RANK, 8, 5, 3, 1]);
+        assert_eq!(BlindPayTable::standard().payout(&royal), 500.0);
+        assert_eq!(BlindPayTable::standard().payout(&flush), 1.5);
+        assert_eq!(BlindPayTable::standard().payout(&pair), 0.0);
+        assert_eq!(TripsPayTable::standard().payout(&pair), -1.0);
+        assert!(dealer_qualifies(&pair));
+        assert!(!dealer_qualifies(&ace_high));
+    }
+
+    #[test]
+    fn rule_settle_test() {
+        let blind = BlindPayTable::standard();
+        let flush = HandRank::new(Category::Flush, &[10, 8, 5, 3, 1]);
+        let pair = HandRank::new(Category::OnePair, &[2, 10, 8, 5]);
+        let ace_high = HandRank::new(Category::HighCard, &[ACE_RANK, 8, 5, 3, 1]);
+        // flush beats a qualified dealer: ante, play and blind 3:2
+        assert_eq!(settle(&blind, 4.0, &flush, &pair), 1.0 + 4.0 + 1.5);
+        // pair beats an unqualified dealer: ante pushes, blind pushes
+        assert_eq!(settle(&blind, 1.0, &pair, &ace_high), 1.0);
+        // loses to a qualified dealer: ante, play and blind lost
+        assert_eq!(settle(&blind, 2.0, &ace_high, &pair), -4.0);
+        assert_eq!(settle(&blind, 2.0, &pair, &pair), 0.0);
+    }
+
+    #[test]
+    fn rule_strategy_test() {
+        let strategy = BasicStrategy;
+        let hole = [
+            Card::new(Suit::Spade, Number::King),
+            Card::new(Suit::Spade, Number::Two),
+        ];
+        assert!(strategy.preflop(&hole));
+        let hole = [
+            Card::new(Suit::Spade, Number::Queen),
+            Card::new(Suit::Heart, Number::Seven),
+        ];
+        assert!(!strategy.preflop(&hole));
+        let hole = [
+            Card::new(Suit::Spade, Number::Two),
+            Card::new(Suit::Heart, Number::Two),
+        ];
+        assert!(!strategy.preflop(&hole));
+
+        // Q, 7 with a seven on the flop
+        let hole = [
+            Card::new(Suit::Spade, Number::Queen),
+            Card::new(Suit::Heart, Number::Seven),
+        ];
+        let flop = [
+            Card::new(Suit::Club, Number::Seven),
+            Card::new(Suit::Heart, Number::Three),
+            Card::new(Suit::Diamond, Number::King),
+        ];
+        assert!(strategy.flop(&hole, &flop));
+        // pair on board only
+        let flop = [
+            Card::new(Suit::Club, Number::Four),
+            Card::new(Suit::Heart, Number::Four),
+            Card::new(Suit::Diamond, Number::King),
+        ];
+        assert!(!strategy.flop(&hole, &flop));
+        // four to a flush with a hidden queen
+        let flop = [
+            Card::new(Suit::Spade, Number::Four),
+            Card::new(Suit::Spade, Number::Nine),
+            Card::new(Suit::Spade, Number::Two),
+        ];
+        assert!(strategy.flop(&hole, &flop));
+    }
+}
@@ -0,0 +1,98 @@
+use super::pay_table::{PayHand, PayTable};
+use super::strategy::{best_hold, Hold, HAND_NUM};
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::deck::Deck as DeckTrait;
+
+pub struct Result {
+    pub dealt: [Card; 5],
+    pub hold: Hold,
+    pub hand: [Card; 5],
+    pub pay_hand: PayHand,
+    pub payout: f64, // returned for the bet, zero on a loss
+}
+
+pub struct Game {
+    pub deck: Deck,
+    pub pay_table: PayTable,
+    pub results: Vec<Result>,
+}
+
+impl Game {
+    pub fn new(pay_table: &PayTable) -> Game {
+        Game {
+            deck: pay_table.deck(),
+            pay_table: pay_table.clone(),
+            results: Vec::new(),
+        }
+    }
+
+    // Plays the current deck order as is: the first five cards are dealt and
+    // replacements come off the deck in order. The hold is the best of all 32.
+    pub fn play_dealt_round(&mut self, bet: f64) {
+        let cards = &self.deck.cards;
+        let dealt: [Card; HAND_NUM] = core::array::from_fn(|idx| cards[idx].clone());
+        let (hold, _) = best_hold(&self.pay_table, &dealt, &cards[HAND_NUM..]);
+        let mut replacements = cards[HAND_NUM..].iter();
+        let hand = core::array::from_fn(|idx| {
+            if hold.holds(idx) {
+                dealt[idx].clone()
+            } else {
+                replacements.next().unwrap().clone()
+            }
+        });
+        let pay_hand = self.pay_table.classify(&hand);
+        self.results.push(Result {
+            dealt,
+            hold,
+            hand,
+            pay_hand,
+            payout: bet * self.pay_table.pay(pay_hand),
+        });
+    }
+
+    pub fn play_one_round(&mut self, bet: f64) {
+        self.deck.shuffle();
+        self.play_dealt_round(bet);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::traits::order::Order;
+
+    #[test]
+    fn game_test() {
+        let mut game = Game::new(&PayTable::jacks_or_better());
+
+        // A, 2, 3, 4, 5 of spades
+        game.play_dealt_round(1.0);
+        let result = game.results.last().unwrap();
+        assert_eq!(result.hold, Hold(0b11111));
+        assert_eq!(result.pay_hand, PayHand::StraightFlush);
+        assert_eq!(result.payout, 50.0);
+
+        // A, 2, 3, 4 of spades and A of hearts: four to a straight flush
+        // beats the aces
+        game.deck.swap(4, 13);
+        game.play_dealt_round(1.0);
+        let result = game.results.last().unwrap();
+        assert_eq!(result.hold, Hold(0b01111));
+        assert_eq!(result.hand[4].order(), game.deck.cards[5].order());
+    }
+
+    #[test]
+    fn game_joker_test() {
+        let mut game = Game::new(&PayTable::joker_poker());
+        assert!(game.deck.cards[52].is_joker());
+
+        // A, 2, 3, 4 of spades and the joker as the five
+        game.deck.swap(4, 52);
+        game.play_dealt_round(1.0);
+        let result = game.results.last().unwrap();
+        assert_eq!(result.hold, Hold(0b11111));
+        assert_eq!(result.pay_hand, PayHand::StraightFlush);
+        assert_eq!(result.payout, 50.0);
+    }
+}
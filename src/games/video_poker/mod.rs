@@ -0,0 +1,4 @@
+pub mod game;
+pub mod pay_table;
+pub mod rtp;
+pub mod strategy;
@@ -0,0 +1,530 @@
+use crate::cards::card::Card;
+use crate::cards::deck::Deck;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::{evaluate_ranks, rank, Category, HandRank, ACE_RANK, WILD_RANK};
+
+const HAND_NUM: usize = 5;
+const DEUCE_RANK: u8 = 0;
+const TEN_RANK: u8 = 8;
+const JACK_RANK: u8 = 9;
+const KING_RANK: u8 = 11;
+const FOUR_RANK: u8 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Variant {
+    JacksOrBetter,
+    BonusPoker,
+    DoubleDoubleBonus,
+    DeucesWild,
+    JokerPoker,
+}
+
+// Every hand any supported pay table tells apart.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum PayHand {
+    RoyalFlush,
+    FourDeuces,
+    WildRoyalFlush,
+    FiveOfAKind,
+    StraightFlush,
+    FourAcesWithKicker, // 2, 3 or 4 kicker
+    FourLowWithKicker,  // twos to fours with an A, 2, 3 or 4 kicker
+    FourAces,
+    FourLow, // twos to fours
+    FourOfAKind,
+    FullHouse,
+    Flush,
+    Straight,
+    ThreeOfAKind,
+    TwoPair,
+    JacksOrBetter,
+    KingsOrBetter,
+    Nothing,
+}
+
+pub const ALL_PAY_HAND_NUM: usize = 18;
+
+pub static ALL_PAY_HANDS: [PayHand; ALL_PAY_HAND_NUM] = [
+    PayHand::RoyalFlush,
+    PayHand::FourDeuces,
+    PayHand::WildRoyalFlush,
+    PayHand::FiveOfAKind,
+    PayHand::StraightFlush,
+    PayHand::FourAcesWithKicker,
+    PayHand::FourLowWithKicker,
+    PayHand::FourAces,
+    PayHand::FourLow,
+    PayHand::FourOfAKind,
+    PayHand::FullHouse,
+    PayHand::Flush,
+    PayHand::Straight,
+    PayHand::ThreeOfAKind,
+    PayHand::TwoPair,
+    PayHand::JacksOrBetter,
+    PayHand::KingsOrBetter,
+    PayHand::Nothing,
+];
+
+impl Order for PayHand {
+    fn order(&self) -> u64 {
+        match *self {
+            PayHand::RoyalFlush => 0,
+            PayHand::FourDeuces => 1,
+            PayHand::WildRoyalFlush => 2,
+            PayHand::FiveOfAKind => 3,
+            PayHand::StraightFlush => 4,
+            PayHand::FourAcesWithKicker => 5,
+            PayHand::FourLowWithKicker => 6,
+            PayHand::FourAces => 7,
+            PayHand::FourLow => 8,
+            PayHand::FourOfAKind => 9,
+            PayHand::FullHouse => 10,
+            PayHand::Flush => 11,
+            PayHand::Straight => 12,
+            PayHand::ThreeOfAKind => 13,
+            PayHand::TwoPair => 14,
+            PayHand::JacksOrBetter => 15,
+            PayHand::KingsOrBetter => 16,
+            PayHand::Nothing => 17,
+        }
+    }
+}
+
+// Pays are "for one" per unit bet, with the royal at the max-coin rate.
+#[derive(Clone, Debug)]
+pub struct PayTable {
+    pub variant: Variant,
+    pub pays: Vec<(PayHand, f64)>,
+}
+
+impl PayTable {
+    // 9/6
+    pub fn jacks_or_better() -> PayTable {
+        PayTable {
+            variant: Variant::JacksOrBetter,
+            pays: vec![
+                (PayHand::RoyalFlush, 800.0),
+                (PayHand::StraightFlush, 50.0),
+                (PayHand::FourOfAKind, 25.0),
+                (PayHand::FullHouse, 9.0),
+                (PayHand::Flush, 6.0),
+                (PayHand::Straight, 4.0),
+                (PayHand::ThreeOfAKind, 3.0),
+                (PayHand::TwoPair, 2.0),
+                (PayHand::JacksOrBetter, 1.0),
+            ],
+        }
+    }
+
+    // 8/5
+    pub fn bonus_poker() -> PayTable {
+        PayTable {
+            variant: Variant::BonusPoker,
+            pays: vec![
+                (PayHand::RoyalFlush, 800.0),
+                (PayHand::StraightFlush, 50.0),
+                (PayHand::FourAces, 80.0),
+                (PayHand::FourLow, 40.0),
+                (PayHand::FourOfAKind, 25.0),
+                (PayHand::FullHouse, 8.0),
+                (PayHand::Flush, 5.0),
+                (PayHand::Straight, 4.0),
+                (PayHand::ThreeOfAKind, 3.0),
+                (PayHand::TwoPair, 2.0),
+                (PayHand::JacksOrBetter, 1.0),
+            ],
+        }
+    }
+
+    // 9/6
+    pub fn double_double_bonus() -> PayTable {
+        PayTable {
+            variant: Variant::DoubleDoubleBonus,
+            pays: vec![
+                (PayHand::RoyalFlush, 800.0),
+                (PayHand::StraightFlush, 50.0),
+                (PayHand::FourAcesWithKicker, 400.0),
+                (PayHand::FourLowWithKicker, 160.0),
+                (PayHand::FourAces, 160.0),
+                (PayHand::FourLow, 80.0),
+                (PayHand::FourOfAKind, 50.0),
+                (PayHand::FullHouse, 9.0),
+                (PayHand::Flush, 6.0),
+                (PayHand::Straight, 4.0),
+                (PayHand::ThreeOfAKind, 3.0),
+                (PayHand::TwoPair, 1.0),
+                (PayHand::JacksOrBetter, 1.0),
+            ],
+        }
+    }
+
+    // full pay
+    pub fn deuces_wild() -> PayTable {
+        PayTable {
+            variant: Variant::DeucesWild,
+            pays: vec![
+                (PayHand::RoyalFlush, 800.0),
+                (PayHand::FourDeuces, 200.0),
+                (PayHand::WildRoyalFlush, 25.0),
+                (PayHand::FiveOfAKind, 15.0),
+                (PayHand::StraightFlush, 9.0),
+                (PayHand::FourOfAKind, 5.0),
+                (PayHand::FullHouse, 3.0),
+                (PayHand::Flush, 2.0),
+                (PayHand::Straight, 2.0),
+                (PayHand::ThreeOfAKind, 1.0),
+            ],
+        }
+    }
+
+    // Kings or Better, full pay, one joker in the deck
+    pub fn joker_poker() -> PayTable {
+        PayTable {
+            variant: Variant::JokerPoker,
+            pays: vec![
+                (PayHand::RoyalFlush, 800.0),
+                (PayHand::FiveOfAKind, 200.0),
+                (PayHand::WildRoyalFlush, 100.0),
+                (PayHand::StraightFlush, 50.0),
+                (PayHand::FourOfAKind, 20.0),
+                (PayHand::FullHouse, 7.0),
+                (PayHand::Flush, 5.0),
+                (PayHand::Straight, 3.0),
+                (PayHand::ThreeOfAKind, 2.0),
+                (PayHand::TwoPair, 1.0),
+                (PayHand::KingsOrBetter, 1.0),
+            ],
+        }
+    }
+
+    // The deck the game deals from, with its jokers.
+    pub fn deck(&self) -> Deck {
+        match self.variant {
+            Variant::JokerPoker => Deck::with_jokers(1),
+            _ => Deck::new(),
+        }
+    }
+
+    pub fn pay(&self, pay_hand: PayHand) -> f64 {
+        self.pays
+            .iter()
+            .find(|(hand, _)| *hand == pay_hand)
+            .map_or(0.0, |(_, pay)| *pay)
+    }
+
+    pub fn classify(&self, cards: &[Card; 5]) -> PayHand {
+        classify_ranks(
+            self.variant,
+            &core::array::from_fn(|idx| rank(&cards[idx].number)),
+            &core::array::from_fn(|idx| cards[idx].suit.order() as u8),
+        )
+    }
+
+    pub fn payout(&self, cards: &[Card; 5]) -> f64 {
+        self.pay(self.classify(cards))
+    }
+}
+
+fn fits_straight(rank_mask: u16) -> bool {
+    const WHEEL_MASK: u16 = 0b1_0000_0000_1111;
+    (0..=(ACE_RANK - 4)).any(|low| rank_mask & !(0b1_1111 << low) == 0)
+        || rank_mask & !WHEEL_MASK == 0
+}
+
+fn classify_natural(variant: Variant, ranks: &[u8; 5], suits: &[u8; 5]) -> PayHand {
+    pay_hand(variant, evaluate_ranks(ranks, suits))
+}
+
+fn pay_hand(variant: Variant, hand_rank: HandRank) -> PayHand {
+    let top = hand_rank.ranks();
+    match hand_rank.category() {
+        Category::StraightFlush if hand_rank.is_royal_flush() => PayHand::RoyalFlush,
+        Category::StraightFlush => PayHand::StraightFlush,
+        Category::FourOfAKind => {
+            let (quad, kicker) = (top[0], top[1]);
+            let low_quad = quad <= FOUR_RANK;
+            match variant {
+                Variant::DoubleDoubleBonus if quad == ACE_RANK && kicker <= FOUR_RANK => {
+                    PayHand::FourAcesWithKicker
+                }
+                Variant::DoubleDoubleBonus
+                    if low_quad && (kicker <= FOUR_RANK || kicker == ACE_RANK) =>
+                {
+                    PayHand::FourLowWithKicker
+                }
+                Variant::BonusPoker | Variant::DoubleDoubleBonus if quad == ACE_RANK => {
+                    PayHand::FourAces
+                }
+                Variant::BonusPoker | Variant::DoubleDoubleBonus if low_quad => PayHand::FourLow,
+                _ => PayHand::FourOfAKind,
+            }
+        }
+        Category::FullHouse => PayHand::FullHouse,
+        Category::Flush => PayHand::Flush,
+        Category::Straight => PayHand::Straight,
+        Category::ThreeOfAKind => PayHand::ThreeOfAKind,
+        Category::TwoPair => PayHand::TwoPair,
+        Category::OnePair if variant == Variant::JokerPoker && top[0] >= KING_RANK => {
+            PayHand::KingsOrBetter
+        }
+        Category::OnePair if variant == Variant::JokerPoker => PayHand::Nothing,
+        Category::OnePair if top[0] >= JACK_RANK => PayHand::JacksOrBetter,
+        _ => PayHand::Nothing,
+    }
+}
+
+// Deuces play as whatever makes the best hand, five of a kind included.
+fn classify_deuces_wild(ranks: &[u8; 5], suits: &[u8; 5]) -> PayHand {
+    let wild_num = ranks.iter().filter(|rank| **rank == DEUCE_RANK).count();
+    if wild_num == 4 {
+        return PayHand::FourDeuces;
+    }
+    if wild_num == 0 {
+        return match classify_natural(Variant::DeucesWild, ranks, suits) {
+            PayHand::TwoPair | PayHand::JacksOrBetter => PayHand::Nothing,
+            pay_hand => pay_hand,
+        };
+    }
+
+    let mut counts = [0u8; 13];
+    let mut rank_mask = 0u16;
+    let mut suit = None;
+    let mut suited = true;
+    for (rank, card_suit) in ranks.iter().zip(suits.iter()) {
+        if *rank == DEUCE_RANK {
+            continue;
+        }
+        counts[*rank as usize] += 1;
+        rank_mask |= 1 << rank;
+        suited &= *suit.get_or_insert(*card_suit) == *card_suit;
+    }
+    let natural_num = HAND_NUM - wild_num;
+    let distinct = rank_mask.count_ones() as usize == natural_num;
+    let max_count = *counts.iter().max().unwrap() as usize;
+    let pair_num = counts.iter().filter(|count| **count == 2).count();
+
+    if suited && distinct && rank_mask >> TEN_RANK << TEN_RANK == rank_mask {
+        PayHand::WildRoyalFlush
+    } else if max_count + wild_num >= 5 {
+        PayHand::FiveOfAKind
+    } else if suited && distinct && fits_straight(rank_mask) {
+        PayHand::StraightFlush
+    } else if max_count + wild_num >= 4 {
+        PayHand::FourOfAKind
+    } else if wild_num == 1 && pair_num == 2 {
+        PayHand::FullHouse
+    } else if suited {
+        PayHand::Flush
+    } else if distinct && fits_straight(rank_mask) {
+        PayHand::Straight
+    } else if max_count + wild_num >= 3 {
+        PayHand::ThreeOfAKind
+    } else {
+        PayHand::Nothing
+    }
+}
+
+// The joker plays as whatever makes the best hand, and a royal made with it
+// pays less than a natural one.
+fn classify_joker_poker(ranks: &[u8; 5], suits: &[u8; 5]) -> PayHand {
+    let hand_rank = evaluate_ranks(ranks, suits);
+    match hand_rank.category() {
+        Category::FiveOfAKind => PayHand::FiveOfAKind,
+        Category::StraightFlush if hand_rank.is_royal_flush() && ranks.contains(&WILD_RANK) => {
+            PayHand::WildRoyalFlush
+        }
+        _ => pay_hand(Variant::JokerPoker, hand_rank),
+    }
+}
+
+pub fn classify_ranks(variant: Variant, ranks: &[u8; 5], suits: &[u8; 5]) -> PayHand {
+    match variant {
+        Variant::DeucesWild => classify_deuces_wild(ranks, suits),
+        Variant::JokerPoker => classify_joker_poker(ranks, suits),
+        _ => classify_natural(variant, ranks, suits),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+
+    fn hand(cards: [(Suit, Number); 5]) -> [Card; 5] {
+        cards.map(|(suit, number)| Card::new(suit, number))
+    }
+
+    #[test]
+    fn pay_table_jacks_or_better_test() {
+        let table = PayTable::jacks_or_better();
+        // J, J, 4, 7, 9
+        let cards = hand([
+            (Suit::Spade, Number::Jack),
+            (Suit::Heart, Number::Jack),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::JacksOrBetter);
+        assert_eq!(table.payout(&cards), 1.0);
+        // T, T, 4, 7, 9
+        let cards = hand([
+            (Suit::Spade, Number::Ten),
+            (Suit::Heart, Number::Ten),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::Nothing);
+        assert_eq!(table.payout(&cards), 0.0);
+        // royal
+        let cards = hand([
+            (Suit::Club, Number::Ten),
+            (Suit::Club, Number::Jack),
+            (Suit::Club, Number::Queen),
+            (Suit::Club, Number::King),
+            (Suit::Club, Number::Ace),
+        ]);
+        assert_eq!(table.payout(&cards), 800.0);
+    }
+
+    #[test]
+    fn pay_table_bonus_test() {
+        // A, A, A, A, 3
+        let cards = hand([
+            (Suit::Spade, Number::Ace),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Ace),
+            (Suit::Diamond, Number::Ace),
+            (Suit::Diamond, Number::Three),
+        ]);
+        assert_eq!(PayTable::jacks_or_better().payout(&cards), 25.0);
+        assert_eq!(PayTable::bonus_poker().payout(&cards), 80.0);
+        assert_eq!(PayTable::double_double_bonus().payout(&cards), 400.0);
+
+        // 3, 3, 3, 3, K
+        let cards = hand([
+            (Suit::Spade, Number::Three),
+            (Suit::Heart, Number::Three),
+            (Suit::Club, Number::Three),
+            (Suit::Diamond, Number::Three),
+            (Suit::Diamond, Number::King),
+        ]);
+        assert_eq!(PayTable::bonus_poker().payout(&cards), 40.0);
+        assert_eq!(PayTable::double_double_bonus().payout(&cards), 80.0);
+    }
+
+    #[test]
+    fn pay_table_deuces_wild_test() {
+        let table = PayTable::deuces_wild();
+        // 2, 2, 2, 2, 7
+        let cards = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Two),
+            (Suit::Club, Number::Two),
+            (Suit::Diamond, Number::Two),
+            (Suit::Diamond, Number::Seven),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::FourDeuces);
+        // 2, Q, K, A suited, 2
+        let cards = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Queen),
+            (Suit::Heart, Number::King),
+            (Suit::Heart, Number::Ace),
+            (Suit::Diamond, Number::Two),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::WildRoyalFlush);
+        // 2, 7, 7, 7, 9
+        let cards = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Seven),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::FourOfAKind);
+        // 2, 5, 5, 9, 9
+        let cards = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Five),
+            (Suit::Club, Number::Five),
+            (Suit::Spade, Number::Nine),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::FullHouse);
+        // 2, A, 3, 4, 5 offsuit
+        let cards = hand([
+            (Suit::Spade, Number::Two),
+            (Suit::Heart, Number::Ace),
+            (Suit::Club, Number::Three),
+            (Suit::Spade, Number::Four),
+            (Suit::Diamond, Number::Five),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::Straight);
+        // K, K, 7, 7, 4: two pair pays nothing
+        let cards = hand([
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::King),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Four),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::Nothing);
+    }
+
+    #[test]
+    fn pay_table_joker_poker_test() {
+        let table = PayTable::joker_poker();
+        let joker = Card::joker(0).unwrap();
+        // joker, 7, 7, 7, 7
+        let mut cards = hand([
+            (Suit::Spade, Number::Seven),
+            (Suit::Heart, Number::Seven),
+            (Suit::Club, Number::Seven),
+            (Suit::Diamond, Number::Seven),
+            (Suit::Diamond, Number::Two),
+        ]);
+        cards[4] = joker.clone();
+        assert_eq!(table.classify(&cards), PayHand::FiveOfAKind);
+        assert_eq!(table.payout(&cards), 200.0);
+        // joker, J, Q, K, A of hearts
+        let mut cards = hand([
+            (Suit::Heart, Number::Jack),
+            (Suit::Heart, Number::Queen),
+            (Suit::Heart, Number::King),
+            (Suit::Heart, Number::Ace),
+            (Suit::Heart, Number::Two),
+        ]);
+        cards[4] = joker.clone();
+        assert_eq!(table.classify(&cards), PayHand::WildRoyalFlush);
+        // T, J, Q, K, A of hearts
+        cards[4] = Card::new(Suit::Heart, Number::Ten);
+        assert_eq!(table.classify(&cards), PayHand::RoyalFlush);
+        assert_eq!(table.payout(&cards), 800.0);
+        // joker, K, 4, 7, 9: the joker pairs the king
+        let mut cards = hand([
+            (Suit::Spade, Number::King),
+            (Suit::Heart, Number::Four),
+            (Suit::Club, Number::Seven),
+            (Suit::Spade, Number::Nine),
+            (Suit::Diamond, Number::Two),
+        ]);
+        cards[4] = joker;
+        assert_eq!(table.classify(&cards), PayHand::KingsOrBetter);
+        assert_eq!(table.payout(&cards), 1.0);
+        // Q, Q, 4, 7, 9: queens pay nothing
+        let cards = hand([
+            (Suit::Spade, Number::Queen),
+            (Suit::Heart, Number::Queen),
+            (Suit::Club, Number::Four),
+            (Suit::Spade, Number::Seven),
+            (Suit::Diamond, Number::Nine),
+        ]);
+        assert_eq!(table.classify(&cards), PayHand::Nothing);
+
+        assert_eq!(table.deck().cards.len(), 53);
+        assert_eq!(PayTable::jacks_or_better().deck().cards.len(), 52);
+    }
+}
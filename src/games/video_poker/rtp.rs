@@ -0,0 +1,113 @@
+use super::pay_table::ALL_PAY_HAND_NUM;
+use super::strategy::{Analyzer, HAND_NUM};
+use crate::games::poker::enumerate::suit_classes;
+
+#[derive(Clone, Debug)]
+pub struct Rtp {
+    pub deals: u64,
+    pub rtp: f64,
+    // chance of finishing on each pay hand, by `PayHand::order()`
+    pub pay_hands: [f64; ALL_PAY_HAND_NUM],
+}
+
+// Return to player with the best hold on every deal of the analyzer's deck.
+// Deals are reduced by suit isomorphism, so the deck has to be closed under
+// suit permutation, jokers aside. Jacks or Better 9/6 comes to 0.995439 on a
+// full deck.
+pub fn rtp(analyzer: &Analyzer) -> Rtp {
+    let mut deals = 0;
+    let mut total = 0.0;
+    let mut pay_hands = [0.0; ALL_PAY_HAND_NUM];
+    for (hand, weight) in suit_classes(analyzer.cards(), HAND_NUM) {
+        let positions: [usize; HAND_NUM] =
+            core::array::from_fn(|idx| analyzer.position(&hand[idx]).unwrap());
+        let (hold, value) = analyzer.best_hold_positions(&positions);
+        let counts = analyzer.hold_counts(&positions, hold);
+        let draws: i64 = counts.iter().sum();
+        for (pay_hand, count) in pay_hands.iter_mut().zip(counts.iter()) {
+            *pay_hand += (weight as f64) * (*count as f64) / (draws as f64);
+        }
+        deals += weight;
+        total += (weight as f64) * value;
+    }
+    for pay_hand in pay_hands.iter_mut() {
+        *pay_hand /= deals as f64;
+    }
+    Rtp {
+        deals,
+        rtp: total / (deals as f64),
+        pay_hands,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::{all_cards, Card};
+    use crate::games::poker::enumerate::Combinations;
+    use crate::games::poker::high::rank;
+    use crate::games::video_poker::pay_table::{PayTable, ALL_PAY_HANDS};
+
+    #[test]
+    fn rtp_test() {
+        // deuces and jacks to aces of every suit
+        let deck: Vec<Card> = all_cards()
+            .iter()
+            .filter(|card| rank(&card.number) == 0 || rank(&card.number) >= 9)
+            .cloned()
+            .collect();
+        let mut joker_deck = deck.clone();
+        joker_deck.push(Card::joker(0).unwrap());
+        for (pay_table, deck) in [
+            (PayTable::bonus_poker(), deck.clone()),
+            (PayTable::deuces_wild(), deck),
+            (PayTable::joker_poker(), joker_deck),
+        ] {
+            let analyzer = Analyzer::new(&pay_table, &deck);
+            let mut deals = 0u64;
+            let mut total = 0.0;
+            for hand in Combinations::new(deck.len(), HAND_NUM) {
+                let hand: [usize; HAND_NUM] = core::array::from_fn(|idx| hand[idx]);
+                total += analyzer.best_hold_positions(&hand).1;
+                deals += 1;
+            }
+            let result = rtp(&analyzer);
+            assert_eq!(result.deals, deals);
+            assert!((result.rtp - total / (deals as f64)).abs() < 1e-9);
+            assert!((result.pay_hands.iter().sum::<f64>() - 1.0).abs() < 1e-9);
+            let pay_return: f64 = result
+                .pay_hands
+                .iter()
+                .zip(ALL_PAY_HANDS.iter())
+                .map(|(chance, pay_hand)| chance * analyzer.pay_table.pay(*pay_hand))
+                .sum();
+            assert!((pay_return - result.rtp).abs() < 1e-9);
+        }
+    }
+
+    // Under a minute optimized: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn rtp_jacks_or_better_full_deck_test() {
+        let pay_table = PayTable::jacks_or_better();
+        let analyzer = Analyzer::new(&pay_table, &pay_table.deck().cards);
+        let result = rtp(&analyzer);
+        // C(52, 5)
+        assert_eq!(result.deals, 2_598_960);
+        // the published 99.5439% of 9/6
+        assert!((result.rtp - 0.995439).abs() < 1e-6, "{}", result.rtp);
+    }
+
+    // Under a minute optimized: cargo test -- --ignored
+    #[test]
+    #[ignore]
+    fn rtp_joker_poker_full_deck_test() {
+        let pay_table = PayTable::joker_poker();
+        let analyzer = Analyzer::new(&pay_table, &pay_table.deck().cards);
+        let result = rtp(&analyzer);
+        // C(53, 5)
+        assert_eq!(result.deals, 2_869_685);
+        // the published 100.6463% of full pay Kings or Better
+        assert!((result.rtp - 1.006463).abs() < 1e-6, "{}", result.rtp);
+    }
+}
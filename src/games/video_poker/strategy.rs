@@ -0,0 +1,342 @@
+use super::pay_table::{classify_ranks, PayTable, ALL_PAY_HANDS, ALL_PAY_HAND_NUM};
+use crate::cards::card::Card;
+use crate::cards::traits::order::Order;
+use crate::games::poker::high::rank;
+
+pub const HAND_NUM: usize = 5;
+pub const HOLD_NUM: usize = 1 << HAND_NUM;
+
+// Bit `idx` set keeps the dealt card at `idx`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Hold(pub u8);
+
+impl Hold {
+    pub fn holds(&self, idx: usize) -> bool {
+        self.0 & (1 << idx) != 0
+    }
+
+    pub fn held_num(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+}
+
+fn binomials(n: usize) -> Vec<[u64; HAND_NUM + 1]> {
+    let mut table = vec![[0u64; HAND_NUM + 1]; n + 1];
+    table[0][0] = 1;
+    for i in 1..=n {
+        table[i][0] = 1;
+        for k in 1..=HAND_NUM {
+            table[i][k] = table[i - 1][k - 1] + table[i - 1][k];
+        }
+    }
+    table
+}
+
+fn for_each_draw<F: FnMut(&[usize])>(rest_num: usize, draw_num: usize, f: &mut F) {
+    fn fill<F: FnMut(&[usize])>(
+        drawn: &mut [usize; HAND_NUM],
+        len: usize,
+        start: usize,
+        rest_num: usize,
+        draw_num: usize,
+        f: &mut F,
+    ) {
+        if len == draw_num {
+            f(&drawn[..len]);
+            return;
+        }
+        for idx in start..=(rest_num + len - draw_num) {
+            drawn[len] = idx;
+            fill(drawn, len + 1, idx + 1, rest_num, draw_num, f);
+        }
+    }
+    let mut drawn = [0usize; HAND_NUM];
+    fill(&mut drawn, 0, 0, rest_num, draw_num, f);
+}
+
+// Expected return of every hold, indexed by the hold mask, drawing from `rest`
+// (the deck without the dealt hand). Every one of the 32 holds is played out
+// against every possible draw.
+pub fn hold_values(
+    pay_table: &PayTable,
+    hand: &[Card; HAND_NUM],
+    rest: &[Card],
+) -> [f64; HOLD_NUM] {
+    let hand_ranks = hand.each_ref().map(|card| rank(&card.number));
+    let hand_suits = hand.each_ref().map(|card| card.suit.order() as u8);
+    let rest_ranks: Vec<u8> = rest.iter().map(|card| rank(&card.number)).collect();
+    let rest_suits: Vec<u8> = rest.iter().map(|card| card.suit.order() as u8).collect();
+    core::array::from_fn(|mask| {
+        let hold = Hold(mask as u8);
+        let held: Vec<usize> = (0..HAND_NUM).filter(|idx| hold.holds(*idx)).collect();
+        let mut ranks = [0u8; HAND_NUM];
+        let mut suits = [0u8; HAND_NUM];
+        for (pos, idx) in held.iter().enumerate() {
+            ranks[pos] = hand_ranks[*idx];
+            suits[pos] = hand_suits[*idx];
+        }
+        let mut total = 0.0;
+        let mut draws = 0;
+        for_each_draw(rest.len(), HAND_NUM - held.len(), &mut |drawn| {
+            for (pos, idx) in drawn.iter().enumerate() {
+                ranks[held.len() + pos] = rest_ranks[*idx];
+                suits[held.len() + pos] = rest_suits[*idx];
+            }
+            total += pay_table.pay(classify_ranks(pay_table.variant, &ranks, &suits));
+            draws += 1;
+        });
+        total / (draws as f64)
+    })
+}
+
+pub fn best_hold(pay_table: &PayTable, hand: &[Card; HAND_NUM], rest: &[Card]) -> (Hold, f64) {
+    best_of(&hold_values(pay_table, hand, rest))
+}
+
+fn best_of(values: &[f64; HOLD_NUM]) -> (Hold, f64) {
+    let mut best = (Hold(0), values[0]);
+    for (mask, value) in values.iter().enumerate() {
+        if *value > best.1 {
+            best = (Hold(mask as u8), *value);
+        }
+    }
+    best
+}
+
+// Every final hand of a deck tallied by pay hand, once per subset of it, so the
+// draws behind any hold come out of a few table lookups.
+pub struct Analyzer {
+    pub pay_table: PayTable,
+    cards: Vec<Card>,
+    positions: Vec<Option<usize>>, // card order -> position in `cards`
+    pays: [f64; ALL_PAY_HAND_NUM],
+    binomials: Vec<[u64; HAND_NUM + 1]>,
+    subset_counts: Vec<Vec<u32>>, // [subset size][colex rank * ALL_PAY_HAND_NUM + pay hand]
+    hand_pays: Vec<u8>,           // pay hand of each five-card hand by colex rank
+}
+
+impl Analyzer {
+    // Tallies all C(n, 5) hands of `cards` up front.
+    pub fn new(pay_table: &PayTable, cards: &[Card]) -> Analyzer {
+        let card_num = cards.len();
+        let binomials = binomials(card_num);
+        let mut positions = Vec::new();
+        for (idx, card) in cards.iter().enumerate() {
+            let order = card.order() as usize;
+            if positions.len() <= order {
+                positions.resize(order + 1, None);
+            }
+            positions[order] = Some(idx);
+        }
+        let pays = core::array::from_fn(|idx| pay_table.pay(ALL_PAY_HANDS[idx]));
+        let ranks: Vec<u8> = cards.iter().map(|card| rank(&card.number)).collect();
+        let suits: Vec<u8> = cards.iter().map(|card| card.suit.order() as u8).collect();
+
+        let mut subset_counts: Vec<Vec<u32>> = (0..HAND_NUM)
+            .map(|size| vec![0; binomials[card_num][size] as usize * ALL_PAY_HAND_NUM])
+            .collect();
+        let mut hand_pays = vec![0u8; binomials[card_num][HAND_NUM] as usize];
+        let mut idxes = [0usize; HAND_NUM];
+        let mut tally_hand = |idxes: &[usize; HAND_NUM]| {
+            let pay_hand = classify_ranks(
+                pay_table.variant,
+                &idxes.map(|idx| ranks[idx]),
+                &idxes.map(|idx| suits[idx]),
+            )
+            .order() as usize;
+            for mask in 0..(HOLD_NUM - 1) {
+                let mut size = 0;
+                let mut colex = 0;
+                for (pos, idx) in idxes.iter().enumerate() {
+                    if mask & (1 << pos) != 0 {
+                        size += 1;
+                        colex += binomials[*idx][size];
+                    }
+                }
+                subset_counts[size][colex as usize * ALL_PAY_HAND_NUM + pay_hand] += 1;
+            }
+            let colex: u64 = idxes
+                .iter()
+                .enumerate()
+                .map(|(pos, idx)| binomials[*idx][pos + 1])
+                .sum();
+            hand_pays[colex as usize] = pay_hand as u8;
+        };
+        for a in 0..card_num {
+            idxes[0] = a;
+            for b in (a + 1)..card_num {
+                idxes[1] = b;
+                for c in (b + 1)..card_num {
+                    idxes[2] = c;
+                    for d in (c + 1)..card_num {
+                        idxes[3] = d;
+                        for e in (d + 1)..card_num {
+                            idxes[4] = e;
+                            tally_hand(&idxes);
+                        }
+                    }
+                }
+            }
+        }
+
+        Analyzer {
+            pay_table: pay_table.clone(),
+            cards: cards.to_vec(),
+            positions,
+            pays,
+            binomials,
+            subset_counts,
+            hand_pays,
+        }
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    pub fn position(&self, card: &Card) -> Option<usize> {
+        self.positions.get(card.order() as usize).copied().flatten()
+    }
+
+    fn colex(&self, sorted: &[usize]) -> usize {
+        sorted
+            .iter()
+            .enumerate()
+            .map(|(pos, idx)| self.binomials[*idx][pos + 1])
+            .sum::<u64>() as usize
+    }
+
+    // Final hands tallied by pay hand over all draws to `hold`, the discarded
+    // cards being gone from the deck.
+    pub fn hold_counts(&self, hand: &[usize; HAND_NUM], hold: Hold) -> [i64; ALL_PAY_HAND_NUM] {
+        let mut counts = [0i64; ALL_PAY_HAND_NUM];
+        let discard = !hold.0 & (HOLD_NUM as u8 - 1);
+        // inclusion-exclusion over the discarded cards
+        let mut removed = discard;
+        loop {
+            let mask = hold.0 | removed;
+            let sign = if removed.count_ones().is_multiple_of(2) {
+                1
+            } else {
+                -1
+            };
+            let mut subset: Vec<usize> = (0..HAND_NUM)
+                .filter(|pos| mask & (1 << pos) != 0)
+                .map(|pos| hand[pos])
+                .collect();
+            subset.sort();
+            let colex = self.colex(&subset);
+            if subset.len() == HAND_NUM {
+                counts[self.hand_pays[colex] as usize] += sign;
+            } else {
+                let row = &self.subset_counts[subset.len()]
+                    [colex * ALL_PAY_HAND_NUM..(colex + 1) * ALL_PAY_HAND_NUM];
+                for (count, subset_count) in counts.iter_mut().zip(row.iter()) {
+                    *count += sign * (*subset_count as i64);
+                }
+            }
+            if removed == 0 {
+                break;
+            }
+            removed = (removed - 1) & discard;
+        }
+        counts
+    }
+
+    // Expected return of every hold, indexed by the hold mask.
+    pub fn hold_values(&self, hand: &[usize; HAND_NUM]) -> [f64; HOLD_NUM] {
+        let draw_from = self.cards.len() - HAND_NUM;
+        core::array::from_fn(|mask| {
+            let hold = Hold(mask as u8);
+            let counts = self.hold_counts(hand, hold);
+            let total: f64 = counts
+                .iter()
+                .zip(self.pays.iter())
+                .map(|(count, pay)| (*count as f64) * pay)
+                .sum();
+            total / (self.binomials[draw_from][HAND_NUM - hold.held_num()] as f64)
+        })
+    }
+
+    pub fn best_hold_positions(&self, hand: &[usize; HAND_NUM]) -> (Hold, f64) {
+        best_of(&self.hold_values(hand))
+    }
+
+    pub fn best_hold(&self, hand: &[Card; HAND_NUM]) -> (Hold, f64) {
+        let positions = hand
+            .each_ref()
+            .map(|card| self.position(card).expect("card is not in the deck"));
+        self.best_hold_positions(&positions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::card::all_cards;
+    use crate::cards::number::Number;
+    use crate::cards::suit::Suit;
+    use crate::games::video_poker::pay_table::PayHand;
+
+    // sevens to aces of every suit
+    fn small_deck() -> Vec<Card> {
+        all_cards()
+            .iter()
+            .filter(|card| rank(&card.number) >= 5)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn hold_values_test() {
+        let mut joker_deck = small_deck();
+        joker_deck.push(Card::joker(0).unwrap());
+        for (pay_table, deck) in [
+            (PayTable::jacks_or_better(), small_deck()),
+            (PayTable::double_double_bonus(), small_deck()),
+            (PayTable::joker_poker(), joker_deck),
+        ] {
+            let analyzer = Analyzer::new(&pay_table, &deck);
+            for hand in [[0, 1, 2, 3, 4], [0, 8, 16, 17, 30], [3, 11, 12, 20, 31]] {
+                let values = analyzer.hold_values(&hand);
+                let cards = hand.map(|idx| deck[idx].clone());
+                let rest: Vec<Card> = (0..deck.len())
+                    .filter(|idx| !hand.contains(idx))
+                    .map(|idx| deck[idx].clone())
+                    .collect();
+                let direct = hold_values(&pay_table, &cards, &rest);
+                for (value, direct) in values.iter().zip(direct.iter()) {
+                    assert!((value - direct).abs() < 1e-9);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn best_hold_test() {
+        let deck = small_deck();
+        let analyzer = Analyzer::new(&PayTable::jacks_or_better(), &deck);
+
+        // a pat straight stays
+        let hand = [
+            Card::new(Suit::Spade, Number::Nine),
+            Card::new(Suit::Heart, Number::Ten),
+            Card::new(Suit::Club, Number::Jack),
+            Card::new(Suit::Spade, Number::Queen),
+            Card::new(Suit::Diamond, Number::King),
+        ];
+        assert_eq!(analyzer.best_hold(&hand).0, Hold(0b11111));
+
+        // four to a royal beats a made flush
+        let hand = [
+            Card::new(Suit::Spade, Number::Ten),
+            Card::new(Suit::Spade, Number::Jack),
+            Card::new(Suit::Spade, Number::Queen),
+            Card::new(Suit::Spade, Number::King),
+            Card::new(Suit::Spade, Number::Seven),
+        ];
+        let (hold, value) = analyzer.best_hold(&hand);
+        assert_eq!(hold, Hold(0b01111));
+        assert!(value > PayTable::jacks_or_better().pay(PayHand::Flush));
+    }
+}
@@ -2,4 +2,5 @@
 extern crate lazy_static;
 
 pub mod cards;
+pub mod error;
 pub mod games;
@@ -0,0 +1,62 @@
+extern crate poker;
+
+use poker::games::baccarat::game::Game;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+// Counts the allocations of the thread making them, so tests running
+// alongside don't add to it. It is this binary's allocator, kept out of the
+// lib tests.
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOC_NUM: Cell<usize> = const { Cell::new(0) };
+}
+
+fn alloc_num() -> usize {
+    ALLOC_NUM.with(|num| num.get())
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOC_NUM.try_with(|num| num.set(num.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOC_NUM.try_with(|num| num.set(num.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAlloc = CountingAlloc;
+
+#[test]
+fn game_reset_alloc_test() {
+    let mut game = Game::new(8);
+    game.skip_results = true;
+    let play_shoe = |game: &mut Game| {
+        game.reset();
+        let mut round_num = 0;
+        while game.play_one_round().is_ok() {
+            round_num += 1;
+        }
+        round_num
+    };
+    // the first shoe warms up the buffers
+    play_shoe(&mut game);
+    let before = alloc_num();
+    let round_num: usize = (0..10).map(|_| play_shoe(&mut game)).sum();
+    assert_eq!(alloc_num(), before);
+    assert!(round_num > 10 * 70);
+    // a snapshot shares the order, so the next reset has to copy it
+    let snapshot = game.snapshot();
+    play_shoe(&mut game);
+    assert!(alloc_num() > before);
+    drop(snapshot);
+}